@@ -0,0 +1,53 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use pymonet::blend::{self, Harmonizer};
+use std::hint::black_box;
+
+const SOURCE: [u8; 4] = [0xff, 0x42, 0x85, 0xf4];
+const DESIGN_COLORS: [[u8; 4]; 6] = [
+    [0xff, 0xff, 0x00, 0x00],
+    [0xff, 0x00, 0xff, 0x00],
+    [0xff, 0xff, 0xff, 0x00],
+    [0xff, 0x9a, 0x6b, 0x6b],
+    [0xff, 0x00, 0x80, 0x80],
+    [0xff, 0x80, 0x00, 0x80],
+];
+
+fn harmonize(c: &mut Criterion) {
+    c.bench_function("blend::harmonize", |b| {
+        b.iter(|| {
+            for design in DESIGN_COLORS {
+                black_box(blend::harmonize(black_box(design), black_box(SOURCE)));
+            }
+        })
+    });
+    c.bench_function("Harmonizer::harmonize", |b| {
+        b.iter(|| {
+            let harmonizer = Harmonizer::new(black_box(SOURCE));
+            for design in DESIGN_COLORS {
+                black_box(harmonizer.harmonize(black_box(design)));
+            }
+        })
+    });
+}
+
+fn blends(c: &mut Criterion) {
+    let (from, to) = (DESIGN_COLORS[0], SOURCE);
+    c.bench_function("blend::cam16ucs", |b| {
+        b.iter(|| blend::cam16ucs(black_box(from), black_box(to), black_box(0.5)))
+    });
+    c.bench_function("blend::hct_hue", |b| {
+        b.iter(|| blend::hct_hue(black_box(from), black_box(to), black_box(0.5)))
+    });
+    c.bench_function("blend::hct_tone", |b| {
+        b.iter(|| blend::hct_tone(black_box(from), black_box(to), black_box(0.5)))
+    });
+    c.bench_function("blend::oklab", |b| {
+        b.iter(|| blend::oklab(black_box(from), black_box(to), black_box(0.5)))
+    });
+    c.bench_function("blend::gradient 16 steps", |b| {
+        b.iter(|| blend::gradient(black_box(from), black_box(to), 16))
+    });
+}
+
+criterion_group!(benches, harmonize, blends);
+criterion_main!(benches);
@@ -0,0 +1,73 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use pymonet::hct::cam16::Cam16;
+use pymonet::hct::viewing_conditions::ViewingConditions;
+use pymonet::hct::Hct;
+use pymonet::utils::color::{y_from_lstar, WHITE_POINT_D65};
+use std::f64::consts::PI;
+use std::hint::black_box;
+
+/// Colors spread over the sRGB cube, so no single branch of the models dominates.
+fn colors() -> Vec<[u8; 4]> {
+    (0..=255u8)
+        .step_by(51)
+        .flat_map(|r| (0..=255u8).step_by(51).map(move |g| (r, g)))
+        .flat_map(|(r, g)| (0..=255u8).step_by(51).map(move |b| [0xff, r, g, b]))
+        .collect()
+}
+
+fn cam16_from_argb(c: &mut Criterion) {
+    let colors = colors();
+    c.bench_function("Cam16::from_argb", |b| {
+        b.iter(|| {
+            for &argb in &colors {
+                black_box(Cam16::from_argb(black_box(argb)));
+            }
+        })
+    });
+    // The same conversions, but rebuilding the default conditions instead of borrowing the cached
+    // ones.
+    let adapting_luminance = 200.0 / PI * y_from_lstar(50.0) / 100.0;
+    c.bench_function("Cam16::from_argb rebuilt conditions", |b| {
+        b.iter(|| {
+            for &argb in &colors {
+                let conditions =
+                    ViewingConditions::make(WHITE_POINT_D65, adapting_luminance, 50.0, 2.0, false);
+                black_box(Cam16::from_argb_in_viewing_conditions(
+                    black_box(argb),
+                    &conditions,
+                ));
+            }
+        })
+    });
+}
+
+fn hct_from(c: &mut Criterion) {
+    let requests: Vec<_> = colors()
+        .into_iter()
+        .map(|argb| {
+            let hct = Hct::from_int(argb);
+            (hct.hue(), hct.chroma(), hct.tone())
+        })
+        .collect();
+    c.bench_function("Hct::from", |b| {
+        b.iter(|| {
+            for &(hue, chroma, tone) in &requests {
+                black_box(Hct::from(
+                    black_box(hue),
+                    black_box(chroma),
+                    black_box(tone),
+                ));
+            }
+        })
+    });
+    c.bench_function("Hct::from tonal ramp", |b| {
+        b.iter(|| {
+            for tone in 0..=100 {
+                black_box(Hct::from(black_box(282.0), black_box(36.0), tone as f64));
+            }
+        })
+    });
+}
+
+criterion_group!(benches, cam16_from_argb, hct_from);
+criterion_main!(benches);
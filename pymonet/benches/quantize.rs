@@ -0,0 +1,48 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use pymonet::quantize::celebi;
+use pymonet::utils::color::{lab_from_argb, labs_from_argb, lstar_from_argb, lstars_from_argb};
+use std::hint::black_box;
+
+/// A 128x128 image: smooth gradients with enough distinct colors to keep the quantizers busy.
+fn pixels() -> Vec<[u8; 4]> {
+    (0..128u32)
+        .flat_map(|y| (0..128u32).map(move |x| [0xff, (x * 2) as u8, (y * 2) as u8, (x + y) as u8]))
+        .collect()
+}
+
+fn lab_conversion(c: &mut Criterion) {
+    let pixels = pixels();
+    let mut out = vec![[0.0; 3]; pixels.len()];
+    c.bench_function("lab_from_argb per pixel", |b| {
+        b.iter(|| {
+            for (out, &argb) in out.iter_mut().zip(&pixels) {
+                *out = lab_from_argb(black_box(argb));
+            }
+        })
+    });
+    c.bench_function("labs_from_argb", |b| {
+        b.iter(|| labs_from_argb(black_box(&pixels), &mut out))
+    });
+
+    let mut lstars = vec![0.0; pixels.len()];
+    c.bench_function("lstar_from_argb per pixel", |b| {
+        b.iter(|| {
+            for (out, &argb) in lstars.iter_mut().zip(&pixels) {
+                *out = lstar_from_argb(black_box(argb));
+            }
+        })
+    });
+    c.bench_function("lstars_from_argb", |b| {
+        b.iter(|| lstars_from_argb(black_box(&pixels), &mut lstars))
+    });
+}
+
+fn quantize(c: &mut Criterion) {
+    let pixels = pixels();
+    c.bench_function("celebi::quantize", |b| {
+        b.iter(|| black_box(celebi::quantize(black_box(&pixels), 128)))
+    });
+}
+
+criterion_group!(benches, lab_conversion, quantize);
+criterion_main!(benches);
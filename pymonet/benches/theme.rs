@@ -0,0 +1,39 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use pymonet::hct::hct_solver;
+use pymonet::utils::theme::Theme;
+use std::hint::black_box;
+
+fn from_source_color(c: &mut Criterion) {
+    c.bench_function("Theme::from_source_color", |b| {
+        b.iter(|| {
+            black_box(Theme::from_source_color(black_box([
+                0xff, 0x42, 0x85, 0xf4,
+            ])))
+        })
+    });
+    hct_solver::enable_cache(1024);
+    c.bench_function("Theme::from_source_color solver cache", |b| {
+        b.iter(|| {
+            black_box(Theme::from_source_color(black_box([
+                0xff, 0x42, 0x85, 0xf4,
+            ])))
+        })
+    });
+    hct_solver::disable_cache();
+}
+
+fn harmonize_custom_colors(c: &mut Criterion) {
+    let theme = Theme::from_source_color([0xff, 0x42, 0x85, 0xf4]);
+    let colors = [
+        ("success", [0xff, 0x00, 0xa0, 0x00]),
+        ("warning", [0xff, 0xf4, 0xb4, 0x00]),
+        ("info", [0xff, 0x00, 0x8b, 0xd8]),
+        ("danger", [0xff, 0xd3, 0x2f, 0x2f]),
+    ];
+    c.bench_function("Theme::harmonize_custom_colors", |b| {
+        b.iter(|| black_box(theme.harmonize_custom_colors(black_box(&colors))))
+    });
+}
+
+criterion_group!(benches, from_source_color, harmonize_custom_colors);
+criterion_main!(benches);
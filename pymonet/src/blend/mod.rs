@@ -1,10 +1,38 @@
-//! A collection of functions for blending in HCT and CAM16.
+//! A collection of functions for blending in HCT, CAM16, and Oklab.
 //!
 //! All formulas here are derived from the original Blend sources in <https://github.com/material-foundation/material-color-utilities>
+//!
+//! Blend amounts are clamped to [0, 1], so that no blend extrapolates past its endpoints into
+//! colors the color models can't represent. A NaN or infinite amount is a bug in the caller: it
+//! fails a debug assertion, and in release builds NaN blends nothing, returning `from` unchanged,
+//! while infinities are clamped like any other amount.
 use crate::hct::cam16::Cam16;
 use crate::hct::Hct;
-use crate::utils::color::lstar_from_argb;
-use crate::utils::math::{difference_degrees, rotation_direction, sanitize_degrees_double};
+use crate::scheme::{Role, Scheme};
+use crate::utils::color::{
+    argb_from_oklab, argb_from_oklch, lstar_from_argb, oklab_from_argb, oklch_from_argb,
+};
+use crate::utils::math::{
+    difference_degrees, lerp_degrees, rotation_direction, sanitize_degrees_double,
+};
+
+/// How far [`harmonize_with_options`] rotates a hue. The defaults are those of [`harmonize`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HarmonizeOptions {
+    /// Fraction of the hue difference to rotate by, ex. 0.5 to meet halfway.
+    pub factor: f64,
+    /// Most degrees to rotate by, ex. 5 for a subtle shift or 30 for an aggressive one.
+    pub max_rotation_degrees: f64,
+}
+
+impl Default for HarmonizeOptions {
+    fn default() -> HarmonizeOptions {
+        HarmonizeOptions {
+            factor: 0.5,
+            max_rotation_degrees: 15.0,
+        }
+    }
+}
 
 /// Blend the design color's HCT hue towards the key color's HCT hue, in a way
 /// that leaves the original color recognizable and recognizably shifted
@@ -20,16 +48,160 @@ use crate::utils::math::{difference_degrees, rotation_direction, sanitize_degree
 /// * The design color with a hue shifted towards the system's color, a
 ///   slightly warmer/cooler variant of the design color's hue.
 pub fn harmonize(design_color: [u8; 4], source_color: [u8; 4]) -> [u8; 4] {
+    harmonize_with_options(design_color, source_color, HarmonizeOptions::default())
+}
+
+/// Like [`harmonize`], with a configurable amount of rotation.
+///
+/// # Arguments
+///
+/// * `design_color`: ARGB representation of an arbitrary color.
+/// * `source_color`: ARGB representation of the main theme color.
+/// * `options`: How far to rotate the hue.
+///
+/// # Returns
+///
+/// * The design color with a hue shifted towards the system's color by
+///   `options.factor` of the difference, up to `options.max_rotation_degrees`.
+pub fn harmonize_with_options(
+    design_color: [u8; 4],
+    source_color: [u8; 4],
+    options: HarmonizeOptions,
+) -> [u8; 4] {
+    rotate_toward_hue(design_color, Hct::from_int(source_color).hue(), options)
+}
+
+fn rotate_toward_hue(design_color: [u8; 4], source_hue: f64, options: HarmonizeOptions) -> [u8; 4] {
     let from_hct = Hct::from_int(design_color);
-    let to_hct = Hct::from_int(source_color);
-    let difference_degrees = difference_degrees(from_hct.hue(), to_hct.hue());
-    let rotation_degrees = (difference_degrees * 0.5).min(15.0);
+    let difference_degrees = difference_degrees(from_hct.hue(), source_hue);
+    let rotation_degrees = (difference_degrees * options.factor).min(options.max_rotation_degrees);
     let output_hue = sanitize_degrees_double(
-        from_hct.hue() + rotation_degrees * rotation_direction(from_hct.hue(), to_hct.hue()),
+        from_hct.hue() + rotation_degrees * rotation_direction(from_hct.hue(), source_hue),
     );
     Hct::from(output_hue, from_hct.chroma(), from_hct.tone()).to_int()
 }
 
+/// Harmonizes many colors toward one source color, analyzing the source only once.
+///
+/// Each result is exactly what [`harmonize`] or [`harmonize_with_options`] would return.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Harmonizer {
+    source_hue: f64,
+    options: HarmonizeOptions,
+}
+
+impl Harmonizer {
+    /// Create a harmonizer like [`harmonize`].
+    ///
+    /// # Arguments
+    ///
+    /// * `source_color`: ARGB representation of the main theme color.
+    pub fn new(source_color: [u8; 4]) -> Harmonizer {
+        Harmonizer::with_options(source_color, HarmonizeOptions::default())
+    }
+
+    /// Create a harmonizer like [`harmonize_with_options`].
+    ///
+    /// # Arguments
+    ///
+    /// * `source_color`: ARGB representation of the main theme color.
+    /// * `options`: How far to rotate hues.
+    pub fn with_options(source_color: [u8; 4], options: HarmonizeOptions) -> Harmonizer {
+        #[cfg(test)]
+        SOURCE_ANALYSES.with(|count| count.set(count.get() + 1));
+        Harmonizer {
+            source_hue: Hct::from_int(source_color).hue(),
+            options,
+        }
+    }
+
+    /// Harmonize one color toward the source color.
+    ///
+    /// # Arguments
+    ///
+    /// * `design_color`: ARGB representation of an arbitrary color.
+    ///
+    /// # Returns
+    ///
+    /// * The design color with a hue shifted towards the source color.
+    pub fn harmonize(&self, design_color: [u8; 4]) -> [u8; 4] {
+        rotate_toward_hue(design_color, self.source_hue, self.options)
+    }
+
+    /// Harmonize several colors toward the source color. With the `rayon` feature the colors
+    /// are processed in parallel.
+    ///
+    /// # Arguments
+    ///
+    /// * `design_colors`: ARGB representations of arbitrary colors.
+    ///
+    /// # Returns
+    ///
+    /// * Each color harmonized, in the order of `design_colors`.
+    pub fn harmonize_all(&self, design_colors: &[[u8; 4]]) -> Vec<[u8; 4]> {
+        #[cfg(feature = "rayon")]
+        {
+            use rayon::prelude::*;
+            design_colors
+                .par_iter()
+                .map(|&design_color| self.harmonize(design_color))
+                .collect()
+        }
+        #[cfg(not(feature = "rayon"))]
+        design_colors
+            .iter()
+            .map(|&design_color| self.harmonize(design_color))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+thread_local! {
+    /// How many times a [`Harmonizer`] has analyzed its source color.
+    pub(crate) static SOURCE_ANALYSES: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+}
+
+/// The roles [`harmonize_scheme`] harmonizes by default: the primary, secondary, and tertiary
+/// families with their containers, the inverse primary, and the surface tint, which follows the
+/// primary.
+pub const ACCENT_ROLES: [Role; 14] = [
+    Role::Primary,
+    Role::OnPrimary,
+    Role::PrimaryContainer,
+    Role::OnPrimaryContainer,
+    Role::Secondary,
+    Role::OnSecondary,
+    Role::SecondaryContainer,
+    Role::OnSecondaryContainer,
+    Role::Tertiary,
+    Role::OnTertiary,
+    Role::TertiaryContainer,
+    Role::OnTertiaryContainer,
+    Role::InversePrimary,
+    Role::SurfaceTint,
+];
+
+/// [`harmonize`] several roles of a scheme toward one color, ex. an app's brand color.
+///
+/// # Arguments
+///
+/// * `scheme`: The scheme to harmonize.
+/// * `toward`: ARGB representation of the color to shift hues toward.
+/// * `roles`: The roles to harmonize, or `None` for the [`ACCENT_ROLES`].
+///
+/// # Returns
+///
+/// * A copy of `scheme` with the listed roles harmonized and every other role, ex. the
+///   surfaces and the error roles, unchanged.
+pub fn harmonize_scheme(scheme: &Scheme, toward: [u8; 4], roles: Option<&[Role]>) -> Scheme {
+    let harmonizer = Harmonizer::new(toward);
+    let mut harmonized = scheme.clone();
+    for &role in roles.unwrap_or(&ACCENT_ROLES) {
+        harmonized[role] = harmonizer.harmonize(scheme[role]);
+    }
+    harmonized
+}
+
 /// Blends hue from one color into another. The chroma and tone of the original color are
 /// maintained.
 ///
@@ -37,18 +209,86 @@ pub fn harmonize(design_color: [u8; 4], source_color: [u8; 4]) -> [u8; 4] {
 ///
 /// * `from`: ARGB representation of color
 /// * `to`: ARGB representation of color
-/// * `amount`: how much blending to perform; 0.0 >= and <= 1.0
+/// * `amount`: how much blending to perform; clamped to 0.0 >= and <= 1.0
 ///
 /// # Returns
 ///
 /// * A shade of `from`, with a hue blended towards ARGB `to`. Chroma and
 ///   tone are constant.
 pub fn hct_hue(from: [u8; 4], to: [u8; 4], amount: f64) -> [u8; 4] {
-    let ucs = cam16ucs(from, to, amount);
-    let ucs_cam = Cam16::from_argb(ucs);
+    hct_hue_with(from, to, amount, HueBlendOptions::default())
+}
+
+/// The tone [`hct_hue_with`] gives its result.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BlendTone {
+    /// The tone of `from`, as in [`hct_hue`].
+    #[default]
+    Keep,
+    /// The tone of `to`.
+    Adopt,
+    /// The tone of `from` blended toward the tone of `to` by the same amount as the hue.
+    Lerp,
+}
+
+/// Options for [`hct_hue_with`]. The defaults are those of [`hct_hue`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct HueBlendOptions {
+    pub tone: BlendTone,
+}
+
+/// Like [`hct_hue`], optionally moving the tone toward `to` as well. The chroma of the
+/// original color is maintained.
+///
+/// # Arguments
+///
+/// * `from`: ARGB representation of color
+/// * `to`: ARGB representation of color
+/// * `amount`: how much blending to perform; clamped to 0.0 >= and <= 1.0
+/// * `options`: which tone the result has
+///
+/// # Returns
+///
+/// * A shade of `from`, with a hue blended towards ARGB `to`, and a tone
+///   chosen by `options.tone`. Chroma is constant.
+pub fn hct_hue_with(from: [u8; 4], to: [u8; 4], amount: f64, options: HueBlendOptions) -> [u8; 4] {
+    let amount = clamp_amount(amount);
     let from_cam = Cam16::from_argb(from);
-    let blended = Hct::from(ucs_cam.hue(), from_cam.chroma(), lstar_from_argb(from));
-    blended.to_int()
+    let to_cam = Cam16::from_argb(to);
+    let from_tone = lstar_from_argb(from);
+    let tone = match options.tone {
+        BlendTone::Keep => from_tone,
+        BlendTone::Adopt => lstar_from_argb(to),
+        BlendTone::Lerp => from_tone + (lstar_from_argb(to) - from_tone) * amount,
+    };
+    hue_between(&from_cam, &to_cam, tone, amount)
+}
+
+/// The blend of [`hct_hue_with`], with both endpoints already analyzed.
+fn hue_between(from_cam: &Cam16, to_cam: &Cam16, tone: f64, amount: f64) -> [u8; 4] {
+    let ucs = ucs_between(from_cam, to_cam, amount);
+    let ucs_cam = Cam16::from_argb(ucs);
+    Hct::from(ucs_cam.hue(), from_cam.chroma(), tone).to_int()
+}
+
+/// Blends tone from one color into another. The hue and chroma of the original color are
+/// maintained, as far as the new tone allows.
+///
+/// # Arguments
+///
+/// * `from`: ARGB representation of color
+/// * `to`: ARGB representation of color
+/// * `amount`: how much blending to perform; clamped to 0.0 >= and <= 1.0
+///
+/// # Returns
+///
+/// * A shade of `from`, with a tone blended towards ARGB `to`. Hue and
+///   chroma are constant.
+pub fn hct_tone(from: [u8; 4], to: [u8; 4], amount: f64) -> [u8; 4] {
+    let from_hct = Hct::from_int(from);
+    let to_tone = lstar_from_argb(to);
+    let tone = from_hct.tone() + (to_tone - from_hct.tone()) * clamp_amount(amount);
+    Hct::from(from_hct.hue(), from_hct.chroma(), tone).to_int()
 }
 
 /// Blend in CAM16-UCS space.
@@ -57,7 +297,7 @@ pub fn hct_hue(from: [u8; 4], to: [u8; 4], amount: f64) -> [u8; 4] {
 ///
 /// * `from`: ARGB representation of color
 /// * `to`: ARGB representation of color
-/// * `amount`: how much blending to perform; 0.0 >= and <= 1.0
+/// * `amount`: how much blending to perform; clamped to 0.0 >= and <= 1.0
 ///
 /// # Returns
 ///
@@ -66,6 +306,11 @@ pub fn hct_hue(from: [u8; 4], to: [u8; 4], amount: f64) -> [u8; 4] {
 pub fn cam16ucs(from: [u8; 4], to: [u8; 4], amount: f64) -> [u8; 4] {
     let from_cam = Cam16::from_argb(from);
     let to_cam = Cam16::from_argb(to);
+    ucs_between(&from_cam, &to_cam, clamp_amount(amount))
+}
+
+/// The blend of [`cam16ucs`], with both endpoints already analyzed.
+fn ucs_between(from_cam: &Cam16, to_cam: &Cam16, amount: f64) -> [u8; 4] {
     let from_j = from_cam.jstar();
     let from_a = from_cam.astar();
     let from_b = from_cam.bstar();
@@ -75,12 +320,154 @@ pub fn cam16ucs(from: [u8; 4], to: [u8; 4], amount: f64) -> [u8; 4] {
     let jstar = from_j + (to_j - from_j) * amount;
     let astar = from_a + (to_a - from_a) * amount;
     let bstar = from_b + (to_b - from_b) * amount;
-    Cam16::from_jch(jstar, astar, bstar).to_int()
+    Cam16::from_ucs(jstar, astar, bstar).to_int()
+}
+
+/// How [`gradient_with_mode`] interpolates between its endpoints.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GradientMode {
+    /// Every step is a [`cam16ucs`] blend: hue, chroma, and tone change.
+    #[default]
+    Ucs,
+    /// Every step is an [`hct_hue`] blend: only the hue changes.
+    HctHue,
+    /// Every step is an [`oklch_hue`] blend: only the Oklch hue changes, at an even pace along
+    /// the shorter way around the hue circle.
+    OklchHue,
+}
+
+/// Colors evenly spaced from one color to another in CAM16-UCS, as [`cam16ucs`] would blend
+/// them.
+///
+/// # Arguments
+///
+/// * `from`: ARGB representation of the first color
+/// * `to`: ARGB representation of the last color
+/// * `steps`: how many colors to return
+///
+/// # Returns
+///
+/// * `steps` colors, starting with `from` and ending with `to`. A single step is `from`, and
+///   0 steps are no colors.
+pub fn gradient(from: [u8; 4], to: [u8; 4], steps: usize) -> Vec<[u8; 4]> {
+    gradient_with_mode(from, to, steps, GradientMode::default())
+}
+
+/// Like [`gradient`], blending the way `mode` says. Both endpoints are analyzed only once.
+///
+/// # Arguments
+///
+/// * `from`: ARGB representation of the first color
+/// * `to`: ARGB representation of the last color
+/// * `steps`: how many colors to return
+/// * `mode`: the blend each step is
+///
+/// # Returns
+///
+/// * `steps` colors, from blending `from` by 0 to blending it by 1. A single step is `from`
+///   blended by 0, and 0 steps are no colors.
+pub fn gradient_with_mode(
+    from: [u8; 4],
+    to: [u8; 4],
+    steps: usize,
+    mode: GradientMode,
+) -> Vec<[u8; 4]> {
+    let from_cam = Cam16::from_argb(from);
+    let to_cam = Cam16::from_argb(to);
+    let from_tone = lstar_from_argb(from);
+    let from_lch = oklch_from_argb(from);
+    let to_lch = oklch_from_argb(to);
+    (0..steps)
+        .map(|step| {
+            let amount = if steps > 1 {
+                step as f64 / (steps - 1) as f64
+            } else {
+                0.0
+            };
+            match mode {
+                GradientMode::Ucs => ucs_between(&from_cam, &to_cam, amount),
+                GradientMode::HctHue => hue_between(&from_cam, &to_cam, from_tone, amount),
+                GradientMode::OklchHue => oklch_hue_between(from_lch, to_lch, amount),
+            }
+        })
+        .collect()
+}
+
+/// Blend in Oklab space, the way CSS gradients and `color-mix()` do with `in oklab`.
+///
+/// # Arguments
+///
+/// * `from`: ARGB representation of color
+/// * `to`: ARGB representation of color
+/// * `amount`: how much blending to perform; clamped to 0.0 >= and <= 1.0
+///
+/// # Returns
+///
+/// * A shade of `from`, blended towards ARGB `to`. Hue, chroma, and
+///   lightness will change.
+pub fn oklab(from: [u8; 4], to: [u8; 4], amount: f64) -> [u8; 4] {
+    let from_lab = oklab_from_argb(from);
+    let to_lab = oklab_from_argb(to);
+    let amount = clamp_amount(amount);
+    let [l, a, b] = [0, 1, 2].map(|i| from_lab[i] + (to_lab[i] - from_lab[i]) * amount);
+    argb_from_oklab(l, a, b)
+}
+
+/// Blends Oklch hue from one color into another, along the shorter way around the hue circle.
+/// The Oklch lightness and chroma of the original color are maintained.
+///
+/// # Arguments
+///
+/// * `from`: ARGB representation of color
+/// * `to`: ARGB representation of color; a gray leaves the hue as is
+/// * `amount`: how much blending to perform; clamped to 0.0 >= and <= 1.0
+///
+/// # Returns
+///
+/// * A shade of `from`, with a hue blended towards ARGB `to`, clipped to
+///   the sRGB gamut.
+pub fn oklch_hue(from: [u8; 4], to: [u8; 4], amount: f64) -> [u8; 4] {
+    oklch_hue_between(
+        oklch_from_argb(from),
+        oklch_from_argb(to),
+        clamp_amount(amount),
+    )
+}
+
+/// The blend of [`oklch_hue`], with both endpoints already converted to Oklch.
+fn oklch_hue_between(from_lch: [f64; 3], to_lch: [f64; 3], amount: f64) -> [u8; 4] {
+    /// Below this chroma, a color's hue is noise, as CSS treats it as missing.
+    const ACHROMATIC_CHROMA: f64 = 1e-4;
+
+    let [l, c, from_hue] = from_lch;
+    let [_, to_chroma, to_hue] = to_lch;
+    if c < ACHROMATIC_CHROMA || to_chroma < ACHROMATIC_CHROMA {
+        return argb_from_oklch(l, c, from_hue);
+    }
+    argb_from_oklch(l, c, lerp_degrees(from_hue, to_hue, amount))
+}
+
+/// Clamps a blend amount to [0, 1], treating NaN as 0, as described in the module docs.
+fn clamp_amount(amount: f64) -> f64 {
+    debug_assert!(amount.is_finite(), "blend amount is not finite");
+    if amount.is_nan() {
+        0.0
+    } else {
+        amount.clamp(0.0, 1.0)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::blend::harmonize;
+    use crate::blend::{
+        cam16ucs, gradient, gradient_with_mode, harmonize, harmonize_scheme,
+        harmonize_with_options, hct_hue, hct_hue_with, hct_tone, oklab, oklch_hue, BlendTone,
+        GradientMode, HarmonizeOptions, Harmonizer, HueBlendOptions, ACCENT_ROLES, SOURCE_ANALYSES,
+    };
+    use crate::hct::Hct;
+    use crate::utils::color::oklch_from_argb;
+    use crate::utils::math::difference_degrees;
+    use assert_approx_eq::assert_approx_eq;
 
     const RED: [u8; 4] = [255, 255, 0, 0];
     const BLUE: [u8; 4] = [255, 0, 0, 255];
@@ -119,7 +506,7 @@ mod tests {
 
     #[test]
     fn harmonize_blue_to_yellow() {
-      let val = harmonize(BLUE, YELLOW);
+        let val = harmonize(BLUE, YELLOW);
         assert_eq!(val, [255, 0, 71, 163]);
     }
 
@@ -158,4 +545,263 @@ mod tests {
         let val = harmonize(YELLOW, RED);
         assert_eq!(val, [255, 255, 246, 227]);
     }
+
+    #[test]
+    fn harmonize_default_options() {
+        for (design, source) in [(RED, BLUE), (GREEN, YELLOW), (BLUE, RED)] {
+            assert_eq!(
+                harmonize_with_options(design, source, HarmonizeOptions::default()),
+                harmonize(design, source)
+            );
+        }
+    }
+
+    #[test]
+    fn harmonize_shift_grows_with_cap() {
+        let hue = Hct::from_int(BLUE).hue();
+        let shift = |max_rotation_degrees: f64| {
+            let options = HarmonizeOptions {
+                max_rotation_degrees,
+                ..Default::default()
+            };
+            let harmonized = harmonize_with_options(BLUE, GREEN, options);
+            difference_degrees(Hct::from_int(harmonized).hue(), hue)
+        };
+        let shifts = [0.0, 5.0, 15.0, 30.0].map(shift);
+        assert!(shifts[0] < 1.0);
+        assert!(shifts.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!((shifts[1] - 5.0).abs() < 1.0);
+        assert!((shifts[3] - 30.0).abs() < 1.0);
+
+        let tenth = HarmonizeOptions {
+            factor: 0.1,
+            max_rotation_degrees: 180.0,
+        };
+        let harmonized = Hct::from_int(harmonize_with_options(BLUE, GREEN, tenth)).hue();
+        let difference = difference_degrees(hue, Hct::from_int(GREEN).hue());
+        assert!((difference_degrees(harmonized, hue) - difference * 0.1).abs() < 1.0);
+    }
+
+    #[test]
+    fn cam16ucs_endpoints() {
+        assert_eq!(cam16ucs(RED, BLUE, 0.0), RED);
+        assert_eq!(cam16ucs(RED, BLUE, 1.0), BLUE);
+        assert_eq!(cam16ucs(GREEN, YELLOW, 0.0), GREEN);
+        assert_eq!(cam16ucs(GREEN, YELLOW, 1.0), YELLOW);
+    }
+
+    #[test]
+    fn harmonizer_matches_harmonize() {
+        let colors = [
+            RED,
+            GREEN,
+            BLUE,
+            YELLOW,
+            [255, 0x9a, 0x6b, 0x6b],
+            [255, 128, 128, 128],
+        ];
+        for source in colors {
+            let harmonizer = Harmonizer::new(source);
+            let expected = colors.map(|design| harmonize(design, source));
+            assert_eq!(colors.map(|design| harmonizer.harmonize(design)), expected);
+            assert_eq!(harmonizer.harmonize_all(&colors), expected);
+
+            let options = HarmonizeOptions {
+                factor: 1.0,
+                max_rotation_degrees: 30.0,
+            };
+            let harmonizer = Harmonizer::with_options(source, options);
+            assert_eq!(
+                harmonizer.harmonize_all(&colors),
+                colors.map(|design| harmonize_with_options(design, source, options))
+            );
+        }
+        assert!(Harmonizer::new(RED).harmonize_all(&[]).is_empty());
+    }
+
+    #[test]
+    fn harmonize_scheme_accents_only() {
+        use crate::palettes::core::CorePalette;
+        use crate::scheme::{Role, Scheme};
+
+        let scheme = Scheme::light_from_core_palette(&CorePalette::of(BLUE));
+        let before = SOURCE_ANALYSES.with(|count| count.get());
+        let harmonized = harmonize_scheme(&scheme, RED, None);
+        assert_eq!(SOURCE_ANALYSES.with(|count| count.get()), before + 1);
+        let red_hue = Hct::from_int(RED).hue();
+        for role in Role::iterator() {
+            if ACCENT_ROLES.contains(role) {
+                assert_eq!(harmonized[role], harmonize(scheme[role], RED), "{role}");
+                let before = difference_degrees(Hct::from_int(scheme[role]).hue(), red_hue);
+                let after = difference_degrees(Hct::from_int(harmonized[role]).hue(), red_hue);
+                assert!(
+                    after < before || Hct::from_int(scheme[role]).chroma() < 3.0,
+                    "{role}"
+                );
+            } else {
+                assert_eq!(harmonized[role], scheme[role], "{role}");
+            }
+        }
+        assert_eq!(harmonized.surface_tint, harmonized.primary);
+        assert_ne!(harmonized.primary, scheme.primary);
+
+        let only_primary = harmonize_scheme(&scheme, RED, Some(&[Role::Primary]));
+        assert_eq!(only_primary.differing_roles(&scheme, 0), [Role::Primary]);
+    }
+
+    #[test]
+    fn hct_hue_tone_modes() {
+        // A light pink toward a dark navy.
+        let light = [255, 0xff, 0xd8, 0xe4];
+        let dark = [255, 0x00, 0x1a, 0x41];
+        let tone = |argb: [u8; 4]| Hct::from_int(argb).tone();
+        let blend = |tone: BlendTone| hct_hue_with(light, dark, 0.5, HueBlendOptions { tone });
+
+        assert_eq!(blend(BlendTone::Keep), hct_hue(light, dark, 0.5));
+        assert_approx_eq!(tone(blend(BlendTone::Keep)), tone(light), 0.5);
+        assert_approx_eq!(tone(blend(BlendTone::Adopt)), tone(dark), 0.5);
+        assert_approx_eq!(
+            tone(blend(BlendTone::Lerp)),
+            (tone(light) + tone(dark)) / 2.0,
+            0.5
+        );
+
+        let lerp = |amount: f64| {
+            hct_hue_with(
+                light,
+                dark,
+                amount,
+                HueBlendOptions {
+                    tone: BlendTone::Lerp,
+                },
+            )
+        };
+        assert_eq!(lerp(0.0), hct_hue(light, dark, 0.0));
+        assert_approx_eq!(tone(lerp(1.0)), tone(dark), 0.5);
+    }
+
+    #[test]
+    fn hct_tone_endpoints() {
+        // A muted blue and a light surface.
+        let accent = [255, 0x57, 0x5e, 0x71];
+        let surface = [255, 0xfe, 0xfb, 0xff];
+        let tone = |argb: [u8; 4]| Hct::from_int(argb).tone();
+        assert_eq!(hct_tone(accent, surface, 0.0), accent);
+        assert_approx_eq!(tone(hct_tone(accent, surface, 1.0)), tone(surface), 0.5);
+        assert_approx_eq!(tone(hct_tone(surface, accent, 1.0)), tone(accent), 0.5);
+
+        let dimmed = Hct::from_int(hct_tone(accent, surface, 0.5));
+        let accent = Hct::from_int(accent);
+        assert_approx_eq!(dimmed.tone(), (accent.tone() + tone(surface)) / 2.0, 0.5);
+        assert!(difference_degrees(dimmed.hue(), accent.hue()) < 1.0);
+        assert_approx_eq!(dimmed.chroma(), accent.chroma(), 1.0);
+    }
+
+    #[test]
+    fn gradient_steps() {
+        assert!(gradient(RED, BLUE, 0).is_empty());
+        assert_eq!(gradient(RED, BLUE, 1), [RED]);
+        assert_eq!(gradient(RED, BLUE, 2), [RED, BLUE]);
+        assert_eq!(
+            gradient(RED, BLUE, 3),
+            [RED, cam16ucs(RED, BLUE, 0.5), BLUE]
+        );
+        let steps = gradient(GREEN, YELLOW, 5);
+        for (index, color) in steps.into_iter().enumerate() {
+            assert_eq!(color, cam16ucs(GREEN, YELLOW, index as f64 / 4.0));
+        }
+
+        let hues = gradient_with_mode(RED, BLUE, 3, GradientMode::HctHue);
+        assert_eq!(
+            hues,
+            [0.0, 0.5, 1.0].map(|amount| hct_hue(RED, BLUE, amount))
+        );
+        assert_eq!(
+            gradient_with_mode(RED, BLUE, 1, GradientMode::HctHue),
+            [hct_hue(RED, BLUE, 0.0)]
+        );
+
+        let rose = [255, 154, 107, 107];
+        let hues = gradient_with_mode(rose, BLUE, 5, GradientMode::OklchHue);
+        for (index, color) in hues.into_iter().enumerate() {
+            assert_eq!(color, oklch_hue(rose, BLUE, index as f64 / 4.0));
+        }
+    }
+
+    #[test]
+    fn amounts_are_clamped() {
+        for blend in [cam16ucs, hct_hue, hct_tone, oklab, oklch_hue] {
+            assert_eq!(blend(RED, BLUE, -0.5), blend(RED, BLUE, 0.0));
+            assert_eq!(blend(RED, BLUE, 1.5), blend(RED, BLUE, 1.0));
+            assert_eq!(blend(GREEN, YELLOW, 1e9), blend(GREEN, YELLOW, 1.0));
+        }
+        assert_eq!(cam16ucs(RED, BLUE, -0.5), RED);
+        assert_eq!(cam16ucs(RED, BLUE, 1.5), BLUE);
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "blend amount is not finite")]
+    fn nan_amount_asserts() {
+        cam16ucs(RED, BLUE, f64::NAN);
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "blend amount is not finite")]
+    fn infinite_amount_asserts() {
+        cam16ucs(RED, BLUE, f64::INFINITY);
+    }
+
+    #[cfg(not(debug_assertions))]
+    #[test]
+    fn non_finite_amounts_in_release() {
+        for blend in [cam16ucs, hct_hue, hct_tone, oklab, oklch_hue] {
+            assert_eq!(blend(RED, BLUE, f64::NAN), blend(RED, BLUE, 0.0));
+            assert_eq!(blend(RED, BLUE, f64::INFINITY), blend(RED, BLUE, 1.0));
+            assert_eq!(blend(RED, BLUE, f64::NEG_INFINITY), blend(RED, BLUE, 0.0));
+        }
+    }
+
+    #[test]
+    fn oklab_endpoints() {
+        assert_eq!(oklab(RED, BLUE, 0.0), RED);
+        assert_eq!(oklab(RED, BLUE, 1.0), BLUE);
+        assert_eq!(oklab(GREEN, YELLOW, 0.0), GREEN);
+        assert_eq!(oklab(GREEN, YELLOW, 1.0), YELLOW);
+    }
+
+    #[test]
+    fn oklab_blue_to_yellow() {
+        // Oklab of blue, (0.4520, -0.0325, -0.3115), and of yellow, (0.9680, -0.0714, 0.1986),
+        // meet at (0.7100, -0.0519, -0.0565): #6cabc7 with Björn Ottosson's reference matrices.
+        let midpoint = oklab(BLUE, YELLOW, 0.5);
+        for (channel, expected) in midpoint.iter().zip([255u8, 108, 171, 199]) {
+            assert!(channel.abs_diff(expected) <= 1, "{midpoint:?}");
+        }
+    }
+
+    #[test]
+    fn oklch_hue_keeps_lightness_and_chroma() {
+        assert_eq!(oklch_hue(RED, BLUE, 0.0), RED);
+        // A muted red, which stays in gamut at blue's hue.
+        let rose = [255, 154, 107, 107];
+        let [rose_l, rose_c, _] = oklch_from_argb(rose);
+        let [_, _, blue_hue] = oklch_from_argb(BLUE);
+        for amount in [0.5, 1.0] {
+            let [l, c, _] = oklch_from_argb(oklch_hue(rose, BLUE, amount));
+            assert_approx_eq!(l, rose_l, 0.01);
+            assert_approx_eq!(c, rose_c, 0.01);
+        }
+        let [_, _, h] = oklch_from_argb(oklch_hue(rose, BLUE, 1.0));
+        assert!(difference_degrees(h, blue_hue) < 2.0);
+
+        // Red to blue goes the short way, through magenta.
+        let [_, _, red_hue] = oklch_from_argb(RED);
+        let [_, _, mid_hue] = oklch_from_argb(oklch_hue(RED, BLUE, 0.5));
+        assert!(difference_degrees(mid_hue, red_hue) < difference_degrees(red_hue, blue_hue));
+        assert!(difference_degrees(mid_hue, blue_hue) < difference_degrees(red_hue, blue_hue));
+
+        assert_eq!(oklch_hue(BLUE, [255, 128, 128, 128], 0.5), BLUE);
+    }
 }
@@ -0,0 +1,165 @@
+//! Utility methods for calculating contrast given two colors, or calculating a
+//! color given one color and a contrast ratio.
+//!
+//! Contrast ratio is calculated using XYZ's Y. When linearized to match human
+//! perception, Y becomes HCT's tone and L*a*b*'s L*. Informally, this is the
+//! lightness of a color.
+//!
+//! Methods refer to tone, T in the the HCT color space. Tone is equivalent to
+//! L* in the L*a*b* color space, or L in the LCH color space.
+//!
+//! All formulas here are derived from the original Contrast sources in <https://github.com/material-foundation/material-color-utilities>
+use crate::utils::color::{lstar_from_y, y_from_lstar};
+use crate::utils::math::clamp_double;
+
+/// Returns a contrast ratio, which ranges from 1 to 21.
+///
+/// # Arguments
+///
+/// * `tone_a`: Tone between 0 and 100. Values outside will be clamped.
+/// * `tone_b`: Tone between 0 and 100. Values outside will be clamped.
+///
+/// # Returns
+///
+/// * The contrast ratio between the two tones.
+pub fn ratio_of_tones(tone_a: f64, tone_b: f64) -> f64 {
+    let tone_a = clamp_double(0.0, 100.0, tone_a);
+    let tone_b = clamp_double(0.0, 100.0, tone_b);
+    ratio_of_ys(y_from_lstar(tone_a), y_from_lstar(tone_b))
+}
+
+fn ratio_of_ys(y1: f64, y2: f64) -> f64 {
+    let lighter = y1.max(y2);
+    let darker = y1.min(y2);
+    (lighter + 5.0) / (darker + 5.0)
+}
+
+/// Returns a tone >= `tone` that ensures `ratio`.
+///
+/// # Arguments
+///
+/// * `tone`: Tone return value must contrast with.
+/// * `ratio`: Desired contrast ratio of return value and `tone`.
+///
+/// # Returns
+///
+/// * A tone that contrasts with `tone` by `ratio`, or `None` if the ratio
+///   cannot be achieved.
+pub fn lighter(tone: f64, ratio: f64) -> Option<f64> {
+    if !(0.0..=100.0).contains(&tone) {
+        return None;
+    }
+    let dark_y = y_from_lstar(tone);
+    let light_y = ratio * (dark_y + 5.0) - 5.0;
+    let real_contrast = ratio_of_ys(light_y, dark_y);
+    let delta = (real_contrast - ratio).abs();
+    if real_contrast < ratio && delta > 0.04 {
+        return None;
+    }
+    // Ensure gamut mapping, which requires a 'range' on tone, will still result
+    // in the correct ratio by darkening slightly.
+    let return_value = lstar_from_y(light_y) + 0.4;
+    if !(0.0..=100.0).contains(&return_value) {
+        return None;
+    }
+    Some(return_value)
+}
+
+/// Returns a tone <= `tone` that ensures `ratio`.
+///
+/// # Arguments
+///
+/// * `tone`: Tone return value must contrast with.
+/// * `ratio`: Desired contrast ratio of return value and `tone`.
+///
+/// # Returns
+///
+/// * A tone that contrasts with `tone` by `ratio`, or `None` if the ratio
+///   cannot be achieved.
+pub fn darker(tone: f64, ratio: f64) -> Option<f64> {
+    if !(0.0..=100.0).contains(&tone) {
+        return None;
+    }
+    let light_y = y_from_lstar(tone);
+    let dark_y = ((light_y + 5.0) / ratio) - 5.0;
+    let real_contrast = ratio_of_ys(light_y, dark_y);
+    let delta = (real_contrast - ratio).abs();
+    if real_contrast < ratio && delta > 0.04 {
+        return None;
+    }
+    // Ensure gamut mapping, which requires a 'range' on tone, will still result
+    // in the correct ratio by darkening slightly.
+    let return_value = lstar_from_y(dark_y) - 0.4;
+    if !(0.0..=100.0).contains(&return_value) {
+        return None;
+    }
+    Some(return_value)
+}
+
+/// Returns a tone >= `tone` that ensures `ratio`, or 100 if the ratio cannot
+/// be achieved.
+///
+/// This method is unsafe because the returned value is guaranteed to be in
+/// bounds, but the ratio is not guaranteed to be met.
+///
+/// # Arguments
+///
+/// * `tone`: Tone return value must contrast with.
+/// * `ratio`: Desired contrast ratio of return value and `tone`.
+pub fn lighter_unsafe(tone: f64, ratio: f64) -> f64 {
+    lighter(tone, ratio).unwrap_or(100.0)
+}
+
+/// Returns a tone <= `tone` that ensures `ratio`, or 0 if the ratio cannot be
+/// achieved.
+///
+/// This method is unsafe because the returned value is guaranteed to be in
+/// bounds, but the ratio is not guaranteed to be met.
+///
+/// # Arguments
+///
+/// * `tone`: Tone return value must contrast with.
+/// * `ratio`: Desired contrast ratio of return value and `tone`.
+pub fn darker_unsafe(tone: f64, ratio: f64) -> f64 {
+    darker(tone, ratio).unwrap_or(0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_approx_eq::assert_approx_eq;
+
+    #[test]
+    fn ratio_of_tones_extremes() {
+        assert_approx_eq!(21.0, ratio_of_tones(0.0, 100.0), 0.001);
+        assert_approx_eq!(21.0, ratio_of_tones(100.0, 0.0), 0.001);
+        assert_approx_eq!(1.0, ratio_of_tones(50.0, 50.0), 0.001);
+    }
+
+    #[test]
+    fn ratio_of_tones_clamps_input() {
+        assert_eq!(ratio_of_tones(-10.0, 110.0), ratio_of_tones(0.0, 100.0));
+    }
+
+    #[test]
+    fn lighter_and_darker_meet_ratio() {
+        let light = lighter(50.0, 3.0).unwrap();
+        let dark = darker(50.0, 3.0).unwrap();
+        assert!(light > 50.0 && ratio_of_tones(light, 50.0) >= 3.0);
+        assert!(dark < 50.0 && ratio_of_tones(dark, 50.0) >= 3.0);
+    }
+
+    #[test]
+    fn unreachable_ratio() {
+        assert_eq!(lighter(90.0, 4.5), None);
+        assert_eq!(darker(10.0, 4.5), None);
+        assert_eq!(lighter_unsafe(90.0, 4.5), 100.0);
+        assert_eq!(darker_unsafe(10.0, 4.5), 0.0);
+    }
+
+    #[test]
+    fn out_of_range_tone() {
+        assert_eq!(lighter(-1.0, 3.0), None);
+        assert_eq!(darker(101.0, 3.0), None);
+    }
+}
@@ -1,6 +1,6 @@
 use crate::hct::viewing_conditions::ViewingConditions;
-use crate::utils::color::{argb_from_xyz, xyz_from_argb};
-use crate::utils::math::matrix_multiply;
+use crate::utils::color::{argb_from_xyz, linearized_f32, xyz_from_argb, SRGB_TO_XYZ};
+use crate::utils::math::{matrix_multiply, matrix_multiply_f32, matrix_to_f32, signum};
 use std::f64::consts::PI;
 
 pub const XYZ_TO_CAM16RGB: [[f64; 3]; 3] = [
@@ -15,6 +15,12 @@ pub const CAM16RGB_TO_XYZ: [[f64; 3]; 3] = [
     [-0.01584150, -0.03412294, 1.0499644],
 ];
 
+// `SRGB_TO_XYZ` and `XYZ_TO_CAM16RGB` rounded once for `Cam16::jch_from_argb_f32`.
+const SRGB_TO_XYZ_F32: [[f32; 3]; 3] = matrix_to_f32(SRGB_TO_XYZ);
+const XYZ_TO_CAM16RGB_F32: [[f32; 3]; 3] = matrix_to_f32(XYZ_TO_CAM16RGB);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Cam16 {
     // CAM16 color dimensions, see getters for documentation.
     hue: f64,
@@ -30,7 +36,16 @@ pub struct Cam16 {
 }
 
 impl Cam16 {
-    pub fn distance(&self, other: Cam16) -> f64 {
+    /// Perceptual distance between two colors, measured as delta E in CAM16-UCS.
+    ///
+    /// # Arguments
+    ///
+    /// * `other`: The color to measure the distance to.
+    ///
+    /// # Returns
+    /// * `1.41 * dE'^0.63`, where dE' is the euclidean distance between the two colors' J*, a*,
+    ///   and b* coordinates.
+    pub fn distance(&self, other: &Cam16) -> f64 {
         let d_j = self.jstar() - other.jstar();
         let d_a = self.astar() - other.astar();
         let d_b = self.bstar() - other.bstar();
@@ -143,7 +158,24 @@ impl Cam16 {
     /// # Arguments
     /// * `argb`: ARGB representation of a color.
     pub fn from_argb(argb: [u8; 4]) -> Cam16 {
-        Self::from_int_in_viewing_condition(argb, ViewingConditions::default())
+        Self::from_argb_in_viewing_conditions(argb, ViewingConditions::cached_default())
+    }
+
+    /// Create a CAM16 color from a color in defined viewing conditions.
+    ///
+    /// # Arguments
+    ///
+    /// * `argb`: ARGB representation of a color.
+    /// * `viewing_conditions`: Information about the environment where the color was observed.
+    ///
+    /// # Returns
+    /// * Cam16
+    #[deprecated(note = "use `Cam16::from_argb_in_viewing_conditions` instead")]
+    pub fn from_int_in_viewing_condition(
+        argb: [u8; 4],
+        viewing_conditions: ViewingConditions,
+    ) -> Cam16 {
+        Self::from_argb_in_viewing_conditions(argb, &viewing_conditions)
     }
 
     /// Create a CAM16 color from a color in defined viewing conditions.
@@ -158,14 +190,81 @@ impl Cam16 {
     // The RGB => XYZ conversion matrix elements are derived scientific constants. While the values
     // may differ at runtime due to floating point imprecision, keeping the values the same, and
     // accurate, across implementations takes precedence.
-    pub fn from_int_in_viewing_condition(
+    pub fn from_argb_in_viewing_conditions(
         argb: [u8; 4],
-        viewing_conditions: ViewingConditions,
+        viewing_conditions: &ViewingConditions,
     ) -> Cam16 {
         // Transform ARGB int to XYZ
-        let xyz = xyz_from_argb(argb);
+        let [x, y, z] = xyz_from_argb(argb);
+        Self::from_xyz_in_viewing_conditions(x, y, z, viewing_conditions)
+    }
+
+    /// Single-precision CAM16 lightness, chroma, and hue of a color in the default viewing
+    /// conditions, for pipelines that keep their buffers in `f32`.
+    ///
+    /// # Arguments
+    ///
+    /// * `argb`: ARGB representation of a color.
+    ///
+    /// # Returns
+    /// * `[j, chroma, hue]`, agreeing with [`Cam16::from_argb`] to within 0.01 for J and chroma.
+    ///   Hue agrees to within 0.01 degrees for colors with a chroma of at least 1; below that,
+    ///   hue is poorly conditioned in either precision.
+    pub fn jch_from_argb_f32(argb: [u8; 4]) -> [f32; 3] {
+        let vc = ViewingConditions::cached_default();
+        let [_, r, g, b] = argb.map(linearized_f32);
+        let xyz = matrix_multiply_f32([r, g, b], SRGB_TO_XYZ_F32);
+        let t = matrix_multiply_f32(xyz, XYZ_TO_CAM16RGB_F32);
+        let rgb_d = vc.rgb_d();
+        let fl = vc.fl() as f32;
+        let a = [0, 1, 2].map(|i| {
+            let d = rgb_d[i] as f32 * t[i];
+            let af = (fl * d.abs() / 100.0).powf(0.42);
+            signum(d as f64) as f32 * 400.0 * af / (af + 27.13)
+        });
+        let red_greenness = (11.0 * a[0] + -12.0 * a[1] + a[2]) / 11.0;
+        let yellowness_blueness = (a[0] + a[1] - 2.0 * a[2]) / 9.0;
+        let u = (20.0 * a[0] + 20.0 * a[1] + 21.0 * a[2]) / 20.0;
+        let p2 = (40.0 * a[0] + 20.0 * a[1] + a[2]) / 20.0;
+        let atan_degrees = yellowness_blueness.atan2(red_greenness).to_degrees();
+        let hue = if atan_degrees < 0.0 {
+            atan_degrees + 360.0
+        } else if atan_degrees >= 360.0 {
+            atan_degrees - 360.0
+        } else {
+            atan_degrees
+        };
+        let ac = p2 * vc.nbb() as f32;
+        let lightness = 100.0 * (ac / vc.aw() as f32).powf(vc.lightness_exponent() as f32);
+        let hue_prime = if hue < 20.14 { hue + 360.0 } else { hue };
+        let e_hue = 0.25 * ((hue_prime.to_radians() + 2.0).cos() + 3.8);
+        let p1 = 50000.0 / 13.0 * e_hue * (vc.nc() * vc.ncb()) as f32;
+        let t = p1 * red_greenness.hypot(yellowness_blueness) / (u + 0.305);
+        let alpha = vc.chroma_factor() as f32 * t.powf(0.9);
+        let chroma = alpha * (lightness / 100.0).sqrt();
+        [lightness, chroma, hue]
+    }
+
+    /// Create a CAM16 color from XYZ coordinates in defined viewing conditions, without rounding
+    /// through 8-bit sRGB.
+    ///
+    /// # Arguments
+    ///
+    /// * `x`: X coordinate in the XYZ color space.
+    /// * `y`: Y coordinate in the XYZ color space.
+    /// * `z`: Z coordinate in the XYZ color space.
+    /// * `viewing_conditions`: Information about the environment where the color was observed.
+    ///
+    /// # Returns
+    /// * Cam16
+    pub fn from_xyz_in_viewing_conditions(
+        x: f64,
+        y: f64,
+        z: f64,
+        viewing_conditions: &ViewingConditions,
+    ) -> Cam16 {
         // Transform XYZ to 'cone'/'rgb' responses
-        let t = matrix_multiply(xyz, XYZ_TO_CAM16RGB);
+        let t = matrix_multiply([x, y, z], XYZ_TO_CAM16RGB);
         // Discount illuminant
         let d = [
             viewing_conditions.rgb_d()[0] * t[0],
@@ -179,9 +278,9 @@ impl Cam16 {
             (viewing_conditions.fl() * d[2].abs() / 100.0).powf(0.42),
         ];
         let a = [
-            d[0].signum() * 400.0 * af[0] / (af[0] + 27.13),
-            d[1].signum() * 400.0 * af[1] / (af[1] + 27.13),
-            d[2].signum() * 400.0 * af[2] / (af[2] + 27.13),
+            signum(d[0]) * 400.0 * af[0] / (af[0] + 27.13),
+            signum(d[1]) * 400.0 * af[1] / (af[1] + 27.13),
+            signum(d[2]) * 400.0 * af[2] / (af[2] + 27.13),
         ];
         // redness-greenness
         let red_greenness = (11.0 * a[0] + -12.0 * a[1] + a[2]) / 11.0;
@@ -204,8 +303,8 @@ impl Cam16 {
         // achromatic response to color
         let ac = p2 * viewing_conditions.nbb();
         // CAM16 lightness and brightness
-        let lightness = 100.0
-            * (ac / viewing_conditions.aw()).powf(viewing_conditions.c() * viewing_conditions.z());
+        let lightness =
+            100.0 * (ac / viewing_conditions.aw()).powf(viewing_conditions.lightness_exponent());
         let brightness = 4.0 / viewing_conditions.c()
             * (lightness / 100.0).sqrt()
             * (viewing_conditions.aw() + 4.0)
@@ -215,7 +314,7 @@ impl Cam16 {
         let e_hue = 0.25 * ((hue_prime.to_radians() + 2.0).cos() + 3.8);
         let p1 = 50000.0 / 13.0 * e_hue * viewing_conditions.nc() * viewing_conditions.ncb();
         let t = p1 * red_greenness.hypot(yellowness_blueness) / (u + 0.305);
-        let alpha = (1.64 - 0.29f64.powf(viewing_conditions.n())).powf(0.73) * t.powf(0.9);
+        let alpha = viewing_conditions.chroma_factor() * t.powf(0.9);
         // CAM16 chroma, colorfulness, saturation
         let chroma = alpha * (lightness / 100.0).sqrt();
         let colorfulness = chroma * viewing_conditions.fl_root();
@@ -240,27 +339,44 @@ impl Cam16 {
         }
     }
 
+    /// Create a CAM16 color from lightness, chroma and hue in the default viewing conditions.
+    ///
     /// # Arguments
     ///
     /// * `j`: CAM16 lightness
     /// * `c`: CAM16 chroma
-    /// * `h`: CAM16 hue
-    pub(crate) fn from_jch(j: f64, c: f64, h: f64) -> Cam16 {
-        Self::from_jch_in_viewing_conditions(j, c, h, ViewingConditions::default())
+    /// * `h`: CAM16 hue, in degrees
+    pub fn from_jch(j: f64, c: f64, h: f64) -> Cam16 {
+        Self::from_jch_in(j, c, h, ViewingConditions::cached_default())
     }
 
+    /// Create a CAM16 color from lightness, chroma and hue in defined viewing conditions.
+    ///
     /// # Arguments
     ///
     /// * `j`: CAM16 lightness
     /// * `c`: CAM16 chroma
-    /// * `h`: CAM16 hue
+    /// * `h`: CAM16 hue, in degrees
     /// * `viewing_conditions`: Information about the environment where the color was observed.
-    fn from_jch_in_viewing_conditions(
+    #[deprecated(note = "use `Cam16::from_jch_in` instead")]
+    pub fn from_jch_in_viewing_conditions(
         j: f64,
         c: f64,
         h: f64,
         viewing_conditions: ViewingConditions,
     ) -> Cam16 {
+        Self::from_jch_in(j, c, h, &viewing_conditions)
+    }
+
+    /// Create a CAM16 color from lightness, chroma and hue in defined viewing conditions.
+    ///
+    /// # Arguments
+    ///
+    /// * `j`: CAM16 lightness
+    /// * `c`: CAM16 chroma
+    /// * `h`: CAM16 hue, in degrees
+    /// * `viewing_conditions`: Information about the environment where the color was observed.
+    pub fn from_jch_in(j: f64, c: f64, h: f64, viewing_conditions: &ViewingConditions) -> Cam16 {
         let q = 4.0 / viewing_conditions.c()
             * (j / 100.0).sqrt()
             * (viewing_conditions.aw() + 4.0)
@@ -295,7 +411,7 @@ impl Cam16 {
     /// * `astar`: CAM16-UCS a dimension. Like a* in L*a*b*, it is a Cartesian coordinate on the Y axis.
     /// * `bstar`: CAM16-UCS b dimension. Like a* in L*a*b*, it is a Cartesian coordinate on the X axis.
     pub fn from_ucs(jstar: f64, astar: f64, bstar: f64) -> Cam16 {
-        Self::from_ucs_in_viewing_conditions(jstar, astar, bstar, ViewingConditions::default())
+        Self::from_ucs_in(jstar, astar, bstar, ViewingConditions::cached_default())
     }
 
     /// Create a CAM16 color from CAM16-UCS coordinates in defined viewing conditions.
@@ -306,11 +422,29 @@ impl Cam16 {
     /// * `astar`: CAM16-UCS a dimension. Like a* in L*a*b*, it is a Cartesian coordinate on the Y axis.
     /// * `bstar`: CAM16-UCS b dimension. Like a* in L*a*b*, it is a Cartesian coordinate on the X axis.
     /// * `viewing_conditions`: Information about the environment where the color was observed.
+    #[deprecated(note = "use `Cam16::from_ucs_in` instead")]
     pub fn from_ucs_in_viewing_conditions(
         jstar: f64,
         astar: f64,
         bstar: f64,
         viewing_conditions: ViewingConditions,
+    ) -> Cam16 {
+        Self::from_ucs_in(jstar, astar, bstar, &viewing_conditions)
+    }
+
+    /// Create a CAM16 color from CAM16-UCS coordinates in defined viewing conditions.
+    ///
+    /// # Arguments
+    ///
+    /// * `jstar`: CAM16-UCS lightness.
+    /// * `astar`: CAM16-UCS a dimension. Like a* in L*a*b*, it is a Cartesian coordinate on the Y axis.
+    /// * `bstar`: CAM16-UCS b dimension. Like a* in L*a*b*, it is a Cartesian coordinate on the X axis.
+    /// * `viewing_conditions`: Information about the environment where the color was observed.
+    pub fn from_ucs_in(
+        jstar: f64,
+        astar: f64,
+        bstar: f64,
+        viewing_conditions: &ViewingConditions,
     ) -> Cam16 {
         let m = astar.hypot(bstar);
         let m2 = (m * 0.0228).exp_m1() / 0.0228;
@@ -320,24 +454,47 @@ impl Cam16 {
             h += 360.0;
         }
         let j = jstar / (1. - (jstar - 100.) * 0.007);
-        Self::from_jch_in_viewing_conditions(j, c, h, viewing_conditions)
+        Self::from_jch_in(j, c, h, viewing_conditions)
     }
 
     pub fn to_int(&self) -> [u8; 4] {
-        self.viewed(ViewingConditions::default())
+        self.viewed_in(ViewingConditions::cached_default())
     }
 
+    #[deprecated(note = "use `Cam16::viewed_in` instead")]
     pub fn viewed(&self, viewing_conditions: ViewingConditions) -> [u8; 4] {
+        self.viewed_in(&viewing_conditions)
+    }
+
+    /// ARGB representation of this color in defined viewing conditions.
+    ///
+    /// # Arguments
+    ///
+    /// * `viewing_conditions`: Information about the environment where the color was observed.
+    pub fn viewed_in(&self, viewing_conditions: &ViewingConditions) -> [u8; 4] {
+        argb_from_xyz(self.xyz_in_viewing_conditions(viewing_conditions))
+    }
+
+    /// XYZ coordinates of this color in defined viewing conditions, without rounding through
+    /// 8-bit sRGB.
+    ///
+    /// # Arguments
+    ///
+    /// * `viewing_conditions`: Information about the environment where the color was observed.
+    ///
+    /// # Returns
+    /// * The color in the XYZ color space.
+    pub fn xyz_in_viewing_conditions(&self, viewing_conditions: &ViewingConditions) -> [f64; 3] {
         let alpha = if self.chroma() == 0.0 || self.j() == 0.0 {
             0.0
         } else {
             self.chroma() / (self.j() / 100.0).sqrt()
         };
-        let t = (alpha / (1.64 - 0.29f64.powf(viewing_conditions.n())).powf(0.73)).powf(1.0 / 0.9);
+        let t = (alpha / viewing_conditions.chroma_factor()).powf(1.0 / 0.9);
         let h_rad = self.hue().to_radians();
         let e_hue = 0.25 * ((h_rad + 2.0).cos() + 3.8);
         let ac = viewing_conditions.aw()
-            * (self.j() / 100.0).powf(1.0 / viewing_conditions.c() / viewing_conditions.z());
+            * (self.j() / 100.0).powf(viewing_conditions.inverse_lightness_exponent());
         let p1 = e_hue * (50000.0 / 13.0) * viewing_conditions.nc() * viewing_conditions.ncb();
         let p2 = ac / viewing_conditions.nbb();
         let h_sin = h_rad.sin();
@@ -349,24 +506,197 @@ impl Cam16 {
         let g_a = (460.0 * p2 - 891.0 * a - 261.0 * b) / 1403.0;
         let b_a = (460.0 * p2 - 220.0 * a - 6300.0 * b) / 1403.0;
         let r_cbase = ((27.13 * r_a.abs()) / (400.0 - r_a.abs())).max(0.0);
-        let r_c = r_a.signum() * (100.0 / viewing_conditions.fl()) * r_cbase.powf(1.0 / 0.42);
+        let r_c = signum(r_a) * (100.0 / viewing_conditions.fl()) * r_cbase.powf(1.0 / 0.42);
         let g_cbase = ((27.13 * g_a.abs()) / (400.0 - g_a.abs())).max(0.0);
-        let g_c = g_a.signum() * (100.0 / viewing_conditions.fl()) * g_cbase.powf(1.0 / 0.42);
+        let g_c = signum(g_a) * (100.0 / viewing_conditions.fl()) * g_cbase.powf(1.0 / 0.42);
         let b_cbase = ((27.13 * b_a.abs()) / (400.0 - b_a.abs())).max(0.0);
-        let b_c = b_a.signum() * (100.0 / viewing_conditions.fl()) * b_cbase.powf(1.0 / 0.42);
+        let b_c = signum(b_a) * (100.0 / viewing_conditions.fl()) * b_cbase.powf(1.0 / 0.42);
         let r_f = r_c / viewing_conditions.rgb_d()[0];
         let g_f = g_c / viewing_conditions.rgb_d()[1];
         let b_f = b_c / viewing_conditions.rgb_d()[2];
-        let xyz = matrix_multiply([r_f, g_f, b_f], CAM16RGB_TO_XYZ);
-        argb_from_xyz(xyz)
+        matrix_multiply([r_f, g_f, b_f], CAM16RGB_TO_XYZ)
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+    use crate::utils::color::{y_from_lstar, WHITE_POINT_D65};
+    use crate::utils::math::{assert_matrices_are_inverses, difference_degrees};
+    use assert_approx_eq::assert_approx_eq;
+
+    const RED: [u8; 4] = [0xff, 0xff, 0x00, 0x00];
+
+    #[test]
+    fn from_argb_in_default_viewing_conditions() {
+        let cam = Cam16::from_argb_in_viewing_conditions(RED, &ViewingConditions::default());
+        assert_approx_eq!(46.445, cam.j(), 0.001);
+        assert_approx_eq!(113.357, cam.chroma(), 0.001);
+        assert_approx_eq!(27.408, cam.hue(), 0.001);
+        assert_eq!(cam.j(), Cam16::from_argb(RED).j());
+    }
+
+    #[test]
+    fn from_argb_in_dark_surround() {
+        let adapting_luminance = 200.0 / PI * y_from_lstar(50.0) / 100.0;
+        let dark = ViewingConditions::make(WHITE_POINT_D65, adapting_luminance, 50.0, 0.0, false);
+        let cam = Cam16::from_argb_in_viewing_conditions(RED, &dark);
+        let reference = Cam16::from_argb(RED);
+        // A dark surround lowers the exponent of the lightness response, so mid-lightness colors
+        // read lighter than they do in the default average surround.
+        assert!(cam.j() > reference.j());
+        assert!(difference_degrees(cam.hue(), reference.hue()) < 1.0);
+        assert_eq!(cam.viewed_in(&dark), RED);
+    }
+
+    #[test]
+    fn xyz_round_trip() {
+        let conditions = ViewingConditions::default();
+        for xyz in [
+            [41.233895, 21.26, 1.932141],
+            [18.05, 7.22, 95.05],
+            [50.0, 50.0, 50.0],
+            [1.0, 2.0, 3.0],
+        ] {
+            let cam = Cam16::from_xyz_in_viewing_conditions(xyz[0], xyz[1], xyz[2], &conditions);
+            let round_trip = cam.xyz_in_viewing_conditions(&conditions);
+            // CAM16RGB_TO_XYZ is only given to 8 significant digits, which bounds the relative
+            // precision of the round trip.
+            for axis in 0..3 {
+                assert_approx_eq!(xyz[axis], round_trip[axis], 1e-6 * xyz[axis].max(1.0));
+            }
+        }
+    }
+
     #[test]
-    fn placeholder_test() {
-        let sum = 2 + 2;
-        assert_eq!(sum, 4);
+    fn from_argb_matches_from_xyz() {
+        let [x, y, z] = crate::utils::color::xyz_from_argb(RED);
+        let cam = Cam16::from_xyz_in_viewing_conditions(x, y, z, &ViewingConditions::default());
+        assert_eq!(cam.hue(), Cam16::from_argb(RED).hue());
+        assert_eq!(cam.chroma(), Cam16::from_argb(RED).chroma());
+    }
+
+    #[test]
+    fn distance_reference_values() {
+        let red = Cam16::from_argb(RED);
+        let green = Cam16::from_argb([0xff, 0x00, 0xff, 0x00]);
+        let blue = Cam16::from_argb([0xff, 0x00, 0x00, 0xff]);
+        let white = Cam16::from_argb([0xff, 0xff, 0xff, 0xff]);
+        let black = Cam16::from_argb([0xff, 0x00, 0x00, 0x00]);
+        let google_blue = Cam16::from_argb([0xff, 0x42, 0x85, 0xf4]);
+        assert_approx_eq!(23.241, red.distance(&green), 0.001);
+        assert_approx_eq!(21.415, red.distance(&blue), 0.001);
+        assert_approx_eq!(25.662, white.distance(&black), 0.001);
+        assert_approx_eq!(11.181, google_blue.distance(&blue), 0.001);
+    }
+
+    #[test]
+    fn distance_is_symmetric_and_zero_to_self() {
+        let red = Cam16::from_argb(RED);
+        let blue = Cam16::from_argb([0xff, 0x00, 0x00, 0xff]);
+        assert_eq!(red.distance(&red), 0.0);
+        assert_eq!(red.distance(&blue), blue.distance(&red));
+    }
+
+    #[test]
+    fn by_value_viewing_conditions_still_accepted() {
+        let cam = Cam16::from_argb([0xff, 0x42, 0x85, 0xf4]);
+        let conditions = ViewingConditions::default();
+        #[allow(deprecated)]
+        let legacy = [
+            cam.viewed(conditions),
+            Cam16::from_jch_in_viewing_conditions(cam.j(), cam.chroma(), cam.hue(), conditions)
+                .to_int(),
+            Cam16::from_ucs_in_viewing_conditions(
+                cam.jstar(),
+                cam.astar(),
+                cam.bstar(),
+                conditions,
+            )
+            .to_int(),
+        ];
+        assert_eq!(legacy, [[0xff, 0x42, 0x85, 0xf4]; 3]);
+    }
+
+    #[test]
+    fn from_jch_round_trips() {
+        for argb in [
+            RED,
+            [0xff, 0x00, 0xff, 0x00],
+            [0xff, 0x00, 0x00, 0xff],
+            [0xff, 0x42, 0x85, 0xf4],
+            [0xff, 0x80, 0x80, 0x80],
+        ] {
+            let cam = Cam16::from_argb(argb);
+            let round_trip = Cam16::from_jch(cam.j(), cam.chroma(), cam.hue());
+            assert_eq!(round_trip.to_int(), argb);
+            assert_approx_eq!(cam.jstar(), round_trip.jstar(), 1e-9);
+            assert_approx_eq!(cam.astar(), round_trip.astar(), 1e-9);
+            assert_approx_eq!(cam.bstar(), round_trip.bstar(), 1e-9);
+        }
+    }
+
+    #[test]
+    fn from_ucs_round_trips() {
+        for argb in [RED, [0xff, 0x00, 0x00, 0xff], [0xff, 0x42, 0x85, 0xf4]] {
+            let cam = Cam16::from_argb(argb);
+            let round_trip = Cam16::from_ucs(cam.jstar(), cam.astar(), cam.bstar());
+            assert_eq!(round_trip.to_int(), argb);
+            assert_approx_eq!(cam.j(), round_trip.j(), 1e-9);
+            assert_approx_eq!(cam.chroma(), round_trip.chroma(), 1e-9);
+            assert_approx_eq!(cam.hue(), round_trip.hue(), 1e-9);
+        }
+    }
+
+    #[test]
+    fn debug_smoke() {
+        let debug = format!("{:?}", Cam16::from_argb(RED));
+        assert!(debug.starts_with("Cam16 {"));
+        assert!(debug.contains("chroma"));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let cam = Cam16::from_argb(RED);
+        let json = serde_json::to_string(&cam).unwrap();
+        assert!(json.contains("\"jstar\""));
+        let back: Cam16 = serde_json::from_str(&json).unwrap();
+        assert_eq!(cam, back);
+    }
+
+    #[test]
+    fn cached_default_is_bit_identical() {
+        let adapting_luminance = 200.0 / PI * y_from_lstar(50.0) / 100.0;
+        let rebuilt =
+            ViewingConditions::make(WHITE_POINT_D65, adapting_luminance, 50.0, 2.0, false);
+        for argb in [RED, [0xff, 0x42, 0x85, 0xf4], [0xff, 0x12, 0x34, 0x56]] {
+            let cam = Cam16::from_argb(argb);
+            assert_eq!(cam, Cam16::from_argb_in_viewing_conditions(argb, &rebuilt));
+            assert_eq!(cam.to_int(), cam.viewed_in(&rebuilt));
+        }
+    }
+
+    #[test]
+    fn cam16_matrices_are_inverses() {
+        assert_matrices_are_inverses(XYZ_TO_CAM16RGB, CAM16RGB_TO_XYZ, 1e-7);
+    }
+
+    #[test]
+    fn jch_f32_matches_f64() {
+        for r in (0..=255).step_by(15) {
+            for g in (0..=255).step_by(15) {
+                for b in (0..=255).step_by(15) {
+                    let argb = [255, r, g, b];
+                    let cam = Cam16::from_argb(argb);
+                    let [j, chroma, hue] = Cam16::jch_from_argb_f32(argb).map(|v| v as f64);
+                    assert_approx_eq!(j, cam.j(), 0.01);
+                    assert_approx_eq!(chroma, cam.chroma(), 0.01);
+                    if cam.chroma() >= 1.0 {
+                        assert!(difference_degrees(hue, cam.hue()) < 0.01);
+                    }
+                }
+            }
+        }
     }
 }
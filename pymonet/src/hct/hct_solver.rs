@@ -2,7 +2,9 @@
 use crate::hct::cam16::Cam16;
 use crate::hct::viewing_conditions::ViewingConditions;
 use crate::utils::color::{argb_from_linrgb, argb_from_lstar, y_from_lstar};
-use crate::utils::math::{matrix_multiply, sanitize_degrees_double};
+use crate::utils::math::{matrix_multiply, sanitize_degrees_double, signum};
+use ahash::AHashMap;
+use std::cell::RefCell;
 use std::f64::consts::PI;
 
 const SCALED_DISCOUNT_FROM_LINRGB: [[f64; 3]; 3] = [
@@ -321,7 +323,7 @@ fn true_delinearized(rgb_comp: f64) -> f64 {
 
 fn chromatic_adaptation(component: f64) -> f64 {
     let af = component.abs().powf(0.42);
-    component.signum() * 400.0 * af / (af + 27.13)
+    signum(component) * 400.0 * af / (af + 27.13)
 }
 
 /// Returns the hue of a linear RGB color in CAM16.
@@ -403,6 +405,7 @@ fn is_bounded(x: f64) -> bool {
 /// * The nth possible vertex of the polygonal intersection of the y plane and the RGB cube,
 /// in linear RGB coordinates, if it exists. If this possible vertex lies outside of the cube,
 /// [-1.0, -1.0, -1.0] is returned.
+#[allow(clippy::doc_lazy_continuation, clippy::manual_is_multiple_of)]
 fn nth_vertex(y: f64, n: usize) -> [f64; 3] {
     let k_r = Y_FROM_LINRGB[0];
     let k_g = Y_FROM_LINRGB[1];
@@ -439,6 +442,49 @@ fn nth_vertex(y: f64, n: usize) -> [f64; 3] {
     }
 }
 
+/// Precision settings for the solver.
+///
+/// The defaults match the reference implementation and are what [`solve_to_int`] uses. Lowering
+/// them trades accuracy for speed, which suits bulk work such as recoloring every pixel of an
+/// image:
+///
+/// * Fewer Newton iterations or a looser `y_epsilon` let the in-gamut search stop earlier. The
+///   answer keeps the requested hue, but its tone and chroma drift further from the request.
+/// * Fewer bisection iterations make out-of-gamut requests land on a coarser point of the gamut
+///   boundary, so their chroma is maximized less precisely.
+///
+/// [`SolverOptions::fast`] stays within 2.0 CAM16 chroma and 1.0 tone of the default results.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SolverOptions {
+    /// Newton iterations spent looking for an in-gamut color with the requested chroma.
+    pub newton_iterations: usize,
+    /// How close, in Y, the in-gamut search must get before it stops early.
+    pub y_epsilon: f64,
+    /// Bisection steps per RGB axis spent on the gamut boundary for out-of-gamut requests.
+    pub bisection_iterations: usize,
+}
+
+impl SolverOptions {
+    /// Settings that cut the work of the solver, for bulk processing.
+    pub fn fast() -> SolverOptions {
+        SolverOptions {
+            newton_iterations: 3,
+            y_epsilon: 0.02,
+            bisection_iterations: 4,
+        }
+    }
+}
+
+impl Default for SolverOptions {
+    fn default() -> Self {
+        SolverOptions {
+            newton_iterations: 5,
+            y_epsilon: 0.002,
+            bisection_iterations: 8,
+        }
+    }
+}
+
 /// Finds the segment containing the desired color.
 ///
 /// # Arguments
@@ -449,6 +495,7 @@ fn nth_vertex(y: f64, n: usize) -> [f64; 3] {
 /// # Returns
 /// * A list of two sets of linear RGB coordinates, each corresponding to an endpoint of the
 /// segment containing the desired color.
+#[allow(clippy::doc_lazy_continuation)]
 fn bisect_to_segment(y: f64, target_hue: f64) -> [[f64; 3]; 2] {
     let mut left = [-1.0, -1.0, -1.0];
     let mut right = left;
@@ -506,10 +553,11 @@ fn critical_plane_above(x: f64) -> u8 {
 ///
 /// * `y`: The Y value of the color.
 /// * `target_hue`: The hue of the color.
+/// * `iterations`: How many bisection steps to take along each axis.
 ///
 /// # Returns
 /// * The desired color, in linear RGB coordinates.
-fn bisect_to_limit(y: f64, target_hue: f64) -> [f64; 3] {
+fn bisect_to_limit(y: f64, target_hue: f64, iterations: usize) -> [f64; 3] {
     let segment = bisect_to_segment(y, target_hue);
     let mut left = segment[0];
     let mut left_hue = hue_of(left);
@@ -528,7 +576,7 @@ fn bisect_to_limit(y: f64, target_hue: f64) -> [f64; 3] {
                 )
             };
 
-            for _ in 0..8 {
+            for _ in 0..iterations {
                 if (r_plane as i16 - l_plane as i16).abs() <= 1 {
                     break;
                 } else {
@@ -554,33 +602,73 @@ fn bisect_to_limit(y: f64, target_hue: f64) -> [f64; 3] {
 fn inverse_chromatic_adaptation(adapted: f64) -> f64 {
     let adapted_abs = adapted.abs();
     let base = (27.13 * adapted_abs / (400.0 - adapted_abs)).max(0.0);
-    adapted.signum() * base.powf(1.0 / 0.42)
+    signum(adapted) * base.powf(1.0 / 0.42)
+}
+
+/// The parts of [`find_result_by_j`] that depend only on the hue, so requests sharing a hue can
+/// share them.
+struct HueTerms {
+    degrees: f64,
+    radians: f64,
+    t_inner_coeff: f64,
+    p1: f64,
+    h_sin: f64,
+    h_cos: f64,
+}
+
+impl HueTerms {
+    /// # Arguments
+    ///
+    /// * `hue_degrees`: The desired hue, already sanitized to [0, 360).
+    fn new(hue_degrees: f64) -> HueTerms {
+        let hue_radians = hue_degrees / 180.0 * PI;
+        // ===========================================================
+        // Operations inlined from Cam16 to avoid repeated calculation
+        // ===========================================================
+        let viewing_conditions = ViewingConditions::cached_default();
+        let t_inner_coeff = 1.0 / viewing_conditions.chroma_factor();
+        let e_hue = 0.25 * ((hue_radians + 2.0).cos() + 3.8);
+        let p1 = e_hue * (50000.0 / 13.0) * viewing_conditions.nc() * viewing_conditions.ncb();
+        HueTerms {
+            degrees: hue_degrees,
+            radians: hue_radians,
+            t_inner_coeff,
+            p1,
+            h_sin: hue_radians.sin(),
+            h_cos: hue_radians.cos(),
+        }
+    }
 }
 
 /// Finds a color with the given hue, chroma, and Y.
 ///
 /// # Arguments
 ///
-/// * `hue_radians`: The desired hue in radians.
+/// * `hue`: The desired hue, with its precomputed terms.
 /// * `chroma`: The desired chroma.
 /// * `y`: The desired Y.
+/// * `options`: How hard to search.
 ///
 /// # Returns
-/// * The desired color as a hexadecimal integer, if found; 0 otherwise.
-fn find_result_by_j(hue_radians: f64, chroma: f64, y: f64) -> [u8; 4] {
+/// * The desired color as a hexadecimal integer, if found; `None` otherwise.
+fn find_result_by_j(
+    hue: &HueTerms,
+    chroma: f64,
+    y: f64,
+    options: &SolverOptions,
+) -> Option<[u8; 4]> {
     // Initial estimate of j.
     let mut j = y.sqrt() * 11.0;
-    // ===========================================================
-    // Operations inlined from Cam16 to avoid repeated calculation
-    // ===========================================================
-    let viewing_conditions = ViewingConditions::default();
-    let t_inner_coeff = 1.0 / (1.64 - 0.29f64.powf(viewing_conditions.n())).powf(0.73);
-    let e_hue = 0.25 * ((hue_radians + 2.0).cos() + 3.8);
-    let p1 = e_hue * (50000.0 / 13.0) * viewing_conditions.nc() * viewing_conditions.ncb();
-    let h_sin = hue_radians.sin();
-    let h_cos = hue_radians.cos();
-
-    for iteration_round in 0..5 {
+    let viewing_conditions = ViewingConditions::cached_default();
+    let HueTerms {
+        t_inner_coeff,
+        p1,
+        h_sin,
+        h_cos,
+        ..
+    } = *hue;
+
+    for iteration_round in 0..options.newton_iterations {
         // ===========================================================
         // Operations inlined from Cam16 to avoid repeated calculation
         // ===========================================================
@@ -592,7 +680,7 @@ fn find_result_by_j(hue_radians: f64, chroma: f64, y: f64) -> [u8; 4] {
         };
         let t = (alpha * t_inner_coeff).powf(1.0 / 0.9);
         let ac = viewing_conditions.aw()
-            * j_normalized.powf(1.0 / viewing_conditions.c() / viewing_conditions.z());
+            * j_normalized.powf(viewing_conditions.inverse_lightness_exponent());
         let p2 = ac / viewing_conditions.nbb();
         let gamma = 23.0 * (p2 + 0.305) * t / (23.0 * p1 + 11.0 * t * h_cos + 108.0 * t * h_sin);
         let a = gamma * h_cos;
@@ -611,26 +699,207 @@ fn find_result_by_j(hue_radians: f64, chroma: f64, y: f64) -> [u8; 4] {
         // Operations inlined from Cam16 to avoid repeated calculation
         // ===========================================================
         if linrgb[0] < 0.0 || linrgb[1] < 0.0 || linrgb[2] < 0.0 {
-            return [0, 0, 0, 0];
+            return None;
         }
         let k_r = Y_FROM_LINRGB[0];
         let k_g = Y_FROM_LINRGB[1];
         let k_b = Y_FROM_LINRGB[2];
         let fnj = k_r * linrgb[0] + k_g * linrgb[1] + k_b * linrgb[2];
         if fnj <= 0.0 {
-            return [0, 0, 0, 0];
+            return None;
         }
-        if iteration_round == 4 || (fnj - y).abs() < 0.002 {
+        if iteration_round + 1 == options.newton_iterations || (fnj - y).abs() < options.y_epsilon {
             if linrgb[0] > 100.01 || linrgb[1] > 100.01 || linrgb[2] > 100.01 {
-                return [0, 0, 0, 0];
+                return None;
             }
-            return argb_from_linrgb(linrgb);
+            return Some(argb_from_linrgb(linrgb));
         }
         // Iterates with Newton method,
         // Using 2 * fn(j) / j as the approximation of fn'(j)
         j = j - (fnj - y) * j / (2.0 * fnj);
     }
-    [0, 0, 0, 0]
+    None
+}
+
+/// Solutions remembered for the current thread, keyed on the request rounded to tenths, see
+/// [`cache_key`], evicting the least recently used solution when full.
+struct SolutionCache {
+    /// Each solution with the `clock` reading of its last use.
+    entries: AHashMap<[i32; 3], ([u8; 4], u64)>,
+    clock: u64,
+    capacity: usize,
+}
+
+impl SolutionCache {
+    fn get(&mut self, key: &[i32; 3]) -> Option<[u8; 4]> {
+        self.clock += 1;
+        let (argb, last_used) = self.entries.get_mut(key)?;
+        *last_used = self.clock;
+        Some(*argb)
+    }
+
+    fn insert(&mut self, key: [i32; 3], argb: [u8; 4]) {
+        if self.entries.len() >= self.capacity {
+            let oldest = self
+                .entries
+                .iter()
+                .min_by_key(|(_, (_, last_used))| *last_used)
+                .map(|(&key, _)| key);
+            if let Some(oldest) = oldest {
+                self.entries.remove(&oldest);
+            }
+        }
+        self.clock += 1;
+        self.entries.insert(key, (argb, self.clock));
+    }
+}
+
+thread_local! {
+    static CACHE: RefCell<Option<SolutionCache>> = const { RefCell::new(None) };
+}
+
+#[cfg(test)]
+thread_local! {
+    /// Cacheable requests solved on this thread, so tests can tell cache hits from misses.
+    static SOLVES: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+}
+
+/// Remember solver results on the current thread, so repeated requests for the same hue, chroma
+/// and tone skip the solve. This pays off when many schemes share source colors, for example
+/// when previewing every variant of a theme.
+///
+/// The cache is off by default. While it is on, hue, chroma and tone are rounded to tenths before
+/// solving, as [`TonalPalette`](crate::palettes::tonal::TonalPalette) does for tones, so that
+/// nearby requests share a solution. Once it holds `capacity` solutions the least recently used
+/// one is evicted.
+///
+/// # Arguments
+///
+/// * `capacity`: The most solutions to hold at once. 0 disables the cache.
+pub fn enable_cache(capacity: usize) {
+    CACHE.with(|cache| {
+        *cache.borrow_mut() = (capacity > 0).then(|| SolutionCache {
+            entries: AHashMap::with_capacity(capacity.min(4096)),
+            clock: 0,
+            capacity,
+        });
+    });
+}
+
+/// Stop caching solver results on the current thread and free the cached entries.
+pub fn disable_cache() {
+    CACHE.with(|cache| *cache.borrow_mut() = None);
+}
+
+/// Rounds a request to tenths of a degree, of chroma, and of tone, so that requests differing
+/// only by floating point error, or by less than a visible step, share a cache entry.
+fn cache_key(hue_degrees: f64, chroma: f64, lstar: f64) -> [i32; 3] {
+    [sanitize_degrees_double(hue_degrees), chroma, lstar].map(|value| (value * 10.0).round() as i32)
+}
+
+fn solve(hue_degrees: f64, chroma: f64, lstar: f64) -> [u8; 4] {
+    let enabled = CACHE.with(|cache| cache.borrow().is_some());
+    // Grays are cheap to solve, and a non-finite request has no meaningful key.
+    if !enabled || is_achromatic(hue_degrees, chroma, lstar) {
+        return solve_with_options(hue_degrees, chroma, lstar, &SolverOptions::default());
+    }
+    let key = cache_key(hue_degrees, chroma, lstar);
+    let cached = CACHE.with(|cache| {
+        cache
+            .borrow_mut()
+            .as_mut()
+            .and_then(|cache| cache.get(&key))
+    });
+    if let Some(argb) = cached {
+        return argb;
+    }
+    #[cfg(test)]
+    SOLVES.with(|solves| solves.set(solves.get() + 1));
+    let [hue, chroma, lstar] = key.map(|value| value as f64 / 10.0);
+    let argb = solve_with_options(hue, chroma, lstar, &SolverOptions::default());
+    CACHE.with(|cache| {
+        if let Some(cache) = cache.borrow_mut().as_mut() {
+            cache.insert(key, argb);
+        }
+    });
+    argb
+}
+
+/// Whether a request is solved as a gray of the given L*. A request whose hue is not finite, or
+/// whose chroma is NaN, has no meaningful hue, so it is gray as well rather than an arbitrary
+/// color. A NaN L* is black.
+fn is_achromatic(hue_degrees: f64, chroma: f64, lstar: f64) -> bool {
+    !hue_degrees.is_finite()
+        || chroma.is_nan()
+        || chroma < 0.0001
+        || !(0.0001..=99.9999).contains(&lstar)
+}
+
+fn solve_with_options(
+    hue_degrees: f64,
+    chroma: f64,
+    lstar: f64,
+    options: &SolverOptions,
+) -> [u8; 4] {
+    if is_achromatic(hue_degrees, chroma, lstar) {
+        return argb_from_lstar(lstar);
+    }
+    let hue = HueTerms::new(sanitize_degrees_double(hue_degrees));
+    solve_chromatic(&hue, chroma, lstar, options)
+}
+
+fn solve_chromatic(hue: &HueTerms, chroma: f64, lstar: f64, options: &SolverOptions) -> [u8; 4] {
+    let y = y_from_lstar(lstar);
+    if let Some(argb) = find_result_by_j(hue, chroma, y, options) {
+        return argb;
+    }
+    let linrgb = bisect_to_limit(y, hue.radians, options.bisection_iterations);
+    argb_from_linrgb(linrgb)
+}
+
+/// Solves a run of requests in order, recomputing the hue terms only when the hue changes.
+fn solve_run(requests: &[(f64, f64, f64)]) -> Vec<[u8; 4]> {
+    let options = SolverOptions::default();
+    let mut hue: Option<HueTerms> = None;
+    requests
+        .iter()
+        .map(|&(hue_degrees, chroma, lstar)| {
+            if is_achromatic(hue_degrees, chroma, lstar) {
+                return argb_from_lstar(lstar);
+            }
+            let hue_degrees = sanitize_degrees_double(hue_degrees);
+            let terms = match hue {
+                Some(ref terms) if terms.degrees == hue_degrees => terms,
+                _ => hue.insert(HueTerms::new(hue_degrees)),
+            };
+            solve_chromatic(terms, chroma, lstar, &options)
+        })
+        .collect()
+}
+
+/// Finds sRGB colors for many (hue, chroma, L*) requests at once.
+///
+/// Equivalent to calling [`solve_to_int`] on every request, but the work that only depends on
+/// the hue is shared between consecutive requests with the same hue, so tonal ramps and other
+/// runs of a single hue are cheaper. With the `rayon` feature the requests are split into chunks
+/// and solved in parallel. Results are never cached.
+///
+/// # Arguments
+///
+/// * `requests`: (hue in degrees, chroma, L*) triples.
+///
+/// # Returns
+/// * The sRGB color for each request, in the same order.
+pub fn solve_many(requests: &[(f64, f64, f64)]) -> Vec<[u8; 4]> {
+    #[cfg(feature = "rayon")]
+    {
+        use rayon::prelude::*;
+        requests.par_chunks(1024).flat_map_iter(solve_run).collect()
+    }
+    #[cfg(not(feature = "rayon"))]
+    {
+        solve_run(requests)
+    }
 }
 
 /// Finds an sRGB color with the given hue, chroma, and L*, if possible.
@@ -645,30 +914,268 @@ fn find_result_by_j(hue_radians: f64, chroma: f64, y: f64) -> [u8; 4] {
 /// * A hexadecimal representing the sRGB color. The color has sufficiently close hue,
 /// chroma, and L* to the desired values, if possible; otherwise, the hue and L* will be,
 /// sufficiently close, and chroma will be maximized.
-pub fn solve_to_int(mut hue_degrees: f64, chroma: f64, lstar: f64) -> [u8; 4] {
-    if chroma < 0.0001 || !(0.0001..=99.9999).contains(&lstar) {
-        return argb_from_lstar(lstar);
-    }
-    hue_degrees = sanitize_degrees_double(hue_degrees);
-    let hue_radians = hue_degrees / 180.0 * PI;
-    let y = y_from_lstar(lstar);
-    let exact_answer = find_result_by_j(hue_radians, chroma, y);
-    if exact_answer != [0, 0, 0, 0] {
-        return exact_answer;
-    }
-    let linrgb = bisect_to_limit(y, hue_radians);
-    argb_from_linrgb(linrgb)
+#[allow(clippy::doc_lazy_continuation)]
+pub fn solve_to_int(hue_degrees: f64, chroma: f64, lstar: f64) -> [u8; 4] {
+    solve(hue_degrees, chroma, lstar)
+}
+
+/// Finds an sRGB color with the given hue, chroma, and L*, if possible, searching as hard as
+/// `options` allow. Results are never cached.
+///
+/// # Arguments
+///
+/// * `hue_degrees`: The desired hue, in degrees.
+/// * `chroma`: The desired chroma.
+/// * `lstar`: The desired L*.
+/// * `options`: Precision settings; see [`SolverOptions`] for the trade-offs.
+///
+/// # Returns
+/// * A hexadecimal representing the sRGB color.
+pub fn solve_to_int_with_options(
+    hue_degrees: f64,
+    chroma: f64,
+    lstar: f64,
+    options: &SolverOptions,
+) -> [u8; 4] {
+    solve_with_options(hue_degrees, chroma, lstar, options)
 }
 
+/// Finds a CAM16 color with the given hue, chroma, and L*, if possible.
+///
+/// # Arguments
+///
+/// * `hue_degrees`: The desired hue, in degrees.
+/// * `chroma`: The desired chroma.
+/// * `lstar`: The desired L*.
+///
+/// # Returns
+/// * The CAM16 representation of the color found by [`solve_to_int`].
 pub fn solve_to_cam(hue_degrees: f64, chroma: f64, lstar: f64) -> Cam16 {
-    Cam16::from_argb(solve_to_int(hue_degrees, chroma, lstar))
+    solve_to_int_and_cam(hue_degrees, chroma, lstar).1
+}
+
+/// Finds an sRGB color with the given hue, chroma, and L*, along with the CAM16 representation
+/// returned by [`solve_to_cam`]. Solves and analyzes the color once.
+pub(crate) fn solve_to_int_and_cam(hue_degrees: f64, chroma: f64, lstar: f64) -> ([u8; 4], Cam16) {
+    let argb = solve(hue_degrees, chroma, lstar);
+    (argb, Cam16::from_argb(argb))
 }
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+    use crate::utils::color::lstar_from_argb;
+    use crate::utils::math::assert_matrices_are_inverses;
+
+    #[test]
+    fn solve_to_cam_matches_analysis() {
+        for hue in (0..360).step_by(15) {
+            for chroma in [8.0, 16.0, 32.0, 48.0] {
+                for tone in [30.0, 50.0, 70.0] {
+                    let hue = hue as f64;
+                    let cam = solve_to_cam(hue, chroma, tone);
+                    let analyzed = Cam16::from_argb(solve_to_int(hue, chroma, tone));
+                    assert_eq!(cam, analyzed);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn solve_to_int_keeps_tone() {
+        for tone in [10.0, 25.0, 50.0, 75.0, 90.0] {
+            let argb = solve_to_int(120.0, 40.0, tone);
+            assert!((lstar_from_argb(argb) - tone).abs() < 0.5);
+        }
+    }
+
+    #[test]
+    fn solve_to_cam_clamps_unattainable_chroma() {
+        let cam = solve_to_cam(270.0, 150.0, 50.0);
+        let analyzed = Cam16::from_argb(solve_to_int(270.0, 150.0, 50.0));
+        assert!(cam.chroma() < 150.0);
+        assert_eq!(cam, analyzed);
+    }
+
+    #[test]
+    fn solve_achromatic() {
+        assert_eq!(solve_to_int(0.0, 0.0, 50.0), argb_from_lstar(50.0));
+        assert_eq!(solve_to_int(42.0, 30.0, 100.0), [0xff, 0xff, 0xff, 0xff]);
+        assert_eq!(solve_to_int(42.0, 30.0, 0.0), [0xff, 0x00, 0x00, 0x00]);
+    }
+
     #[test]
-    fn placeholder_test() {
-        let sum = 2 + 2;
-        assert_eq!(sum, 4);
+    fn cache_solves_rounded_requests() {
+        let requests = [
+            (282.78, 48.0, 40.0),
+            (30.04, 16.33, 87.5),
+            (359.97, 24.0, 10.0),
+        ];
+        let rounded: Vec<[u8; 4]> = requests
+            .iter()
+            .map(|&(hue, chroma, tone)| {
+                let [hue, chroma, tone] =
+                    cache_key(hue, chroma, tone).map(|value| value as f64 / 10.0);
+                solve_to_int(hue, chroma, tone)
+            })
+            .collect();
+        enable_cache(16);
+        for _ in 0..2 {
+            for (&(hue, chroma, tone), expected) in requests.iter().zip(&rounded) {
+                assert_eq!(solve_to_int(hue, chroma, tone), *expected);
+            }
+        }
+        disable_cache();
+        // Grays skip the cache and are solved as requested.
+        enable_cache(16);
+        assert_eq!(solve_to_int(120.0, 0.0, 50.04), argb_from_lstar(50.04));
+        disable_cache();
+    }
+
+    #[test]
+    fn cache_shares_nearby_requests() {
+        enable_cache(16);
+        let solves = SOLVES.with(std::cell::Cell::get);
+        let first = solve_to_int(200.0, 30.0, 50.0);
+        assert_eq!(solve_to_int(200.02, 29.97, 50.04), first);
+        assert_eq!(solve_to_int(560.0, 30.0, 50.0), first);
+        assert_eq!(SOLVES.with(std::cell::Cell::get) - solves, 1);
+        disable_cache();
+    }
+
+    #[test]
+    fn cache_evicts_least_recently_used() {
+        enable_cache(2);
+        let solves = SOLVES.with(std::cell::Cell::get);
+        let misses = || SOLVES.with(std::cell::Cell::get) - solves;
+        solve_to_int(10.0, 30.0, 50.0);
+        solve_to_int(20.0, 30.0, 50.0);
+        // Touch the first, so the second is the least recently used when a third arrives.
+        solve_to_int(10.0, 30.0, 50.0);
+        solve_to_int(30.0, 30.0, 50.0);
+        assert_eq!(misses(), 3);
+        solve_to_int(10.0, 30.0, 50.0);
+        assert_eq!(misses(), 3);
+        solve_to_int(20.0, 30.0, 50.0);
+        assert_eq!(misses(), 4);
+        disable_cache();
+    }
+
+    #[test]
+    fn cache_is_identical_on_the_grid() {
+        let requests: Vec<(f64, f64, f64)> = (0..360)
+            .step_by(30)
+            .flat_map(|hue| [16.0, 48.0].map(|chroma| (hue as f64, chroma)))
+            .flat_map(|(hue, chroma)| [10.0, 40.0, 90.0].map(|tone| (hue, chroma, tone)))
+            .collect();
+        let uncached: Vec<[u8; 4]> = requests
+            .iter()
+            .map(|&(hue, chroma, tone)| solve_to_int(hue, chroma, tone))
+            .collect();
+        // A small capacity also exercises eviction.
+        for capacity in [7, 1024] {
+            enable_cache(capacity);
+            for _ in 0..2 {
+                for (&(hue, chroma, tone), expected) in requests.iter().zip(&uncached) {
+                    assert_eq!(solve_to_int(hue, chroma, tone), *expected);
+                }
+            }
+            disable_cache();
+        }
+    }
+
+    #[test]
+    fn default_options_match_solve_to_int() {
+        let options = SolverOptions::default();
+        for hue in (0..360).step_by(30) {
+            for chroma in [10.0, 40.0, 120.0] {
+                for tone in [20.0, 50.0, 80.0] {
+                    let hue = hue as f64;
+                    assert_eq!(
+                        solve_to_int_with_options(hue, chroma, tone, &options),
+                        solve_to_int(hue, chroma, tone)
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn fast_options_stay_within_tolerance() {
+        let fast = SolverOptions::fast();
+        for hue in (0..360).step_by(10) {
+            for chroma in [5.0, 20.0, 40.0, 60.0, 90.0, 150.0] {
+                for tone in [5.0, 20.0, 35.0, 50.0, 65.0, 80.0, 95.0] {
+                    let hue = hue as f64;
+                    let precise = solve_to_int(hue, chroma, tone);
+                    let quick = solve_to_int_with_options(hue, chroma, tone, &fast);
+                    let (precise_cam, quick_cam) =
+                        (Cam16::from_argb(precise), Cam16::from_argb(quick));
+                    assert!((precise_cam.chroma() - quick_cam.chroma()).abs() < 2.0);
+                    assert!((lstar_from_argb(precise) - lstar_from_argb(quick)).abs() < 1.0);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn solve_many_matches_solve_to_int() {
+        // xorshift64, so the inputs are random but reproducible.
+        let mut state = 0x2545_f491_4f6c_dd1d_u64;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state >> 11) as f64 / (1u64 << 53) as f64
+        };
+        let mut requests = Vec::new();
+        for _ in 0..500 {
+            let hue = next() * 720.0 - 180.0;
+            let chroma = next() * 160.0;
+            // Runs of tones with a shared hue exercise the reuse of hue terms.
+            for _ in 0..(next() * 5.0) as usize + 1 {
+                requests.push((hue, chroma, next() * 110.0 - 5.0));
+            }
+        }
+        requests.push((42.0, 0.0, 50.0));
+        let expected: Vec<[u8; 4]> = requests
+            .iter()
+            .map(|&(hue, chroma, tone)| solve_to_int(hue, chroma, tone))
+            .collect();
+        assert_eq!(solve_many(&requests), expected);
+        assert!(solve_many(&[]).is_empty());
+    }
+
+    #[test]
+    fn solver_matrices_are_inverses() {
+        assert_matrices_are_inverses(
+            SCALED_DISCOUNT_FROM_LINRGB,
+            LINRGB_FROM_SCALED_DISCOUNT,
+            1e-7,
+        );
+    }
+
+    #[test]
+    fn non_finite_requests() {
+        let gray = argb_from_lstar(50.0);
+        for hue in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            assert_eq!(solve_to_int(hue, 40.0, 50.0), gray);
+            assert_eq!(solve_to_cam(hue, 40.0, 50.0), Cam16::from_argb(gray));
+        }
+        assert_eq!(solve_to_int(30.0, f64::NAN, 50.0), gray);
+        assert_eq!(solve_to_int(30.0, f64::NEG_INFINITY, 50.0), gray);
+        // Unbounded chroma is a valid request for the most chromatic color.
+        assert_eq!(
+            solve_to_int(30.0, f64::INFINITY, 50.0),
+            solve_to_int(30.0, 1000.0, 50.0)
+        );
+        assert_eq!(solve_to_int(30.0, 40.0, f64::NAN), [255, 0, 0, 0]);
+        assert_eq!(solve_to_int(30.0, 40.0, f64::NEG_INFINITY), [255, 0, 0, 0]);
+        assert_eq!(
+            solve_to_int(30.0, 40.0, f64::INFINITY),
+            [255, 255, 255, 255]
+        );
+        let requests = [(f64::NAN, 40.0, 50.0), (30.0, f64::NAN, 50.0)];
+        assert_eq!(solve_many(&requests), vec![gray, gray]);
     }
 }
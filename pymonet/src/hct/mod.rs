@@ -10,14 +10,20 @@
 //! Unlike contrast ratio, measuring contrast in L* is linear, and simple to
 //! calculate. A difference of 40 in HCT tone guarantees a contrast ratio >= 3.0,
 //! and a difference of 50 guarantees a contrast ratio >= 4.5.
+use crate::contrast;
 use crate::hct::cam16::Cam16;
-use crate::utils::color::lstar_from_argb;
+use crate::utils::color::{lstar_from_argb, Argb};
+use crate::utils::string::{hex_from_argb, try_argb_from_hex, ParseColorError};
+use std::fmt;
 
 pub mod cam16;
 pub mod hct_solver;
 pub mod viewing_conditions;
 
-#[derive(Default)]
+// A chroma beyond anything representable in sRGB, so the solver always lands on the gamut boundary.
+const MAX_CHROMA_REQUEST: f64 = 200.0;
+
+#[derive(Debug, Default, Clone, Copy)]
 pub struct Hct {
     internal_hue: f64,
     internal_chroma: f64,
@@ -33,15 +39,16 @@ impl Hct {
     ///
     /// # Arguments
     ///
-    /// * `hue`: hue 0 <= hue < 360; invalid values are corrected.
+    /// * `hue`: hue 0 <= hue < 360; invalid values are corrected. A NaN or infinite hue gives a
+    ///   gray, as does a NaN chroma.
     /// * `chrome`: chroma 0 <= chroma < ?; Informally, colorfulness. The color returned may be lower than the requested chroma. Chroma has a different maximum for any given hue and tone.
-    /// * `tone`: tone 0 <= tone <= 100; invalid values are corrected.
+    /// * `tone`: tone 0 <= tone <= 100; invalid values are corrected. A NaN tone gives black.
     ///
     /// # Returns
     /// * HCT representation of a color in default viewing conditions.
     pub fn from(hue: f64, chroma: f64, tone: f64) -> Hct {
         let mut htc = Hct::default();
-        htc.set_internal_state(hct_solver::solve_to_int(hue, chroma, tone));
+        htc.set_solved_state(hue, chroma, tone);
         htc
     }
 
@@ -49,16 +56,49 @@ impl Hct {
     ///
     /// # Arguments
     ///
-    /// * `argb`: ARGB representation of a color.
+    /// * `argb`: ARGB representation of a color, as `[u8; 4]`, [`Argb`], or a `u32` packed as
+    ///   0xAARRGGBB.
     ///
     /// # Returns
     /// * HCT representation of a color in default viewing conditions
-    pub fn from_int(argb: [u8; 4]) -> Hct {
+    pub fn from_int(argb: impl Into<Argb>) -> Hct {
         let mut htc = Hct::default();
-        htc.set_internal_state(argb);
+        htc.set_internal_state(argb.into().0);
         htc
     }
 
+    /// Create an HCT color from a hex color string.
+    ///
+    /// # Arguments
+    ///
+    /// * `hex`: Hex color string with or without a single leading #, using 3, 6, or 8 hex
+    ///   characters, as [`try_argb_from_hex`] accepts.
+    ///
+    /// # Returns
+    /// * HCT representation of the color in default viewing conditions, or the reason the string
+    ///   could not be parsed.
+    pub fn from_hex(hex: &str) -> Result<Hct, ParseColorError> {
+        try_argb_from_hex(hex).map(Hct::from_int)
+    }
+
+    /// The maximum chroma achievable in sRGB for a hue and tone pair, useful for clamping chroma
+    /// sliders instead of letting the solver silently desaturate.
+    ///
+    /// The value is found by asking the solver for an unattainable chroma, which makes it return
+    /// the color on the gamut boundary at that hue and tone. As that color is rounded to 8-bit
+    /// sRGB the result is accurate to within about 0.5 chroma.
+    ///
+    /// # Arguments
+    ///
+    /// * `hue`: 0 <= hue < 360; invalid values are corrected.
+    /// * `tone`: 0 <= tone <= 100; invalid values are corrected.
+    ///
+    /// # Returns
+    /// * The chroma of the most colorful color with the given hue and tone.
+    pub fn max_chroma(hue: f64, tone: f64) -> f64 {
+        Hct::from(hue, MAX_CHROMA_REQUEST, tone).chroma()
+    }
+
     /// Getter for `hue`
     ///
     /// # Returns
@@ -91,6 +131,22 @@ impl Hct {
         self.argb
     }
 
+    /// Getter for the CAM16 representation of the calculated HCT color
+    ///
+    /// # Returns
+    /// * The current color value in CAM16, assuming default viewing conditions
+    pub fn to_cam16(&self) -> Cam16 {
+        Cam16::from_argb(self.argb)
+    }
+
+    /// Getter for the calculated HCT color as a hex string
+    ///
+    /// # Returns
+    /// * The current color value as a hex string, ex. #ff0000 for red.
+    pub fn to_hex(&self) -> String {
+        hex_from_argb(self.argb)
+    }
+
     /// Set the hue of this color. Chroma may decrease because chroma has a different maximum for any
     /// given hue and tone.
     ///
@@ -98,11 +154,7 @@ impl Hct {
     ///
     /// * `hue`: 0 <= newHue < 360; invalid values are corrected.
     pub fn set_hue(&mut self, hue: f64) {
-        self.set_internal_state(hct_solver::solve_to_int(
-            hue,
-            self.internal_chroma,
-            self.internal_tone,
-        ))
+        self.set_solved_state(hue, self.internal_chroma, self.internal_tone)
     }
 
     /// Set the chroma of this color. Chroma may decrease because chroma has a different maximum for
@@ -112,11 +164,7 @@ impl Hct {
     ///
     /// * `chroma`: 0 <= newChroma < ?
     pub fn set_chroma(&mut self, chroma: f64) {
-        self.set_internal_state(hct_solver::solve_to_int(
-            self.internal_hue,
-            chroma,
-            self.internal_tone,
-        ))
+        self.set_solved_state(self.internal_hue, chroma, self.internal_tone)
     }
 
     /// Set the tone of this color. Chroma may decrease because chroma has a different maximum for any
@@ -126,27 +174,163 @@ impl Hct {
     ///
     /// * `tone`: 0 <= newTone <= 100; invalid valids are corrected.
     pub fn set_tone(&mut self, tone: f64) {
-        self.set_internal_state(hct_solver::solve_to_int(
-            self.internal_hue,
-            self.internal_chroma,
-            tone,
-        ))
+        self.set_solved_state(self.internal_hue, self.internal_chroma, tone)
+    }
+
+    /// Create a copy of this color with a different hue. Chroma may decrease because chroma has a
+    /// different maximum for any given hue and tone.
+    ///
+    /// # Arguments
+    ///
+    /// * `hue`: 0 <= newHue < 360; invalid values are corrected.
+    pub fn with_hue(self, hue: f64) -> Hct {
+        Hct::from(hue, self.internal_chroma, self.internal_tone)
+    }
+
+    /// Create a copy of this color with a different chroma. Chroma may decrease because chroma has
+    /// a different maximum for any given hue and tone.
+    ///
+    /// # Arguments
+    ///
+    /// * `chroma`: 0 <= newChroma < ?
+    pub fn with_chroma(self, chroma: f64) -> Hct {
+        Hct::from(self.internal_hue, chroma, self.internal_tone)
+    }
+
+    /// Create a copy of this color with a different tone. Chroma may decrease because chroma has a
+    /// different maximum for any given hue and tone.
+    ///
+    /// # Arguments
+    ///
+    /// * `tone`: 0 <= newTone <= 100; invalid values are corrected.
+    pub fn with_tone(self, tone: f64) -> Hct {
+        Hct::from(self.internal_hue, self.internal_chroma, tone)
+    }
+
+    /// Perceptual distance between two colors, measured as delta E in CAM16-UCS.
+    ///
+    /// # Arguments
+    ///
+    /// * `other`: The color to measure the distance to.
+    ///
+    /// # Returns
+    /// * The CAM16-UCS delta E between the two colors; 0.0 for identical colors.
+    pub fn distance(&self, other: &Hct) -> f64 {
+        self.to_cam16().distance(&other.to_cam16())
+    }
+
+    /// Re-solve this color at each of the requested tones, keeping hue and chroma. Chroma may
+    /// decrease at tones where it is not attainable.
+    ///
+    /// # Arguments
+    ///
+    /// * `tones`: Tones to solve for, 0 <= tone <= 100; fractional tones are allowed.
+    ///
+    /// # Returns
+    /// * One HCT color per requested tone, in the same order.
+    pub fn tones(&self, tones: &[f64]) -> Vec<Hct> {
+        tones.iter().map(|&tone| self.with_tone(tone)).collect()
+    }
+
+    /// Re-solve this color at each of the requested tones, keeping hue and chroma.
+    ///
+    /// # Arguments
+    ///
+    /// * `tones`: Tones to solve for, 0 <= tone <= 100; fractional tones are allowed.
+    ///
+    /// # Returns
+    /// * One ARGB color per requested tone, in the same order.
+    pub fn tones_to_int(&self, tones: &[f64]) -> Vec<[u8; 4]> {
+        tones
+            .iter()
+            .map(|&tone| self.with_tone(tone).to_int())
+            .collect()
+    }
+
+    /// Move the tone of this color until it reaches a contrast ratio against a background,
+    /// keeping hue and chroma.
+    ///
+    /// The direction (lighter or darker) needing the smaller tone change is preferred, falling
+    /// back to the other direction when the preferred one cannot reach `ratio`. If neither can,
+    /// the tone is clamped to whichever extreme (0 or 100) contrasts most with the background.
+    ///
+    /// # Arguments
+    ///
+    /// * `background`: The color the result must contrast with.
+    /// * `ratio`: Desired contrast ratio, 1.0 to 21.0.
+    ///
+    /// # Returns
+    /// * This color unchanged if it already meets `ratio`, otherwise a copy with an adjusted tone.
+    pub fn adjust_tone_for_contrast(&self, background: &Hct, ratio: f64) -> Hct {
+        let tone = self.internal_tone;
+        let background_tone = background.tone();
+        if contrast::ratio_of_tones(tone, background_tone) >= ratio {
+            return *self;
+        }
+        let lighter = contrast::lighter(background_tone, ratio);
+        let darker = contrast::darker(background_tone, ratio);
+        let new_tone = match (lighter, darker) {
+            (Some(light), Some(dark)) => {
+                if (light - tone).abs() <= (tone - dark).abs() {
+                    light
+                } else {
+                    dark
+                }
+            }
+            (Some(light), None) => light,
+            (None, Some(dark)) => dark,
+            (None, None) => {
+                if contrast::ratio_of_tones(100.0, background_tone)
+                    >= contrast::ratio_of_tones(0.0, background_tone)
+                {
+                    100.0
+                } else {
+                    0.0
+                }
+            }
+        };
+        self.with_tone(new_tone)
+    }
+
+    /// Solve for a color, taking hue and chroma from the solver's CAM16 analysis of the ARGB value
+    /// it settled on, so that hue, chroma, and tone always describe the color returned by
+    /// [`Hct::to_int`].
+    fn set_solved_state(&mut self, hue: f64, chroma: f64, tone: f64) {
+        let (argb, cam) = hct_solver::solve_to_int_and_cam(hue, chroma, tone);
+        self.set_analyzed_state(argb, &cam)
     }
 
     fn set_internal_state(&mut self, argb: [u8; 4]) {
+        self.set_analyzed_state(argb, &Cam16::from_argb(argb))
+    }
+
+    fn set_analyzed_state(&mut self, argb: [u8; 4], cam: &Cam16) {
         self.argb = argb;
-        let cam = Cam16::from_argb(argb);
         self.internal_hue = cam.hue();
         self.internal_chroma = cam.chroma();
         self.internal_tone = lstar_from_argb(argb);
     }
 }
 
+impl fmt::Display for Hct {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "HCT({:.1}, {:.1}, {:.1}) {}",
+            self.internal_hue,
+            self.internal_chroma,
+            self.internal_tone,
+            self.to_hex()
+        )
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::hct::viewing_conditions::ViewingConditions;
-    use crate::utils::color::y_from_lstar;
+    use crate::utils::color::{argb_from_lstar, y_from_lstar};
+    use crate::utils::math::difference_degrees;
     use assert_approx_eq::assert_approx_eq;
 
     const BLACK: [u8; 4] = [0xff, 0x00, 0x00, 0x00];
@@ -162,7 +346,7 @@ mod tests {
         let cam = Cam16::from_argb(RED);
         let conditions = ViewingConditions::default();
         println!("{conditions:#?}");
-        let color = cam.viewed(conditions);
+        let color = cam.viewed_in(&conditions);
         assert_eq!(color, RED)
     }
 
@@ -242,4 +426,228 @@ mod tests {
         gamut_map_test(WHITE);
         gamut_map_test(BLACK);
     }
+
+    #[test]
+    fn display_red() {
+        let hct = Hct::from_int(RED);
+        assert_eq!(format!("{hct}"), "HCT(27.4, 113.4, 53.2) #ff0000");
+        assert_eq!(hct.to_hex(), "#ff0000");
+    }
+
+    #[test]
+    fn display_blue() {
+        let hct = Hct::from_int(BLUE);
+        assert_eq!(format!("{hct}"), "HCT(282.8, 87.2, 32.3) #0000ff");
+        assert_eq!(hct.to_hex(), "#0000ff");
+    }
+
+    #[test]
+    fn debug_shows_internal_state() {
+        let debug = format!("{:?}", Hct::from_int(RED));
+        assert!(debug.contains("internal_hue"));
+        assert!(debug.contains("argb"));
+    }
+
+    #[test]
+    fn hex_round_trip() {
+        assert_eq!(Hct::from_hex("#4285f4").unwrap().to_hex(), "#4285f4");
+        assert_eq!(Hct::from_hex("#09c").unwrap().to_hex(), "#0099cc");
+        assert_eq!(Hct::from_hex("ff0000").unwrap().to_int(), RED);
+        assert_eq!(
+            Hct::from_hex("##ff0000").unwrap_err(),
+            ParseColorError::RepeatedHash
+        );
+    }
+
+    #[test]
+    fn from_hex_propagates_errors() {
+        assert_eq!(
+            Hct::from_hex("#4285f").unwrap_err(),
+            ParseColorError::InvalidLength(5)
+        );
+        assert_eq!(
+            Hct::from_hex("#42z5f4").unwrap_err(),
+            ParseColorError::InvalidHexDigit {
+                digit: 'z',
+                position: 3
+            }
+        );
+    }
+
+    #[test]
+    fn to_cam16_matches_hct() {
+        for argb in [RED, GREEN, BLUE, [0xff, 0x42, 0x85, 0xf4]] {
+            let hct = Hct::from_int(argb);
+            let cam = hct.to_cam16();
+            assert_approx_eq!(hct.hue(), cam.hue(), 0.001);
+            assert_approx_eq!(hct.chroma(), cam.chroma(), 0.001);
+        }
+    }
+
+    #[test]
+    fn with_builders_leave_original_unchanged() {
+        let seed = Hct::from_int([0xff, 0x42, 0x85, 0xf4]);
+        let derived = seed.with_tone(40.0).with_chroma(16.0);
+        assert_eq!(seed.to_int(), [0xff, 0x42, 0x85, 0xf4]);
+
+        let expected = Hct::from(
+            Hct::from(seed.hue(), seed.chroma(), 40.0).hue(),
+            16.0,
+            Hct::from(seed.hue(), seed.chroma(), 40.0).tone(),
+        );
+        assert_eq!(derived.to_int(), expected.to_int());
+    }
+
+    #[test]
+    fn with_builders_match_from() {
+        let seed = Hct::from_int(RED);
+        assert_eq!(
+            seed.with_hue(200.0).to_int(),
+            Hct::from(200.0, seed.chroma(), seed.tone()).to_int()
+        );
+        assert_eq!(
+            seed.with_chroma(30.0).to_int(),
+            Hct::from(seed.hue(), 30.0, seed.tone()).to_int()
+        );
+        assert_eq!(
+            seed.with_tone(80.0).to_int(),
+            Hct::from(seed.hue(), seed.chroma(), 80.0).to_int()
+        );
+    }
+
+    #[test]
+    fn max_chroma_at_extremes() {
+        assert_approx_eq!(0.0, Hct::max_chroma(27.4, 0.0), 0.001);
+        // White is slightly chromatic in CAM16 under the D65 white point.
+        assert!(Hct::max_chroma(27.4, 100.0) < 3.0);
+    }
+
+    #[test]
+    fn max_chroma_red() {
+        assert!(Hct::max_chroma(27.4, 53.2) > 100.0);
+    }
+
+    #[test]
+    fn max_chroma_is_attainable() {
+        for hue in [0.0, 60.0, 120.0, 180.0, 240.0, 300.0] {
+            for tone in [20.0, 50.0, 80.0] {
+                let max = Hct::max_chroma(hue, tone);
+                let below = Hct::from(hue, max - 1.0, tone).chroma();
+                assert!(
+                    (below - (max - 1.0)).abs() < 0.5,
+                    "{hue} {tone} {max} {below}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn adjust_tone_for_contrast_prefers_smaller_change() {
+        let background = Hct::from(210.0, 16.0, 50.0);
+        let darker_accent = Hct::from(30.0, 40.0, 45.0);
+        let lighter_accent = Hct::from(30.0, 40.0, 60.0);
+
+        let darkened = darker_accent.adjust_tone_for_contrast(&background, 3.0);
+        assert!(darkened.tone() < background.tone());
+        assert!(contrast::ratio_of_tones(darkened.tone(), background.tone()) >= 3.0);
+
+        let lightened = lighter_accent.adjust_tone_for_contrast(&background, 3.0);
+        assert!(lightened.tone() > background.tone());
+        assert!(contrast::ratio_of_tones(lightened.tone(), background.tone()) >= 3.0);
+        assert!(difference_degrees(lightened.hue(), lighter_accent.hue()) < 2.0);
+    }
+
+    #[test]
+    fn adjust_tone_for_contrast_falls_back() {
+        // At 4.5:1 against tone 50 only the darker direction is reachable.
+        let background = Hct::from(210.0, 16.0, 50.0);
+        let accent = Hct::from(30.0, 40.0, 60.0);
+        let adjusted = accent.adjust_tone_for_contrast(&background, 4.5);
+        assert!(adjusted.tone() < background.tone());
+        assert!(contrast::ratio_of_tones(adjusted.tone(), background.tone()) >= 4.5);
+    }
+
+    #[test]
+    fn adjust_tone_for_contrast_best_effort() {
+        let background = Hct::from(210.0, 16.0, 50.0);
+        let accent = Hct::from(30.0, 40.0, 60.0);
+        let adjusted = accent.adjust_tone_for_contrast(&background, 21.0);
+        assert_approx_eq!(0.0, adjusted.tone(), 0.001);
+    }
+
+    #[test]
+    fn adjust_tone_for_contrast_keeps_sufficient_color() {
+        let background = Hct::from(210.0, 16.0, 90.0);
+        let accent = Hct::from(30.0, 40.0, 30.0);
+        let adjusted = accent.adjust_tone_for_contrast(&background, 4.5);
+        assert_eq!(adjusted.to_int(), accent.to_int());
+    }
+
+    #[test]
+    fn tones_keep_hue() {
+        let seed = Hct::from(200.0, 30.0, 50.0);
+        let requested = [15.0, 17.5, 25.0, 40.0, 60.0, 75.0, 90.0];
+        let ramp = seed.tones(&requested);
+        assert_eq!(ramp.len(), requested.len());
+        for (hct, tone) in ramp.iter().zip(requested) {
+            assert!(difference_degrees(hct.hue(), seed.hue()) < 1.0);
+            assert_approx_eq!(tone, hct.tone(), 0.5);
+        }
+        assert_eq!(
+            seed.tones_to_int(&requested),
+            ramp.iter().map(Hct::to_int).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn distance() {
+        let red = Hct::from_int(RED);
+        let blue = Hct::from_int(BLUE);
+        let almost_red = Hct::from_int([0xff, 0xf8, 0x04, 0x02]);
+        assert_eq!(red.distance(&red), 0.0);
+        assert_eq!(red.distance(&blue), blue.distance(&red));
+        assert!(red.distance(&blue) > 10.0 * red.distance(&almost_red));
+    }
+
+    #[test]
+    fn solved_state_matches_analysis() {
+        for hue in (0..360).step_by(15) {
+            for chroma in [0.0, 5.0, 16.0, 48.0, 120.0] {
+                for tone in (0..=100).step_by(5) {
+                    let (hue, tone) = (hue as f64, tone as f64);
+                    let fast = Hct::from(hue, chroma, tone);
+                    let argb = hct_solver::solve_to_int(hue, chroma, tone);
+                    let cam = hct_solver::solve_to_cam(hue, chroma, tone);
+                    let slow = Hct::from_int(argb);
+                    assert_eq!(fast.to_int(), argb);
+                    assert_eq!((fast.hue(), fast.chroma()), (cam.hue(), cam.chroma()));
+                    assert_eq!(fast.hue(), slow.hue());
+                    assert_eq!(fast.chroma(), slow.chroma());
+                    assert_eq!(fast.tone(), slow.tone());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn from_int_accepts_packed_u32() {
+        let packed = Hct::from_int(0xff4285f4u32);
+        let unpacked = Hct::from_int([0xff, 0x42, 0x85, 0xf4]);
+        assert_eq!(packed.to_int(), unpacked.to_int());
+        assert_eq!(packed.hue(), unpacked.hue());
+    }
+
+    #[test]
+    fn from_non_finite() {
+        for hue in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            let hct = Hct::from(hue, 40.0, 50.0);
+            assert_eq!(hct.to_int(), argb_from_lstar(50.0));
+            assert!(hct.hue().is_finite());
+        }
+        assert_eq!(
+            Hct::from(30.0, f64::NAN, 50.0).to_int(),
+            argb_from_lstar(50.0)
+        );
+        assert_eq!(Hct::from(30.0, 40.0, f64::NAN).to_int(), BLACK);
+    }
 }
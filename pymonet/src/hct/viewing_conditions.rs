@@ -11,7 +11,7 @@ use std::f64::consts::PI;
 /// measured as a slightly chromatic blue by CAM16. (roughly, hue 203, chroma 3, lightness 100)
 /// <p>This class caches intermediate values of the CAM16 conversion process that depend only on
 /// viewing conditions, enabling speed ups.
-#[derive(Clone, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct ViewingConditions {
     aw: f64,
     nbb: f64,
@@ -23,6 +23,9 @@ pub struct ViewingConditions {
     fl: f64,
     fl_root: f64,
     z: f64,
+    chroma_factor: f64,
+    lightness_exponent: f64,
+    inverse_lightness_exponent: f64,
 }
 
 impl ViewingConditions {
@@ -56,6 +59,18 @@ impl ViewingConditions {
     pub fn z(&self) -> f64 {
         self.z
     }
+    /// `(1.64 - 0.29^n)^0.73`, the factor of CAM16 chroma that depends only on `n`.
+    pub fn chroma_factor(&self) -> f64 {
+        self.chroma_factor
+    }
+    /// `c * z`, the exponent taking the achromatic response to CAM16 lightness.
+    pub fn lightness_exponent(&self) -> f64 {
+        self.lightness_exponent
+    }
+    /// `1 / c / z`, the exponent taking CAM16 lightness back to the achromatic response.
+    pub fn inverse_lightness_exponent(&self) -> f64 {
+        self.inverse_lightness_exponent
+    }
 
     /// Create ViewingConditions from a simple, physically relevant, set of parameters.
     ///
@@ -76,13 +91,16 @@ impl ViewingConditions {
     /// perform this process on self-luminous objects like displays.
     ///
     /// returns: ViewingConditions
-    pub fn new(
+    #[allow(clippy::doc_lazy_continuation)]
+    pub fn make(
         white_point: [f64; 3],
         adapting_luminance: f64,
         background_lstar: f64,
         surround: f64,
         discounting_illuminant: bool,
     ) -> ViewingConditions {
+        // A background L* of 0 would make `n` zero and `nbb` infinite.
+        let background_lstar = background_lstar.max(0.1);
         let matrix = cam16::XYZ_TO_CAM16RGB;
         let xyz = white_point;
         let r_w = (xyz[0] * matrix[0][0]) + (xyz[1] * matrix[0][1]) + (xyz[2] * matrix[0][2]);
@@ -100,7 +118,8 @@ impl ViewingConditions {
             1.0
         } else {
             f * (1.0 - ((1.0 / 3.6) * ((-adapting_luminance - 42.0) / 92.0).exp()))
-        };
+        }
+        .clamp(0.0, 1.0);
 
         let nc = f;
         let rgb_d = [
@@ -141,12 +160,35 @@ impl ViewingConditions {
             fl,
             fl_root: fl.powf(0.25),
             z,
+            chroma_factor: (1.64 - 0.29f64.powf(n)).powf(0.73),
+            lightness_exponent: c * z,
+            inverse_lightness_exponent: 1.0 / c / z,
         }
     }
+
+    /// Create ViewingConditions from a simple, physically relevant, set of parameters.
+    ///
+    /// See [`ViewingConditions::make`] for a description of the arguments.
+    #[deprecated(note = "use `ViewingConditions::make` instead")]
+    pub fn new(
+        white_point: [f64; 3],
+        adapting_luminance: f64,
+        background_lstar: f64,
+        surround: f64,
+        discounting_illuminant: bool,
+    ) -> ViewingConditions {
+        Self::make(
+            white_point,
+            adapting_luminance,
+            background_lstar,
+            surround,
+            discounting_illuminant,
+        )
+    }
 }
 
 lazy_static! {
-    static ref DEFAULT: ViewingConditions = ViewingConditions::new(
+    static ref DEFAULT: ViewingConditions = ViewingConditions::make(
         WHITE_POINT_D65,
         200.0 / PI * y_from_lstar(50.0) / 100.0,
         50.0,
@@ -155,17 +197,82 @@ lazy_static! {
     );
 }
 
+impl ViewingConditions {
+    /// The default viewing conditions, computed once and shared by every conversion that does not
+    /// ask for specific conditions.
+    pub fn cached_default() -> &'static ViewingConditions {
+        &DEFAULT
+    }
+}
+
 impl Default for ViewingConditions {
     fn default() -> Self {
-        DEFAULT.clone()
+        *DEFAULT
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+    use assert_approx_eq::assert_approx_eq;
+
+    fn default_adapting_luminance() -> f64 {
+        200.0 / PI * y_from_lstar(50.0) / 100.0
+    }
+
+    #[test]
+    fn default_matches_make() {
+        let made = ViewingConditions::make(
+            WHITE_POINT_D65,
+            default_adapting_luminance(),
+            50.0,
+            2.0,
+            false,
+        );
+        let default = ViewingConditions::default();
+        assert_eq!(made, default);
+        assert_eq!(&made, ViewingConditions::cached_default());
+        assert_approx_eq!(0.69, default.c(), 1e-9);
+        assert_approx_eq!(29.981, default.aw(), 0.001);
+    }
+
+    #[test]
+    fn make_dark_surround() {
+        let vc = ViewingConditions::make(
+            WHITE_POINT_D65,
+            default_adapting_luminance(),
+            50.0,
+            0.0,
+            false,
+        );
+        assert_approx_eq!(0.525, vc.c(), 1e-9);
+        assert_approx_eq!(0.8, vc.nc(), 1e-9);
+        assert_approx_eq!(29.961614, vc.aw(), 1e-6);
+        assert_approx_eq!(1.016919, vc.nbb(), 1e-6);
+        assert_approx_eq!(0.388481, vc.fl(), 1e-6);
+        assert_approx_eq!(1.909170, vc.z(), 1e-6);
+        assert_approx_eq!(1.016942, vc.rgb_d()[0], 1e-6);
+        assert_approx_eq!(0.989046, vc.rgb_d()[1], 1e-6);
+        assert_approx_eq!(0.947168, vc.rgb_d()[2], 1e-6);
+    }
+
+    #[test]
+    fn make_dim_d50_discounting() {
+        let vc = ViewingConditions::make([96.422, 100.0, 82.521], 64.0, 20.0, 1.0, true);
+        assert_approx_eq!(0.59, vc.c(), 1e-9);
+        assert_approx_eq!(0.9, vc.nc(), 1e-9);
+        assert_approx_eq!(0.029891, vc.n(), 1e-6);
+        assert_approx_eq!(54.378197, vc.aw(), 1e-6);
+        assert_approx_eq!(1.462956, vc.nbb(), 1e-6);
+        assert_approx_eq!(0.683990, vc.fl(), 1e-6);
+        assert_approx_eq!(1.652889, vc.z(), 1e-6);
+        assert_approx_eq!(1.199793, vc.rgb_d()[2], 1e-6);
+    }
+
     #[test]
-    fn placeholder_test() {
-        let sum = 2 + 2;
-        assert_eq!(sum, 4);
+    fn make_guards_black_background() {
+        let vc = ViewingConditions::make(WHITE_POINT_D65, 11.72, 0.0, 2.0, false);
+        assert!(vc.nbb().is_finite());
+        assert!(vc.aw().is_finite());
     }
 }
@@ -25,7 +25,7 @@
 //!
 //! fn main() {
 //!   // One liner to create an entire palette - both dark and light modes - from a single color.
-//!   let theme = Theme::from_source_color(argb_from_hex("#4c5f9e"));
+//!   let theme = Theme::from_source_color(argb_from_hex(String::from("#4c5f9e")));
 //!   // `theme` should now be a complete set of colors observably similar or related to #4c5f9e.
 //!
 //!   // We can now pluck colors out of the theme by specifying which mode (scheme) - light/dark
@@ -49,18 +49,23 @@
 //!
 //! ## What's left TODO?
 //!
-//! - [ ] Add the ability to load an image file to identify the "key color" for a palette.
+//! - [x] Add the ability to load an image file to identify the "key color" for a palette.
 //! - [ ] Additional testing
 //! - [ ] Optimizations (I know there is a lot of room for improvements)
 //! - [ ] Documentation improvements
-//! - [ ] Incorporate serde for optionally getting JSON for the palette
+//! - [x] Incorporate serde for optionally getting JSON for the palette
 //!
 //! ## Contributing
+// The getting started example keeps its `fn main` as written.
+#![allow(clippy::needless_doctest_main)]
 
+mod macros;
 
 pub mod blend;
+pub mod contrast;
 pub mod hct;
 pub mod palettes;
+pub mod quantize;
 pub mod scheme;
 pub mod score;
 pub mod utils;
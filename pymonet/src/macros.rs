@@ -0,0 +1,25 @@
+//! Macros for writing colors as literals.
+
+/// Parses a hex color literal into ARGB at compile time, so that malformed brand colors are caught
+/// by the compiler instead of panicking at runtime.
+///
+/// Accepts the same 3, 4, 6, or 8 digit forms as
+/// [`argb_from_hex_const`](crate::utils::string::argb_from_hex_const).
+///
+/// ```
+/// const BRAND: [u8; 4] = pymonet::argb!("#4285f4");
+/// assert_eq!(BRAND, [255, 66, 133, 244]);
+/// ```
+///
+/// A malformed literal does not compile:
+///
+/// ```compile_fail
+/// let brand = pymonet::argb!("#4285g4");
+/// ```
+#[macro_export]
+macro_rules! argb {
+    ($hex:expr) => {{
+        const ARGB: [u8; 4] = $crate::utils::string::argb_from_hex_const($hex);
+        ARGB
+    }};
+}
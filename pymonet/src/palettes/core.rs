@@ -1,8 +1,15 @@
 use crate::hct::Hct;
 use crate::palettes::tonal::TonalPalette;
+use crate::utils::color::Argb;
+use crate::utils::math::sanitize_degrees_double;
 
 /// An intermediate concept between the key color for a UI theme, and a full color scheme. 5 sets of
 /// tones are generated, all except one use the same hue as the key color, and all vary in chroma.
+///
+/// With the `serde` feature, the palette serializes as its six [`TonalPalette`]s. Palettes compare
+/// by the hue and chroma of each [`TonalPalette`], ignoring which tones have been generated.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CorePalette {
     pub a1: TonalPalette,
     pub a2: TonalPalette,
@@ -12,9 +19,69 @@ pub struct CorePalette {
     pub error: TonalPalette,
 }
 
+/// Chromas and hues of the palettes of [`CorePalette::with_options`]. The defaults are those
+/// of [`CorePalette::of`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CorePaletteOptions {
+    /// Minimum chroma of the primary palette. A more colorful source keeps its own chroma.
+    pub primary_chroma: f64,
+    pub secondary_chroma: f64,
+    pub tertiary_chroma: f64,
+    /// Degrees from the source hue to the tertiary hue.
+    pub tertiary_hue_shift: f64,
+    pub neutral_chroma: f64,
+    pub neutral_variant_chroma: f64,
+    /// Hue of the error palette, ex. an orange in place of the Material red.
+    pub error_hue: f64,
+    pub error_chroma: f64,
+}
+
+impl Default for CorePaletteOptions {
+    fn default() -> CorePaletteOptions {
+        CorePaletteOptions {
+            primary_chroma: 48.0,
+            secondary_chroma: 16.0,
+            tertiary_chroma: 24.0,
+            tertiary_hue_shift: 60.0,
+            neutral_chroma: 4.0,
+            neutral_variant_chroma: 8.0,
+            error_hue: 25.0,
+            error_chroma: 84.0,
+        }
+    }
+}
+
 impl CorePalette {
+    /// Create the palettes of a theme, like `CorePalette.of` in the other implementations.
+    ///
+    /// # Arguments
+    ///
+    /// * `argb`: The source color, as `[u8; 4]`, [`Argb`], or a `u32` packed as 0xAARRGGBB.
+    ///
+    /// # Returns
+    ///
+    /// * The palettes, with at least 48 chroma for the primary.
+    pub fn of(argb: impl Into<Argb>) -> CorePalette {
+        CorePalette::from_hct(Hct::from_int(argb.into().0), false)
+    }
+
+    #[deprecated(note = "use `CorePalette::of` or `CorePalette::content_of`")]
     pub fn new(argb: [u8; 4], is_content: bool) -> CorePalette {
-        let hct = Hct::from_int(argb);
+        CorePalette::from_hct(Hct::from_int(argb), is_content)
+    }
+
+    /// Create palettes from a source color already in HCT, skipping the conversion.
+    ///
+    /// # Arguments
+    ///
+    /// * `hct`: The source color. Its tone is not used.
+    /// * `is_content`: Whether to keep the source's chroma, as [`CorePalette::content_of`] does,
+    ///   rather than use that of [`CorePalette::of`].
+    ///
+    /// # Returns
+    ///
+    /// * The palettes.
+    pub fn from_hct(hct: Hct, is_content: bool) -> CorePalette {
         let hue = hct.hue();
         let chroma = hct.chroma();
         let error = TonalPalette::from_hue_and_chroma(25.0, 84.0);
@@ -29,23 +96,462 @@ impl CorePalette {
                 error,
             }
         } else {
-            CorePalette {
-                a1: TonalPalette::from_hue_and_chroma(hue, 48.0f64.max(chroma)),
-                a2: TonalPalette::from_hue_and_chroma(hue, 16.),
-                a3: TonalPalette::from_hue_and_chroma(hue + 60., 24.),
-                n1: TonalPalette::from_hue_and_chroma(hue, 4.),
-                n2: TonalPalette::from_hue_and_chroma(hue, 8.),
-                error,
-            }
+            CorePalette::from_hct_with_options(hct, CorePaletteOptions::default())
+        }
+    }
+
+    /// Create a palette like [`CorePalette::of`], with other chromas, tertiary hue, or error
+    /// palette.
+    ///
+    /// # Arguments
+    ///
+    /// * `argb`: The source color, as `[u8; 4]`, [`Argb`], or a `u32` packed as 0xAARRGGBB.
+    /// * `options`: Chromas and hues of the palettes.
+    ///
+    /// # Returns
+    ///
+    /// * The palettes.
+    pub fn with_options(argb: impl Into<Argb>, options: CorePaletteOptions) -> CorePalette {
+        CorePalette::from_hct_with_options(Hct::from_int(argb.into().0), options)
+    }
+
+    fn from_hct_with_options(hct: Hct, options: CorePaletteOptions) -> CorePalette {
+        let hue = hct.hue();
+        let palette = TonalPalette::from_hue_and_chroma;
+        CorePalette {
+            a1: palette(hue, options.primary_chroma.max(hct.chroma())),
+            a2: palette(hue, options.secondary_chroma),
+            a3: palette(
+                sanitize_degrees_double(hue + options.tertiary_hue_shift),
+                options.tertiary_chroma,
+            ),
+            n1: palette(hue, options.neutral_chroma),
+            n2: palette(hue, options.neutral_variant_chroma),
+            error: palette(
+                sanitize_degrees_double(options.error_hue),
+                options.error_chroma,
+            ),
         }
     }
+
+    /// Create a palette for content, ex. an image or album art, that stays faithful to the source
+    /// color, like `CorePalette.contentOf` in the
+    /// other implementations.
+    ///
+    /// Unlike the default, whose chromas are fixed (at least 48 for the primary, 16 for the
+    /// secondary, and 24 for the tertiary), every chroma is derived from the source's chroma `c`:
+    ///
+    /// | Palette | Hue | Chroma |
+    /// | --- | --- | --- |
+    /// | `a1` | source | `c` |
+    /// | `a2` | source | `c / 3` |
+    /// | `a3` | source + 60 | `c / 2` |
+    /// | `n1` | source | `min(c / 12, 4)` |
+    /// | `n2` | source | `min(c / 6, 8)` |
+    ///
+    /// # Arguments
+    ///
+    /// * `argb`: The source color.
+    ///
+    /// # Returns
+    ///
+    /// * The palettes of the content theme. A dull source gives dull palettes.
+    pub fn content_of(argb: impl Into<Argb>) -> CorePalette {
+        CorePalette::from_hct(Hct::from_int(argb.into().0), true)
+    }
+
+    /// Create palettes that stay as colorful as the source color, like `SchemeFidelity` in the
+    /// other implementations.
+    ///
+    /// [`CorePalette::of`] raises the primary chroma to at least 48, so a pastel or grayish seed
+    /// gives a far more saturated theme than it suggests. Use fidelity when the theme should look
+    /// like its seed, ex. a brand color or a muted wallpaper; use the default when the seed is
+    /// only a starting point and a lively UI matters more.
+    ///
+    /// Compared with [`CorePalette::content_of`], the secondary is closer to the primary and the
+    /// neutrals carry more of the source's hue:
+    ///
+    /// | Palette | Hue | Chroma |
+    /// | --- | --- | --- |
+    /// | `a1` | source | `c` |
+    /// | `a2` | source | `max(c - 32, c / 2)` |
+    /// | `a3` | source + 60 | `max(c - 32, c / 2)` |
+    /// | `n1` | source | `c / 8` |
+    /// | `n2` | source | `c / 8 + 4` |
+    ///
+    /// # Arguments
+    ///
+    /// * `argb`: The source color.
+    ///
+    /// # Returns
+    ///
+    /// * The palettes. The error palette is the standard one.
+    pub fn fidelity_of(argb: impl Into<Argb>) -> CorePalette {
+        let hct = Hct::from_int(argb.into().0);
+        let hue = hct.hue();
+        let chroma = hct.chroma();
+        let accent_chroma = (chroma - 32.0).max(chroma * 0.5);
+        let palette = TonalPalette::from_hue_and_chroma;
+        CorePalette {
+            a1: palette(hue, chroma),
+            a2: palette(hue, accent_chroma),
+            a3: palette(sanitize_degrees_double(hue + 60.0), accent_chroma),
+            n1: palette(hue, chroma / 8.0),
+            n2: palette(hue, chroma / 8.0 + 4.0),
+            error: palette(25.0, 84.0),
+        }
+    }
+
+    /// Create a palette from up to three key colors. The secondary and tertiary palettes take
+    /// their hue and chroma from `secondary` and `tertiary` instead of the primary's hue; the
+    /// neutral and error palettes still derive from `primary`.
+    ///
+    /// # Arguments
+    ///
+    /// * `primary`: Key color for the primary, neutral, and neutral variant palettes.
+    /// * `secondary`: Key color for the secondary palette, or `None` to derive it from `primary`.
+    /// * `tertiary`: Key color for the tertiary palette, or `None` to derive it from `primary`.
+    ///
+    /// # Returns
+    ///
+    /// * A palette whose accent palettes each have the chroma [`CorePalette::of`] would give
+    ///   their own key color.
+    pub fn from_colors(
+        primary: [u8; 4],
+        secondary: Option<[u8; 4]>,
+        tertiary: Option<[u8; 4]>,
+    ) -> CorePalette {
+        CorePalette::create_from_colors(false, primary, secondary, tertiary)
+    }
+
+    /// Like [`CorePalette::from_colors`], but for content, keeping each key color's chroma.
+    pub fn content_from_colors(
+        primary: [u8; 4],
+        secondary: Option<[u8; 4]>,
+        tertiary: Option<[u8; 4]>,
+    ) -> CorePalette {
+        CorePalette::create_from_colors(true, primary, secondary, tertiary)
+    }
+
+    /// Create a palette from brand key colors, each defining its palette exactly.
+    ///
+    /// Unlike [`CorePalette::from_colors`], a given key color's hue and chroma are used as is,
+    /// without the minimum chroma of [`CorePalette::of`], so tone 40 of a brand color's palette
+    /// is close to the brand color itself.
+    ///
+    /// # Arguments
+    ///
+    /// * `primary`: Key color of the primary palette. Palettes without a key color are derived
+    ///   from it as in [`CorePalette::of`].
+    /// * `secondary`: Key color of the secondary palette.
+    /// * `tertiary`: Key color of the tertiary palette.
+    /// * `neutral`: Key color of the neutral palette. The neutral variant palette is still derived
+    ///   from `primary`.
+    /// * `error`: Key color of the error palette.
+    ///
+    /// # Returns
+    ///
+    /// * The palettes, usable with [`crate::scheme::Scheme::light_from_core_palette`] and the
+    ///   other scheme constructors.
+    pub fn from_key_colors(
+        primary: [u8; 4],
+        secondary: Option<[u8; 4]>,
+        tertiary: Option<[u8; 4]>,
+        neutral: Option<[u8; 4]>,
+        error: Option<[u8; 4]>,
+    ) -> CorePalette {
+        let standard = CorePalette::of(primary);
+        CorePalette {
+            a1: TonalPalette::from_int(primary),
+            a2: secondary.map_or(standard.a2, TonalPalette::from_int),
+            a3: tertiary.map_or(standard.a3, TonalPalette::from_int),
+            n1: neutral.map_or(standard.n1, TonalPalette::from_int),
+            n2: standard.n2,
+            error: error.map_or(standard.error, TonalPalette::from_int),
+        }
+    }
+
+    fn create_from_colors(
+        is_content: bool,
+        primary: [u8; 4],
+        secondary: Option<[u8; 4]>,
+        tertiary: Option<[u8; 4]>,
+    ) -> CorePalette {
+        let create = |argb| CorePalette::from_hct(Hct::from_int(argb), is_content);
+        let mut palette = create(primary);
+        if let Some(secondary) = secondary {
+            palette.a2 = create(secondary).a1;
+        }
+        if let Some(tertiary) = tertiary {
+            palette.a3 = create(tertiary).a1;
+        }
+        palette
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+    use assert_approx_eq::assert_approx_eq;
+
+    const BLUE: [u8; 4] = [0xff, 0x42, 0x85, 0xf4];
+    const RED: [u8; 4] = [0xff, 0xdb, 0x44, 0x37];
+    const GREEN: [u8; 4] = [0xff, 0x0f, 0x9d, 0x58];
+
+    #[test]
+    fn from_colors() {
+        let palette = CorePalette::from_colors(BLUE, Some(RED), Some(GREEN));
+        assert_approx_eq!(palette.a1.hue(), Hct::from_int(BLUE).hue(), 1e-9);
+        assert_approx_eq!(palette.a2.hue(), Hct::from_int(RED).hue(), 1e-9);
+        assert_approx_eq!(palette.a3.hue(), Hct::from_int(GREEN).hue(), 1e-9);
+        assert_approx_eq!(palette.n1.hue(), Hct::from_int(BLUE).hue(), 1e-9);
+        assert_approx_eq!(palette.n2.hue(), Hct::from_int(BLUE).hue(), 1e-9);
+        assert_approx_eq!(palette.a2.chroma(), CorePalette::of(RED).a1.chroma(), 1e-9);
+    }
+
+    #[test]
+    fn from_colors_defaults_to_primary() {
+        let palette = CorePalette::from_colors(BLUE, None, None);
+        let expected = CorePalette::of(BLUE);
+        assert_eq!(palette.a2.tone(40), expected.a2.tone(40));
+        assert_eq!(palette.a3.tone(40), expected.a3.tone(40));
+    }
+
     #[test]
-    fn placeholder_test() {
-        let sum = 2 + 2;
-        assert_eq!(sum, 4);
+    fn content_of_follows_source_chroma() {
+        // Pure red is about as colorful as sRGB gets.
+        let red = [0xff, 0xff, 0x00, 0x00];
+        let chroma = Hct::from_int(red).chroma();
+        assert!(chroma > 100.0);
+        let palette = CorePalette::content_of(red);
+        assert_approx_eq!(palette.a1.chroma(), chroma, 1e-9);
+        assert_approx_eq!(palette.a2.chroma(), chroma / 3.0, 1e-9);
+        assert_approx_eq!(palette.a3.chroma(), chroma / 2.0, 1e-9);
+        assert_approx_eq!(palette.n1.chroma(), 4.0, 1e-9);
+        assert_approx_eq!(palette.n2.chroma(), 8.0, 1e-9);
+        assert_approx_eq!(palette.a3.hue(), Hct::from_int(red).hue() + 60.0, 1e-9);
+
+        // A muted slate keeps its low chroma instead of being raised to the defaults.
+        let slate = [0xff, 0x60, 0x6a, 0x78];
+        let chroma = Hct::from_int(slate).chroma();
+        assert!(chroma < 16.0);
+        let palette = CorePalette::content_of(slate);
+        assert_approx_eq!(palette.a1.chroma(), chroma, 1e-9);
+        assert_approx_eq!(palette.a2.chroma(), chroma / 3.0, 1e-9);
+        assert_approx_eq!(palette.a3.chroma(), chroma / 2.0, 1e-9);
+        assert_approx_eq!(palette.n1.chroma(), chroma / 12.0, 1e-9);
+        assert_approx_eq!(palette.n2.chroma(), chroma / 6.0, 1e-9);
+        let standard = CorePalette::of(slate);
+        assert_approx_eq!(standard.a2.chroma(), 16.0, 1e-9);
+        assert_approx_eq!(standard.a3.chroma(), 24.0, 1e-9);
+    }
+
+    #[test]
+    fn from_key_colors() {
+        let palette = CorePalette::from_key_colors(BLUE, Some(RED), None, None, None);
+        let red = Hct::from_int(RED);
+        assert_approx_eq!(palette.a1.chroma(), Hct::from_int(BLUE).chroma(), 1e-9);
+        assert_approx_eq!(palette.a2.hue(), red.hue(), 1e-9);
+        assert_approx_eq!(palette.a2.chroma(), red.chroma(), 1e-9);
+        let standard = CorePalette::of(BLUE);
+        for (actual, expected) in [
+            (&palette.a3, &standard.a3),
+            (&palette.n1, &standard.n1),
+            (&palette.n2, &standard.n2),
+            (&palette.error, &standard.error),
+        ] {
+            assert_eq!(actual.tone(40), expected.tone(40));
+        }
+
+        let palette = CorePalette::from_key_colors(BLUE, None, Some(GREEN), Some(RED), Some(RED));
+        assert_approx_eq!(palette.a2.hue(), standard.a2.hue(), 1e-9);
+        assert_approx_eq!(palette.a3.hue(), Hct::from_int(GREEN).hue(), 1e-9);
+        assert_approx_eq!(palette.n1.hue(), red.hue(), 1e-9);
+        assert_approx_eq!(palette.error.chroma(), red.chroma(), 1e-9);
+
+        let scheme = crate::scheme::Scheme::light_from_core_palette(&palette);
+        assert_eq!(scheme.tertiary, palette.a3.tone(40));
+        assert!((Hct::from_int(scheme.tertiary).hue() - Hct::from_int(GREEN).hue()).abs() < 2.0);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        use crate::scheme::Scheme;
+
+        let palette = CorePalette::from_key_colors(BLUE, Some(RED), None, None, None);
+        let json = serde_json::to_string(&palette).unwrap();
+        let back: CorePalette = serde_json::from_str(&json).unwrap();
+        // serde_json may round the last digit of a float, so compare hues approximately.
+        assert_approx_eq!(back.a2.hue(), palette.a2.hue(), 1e-9);
+        assert_approx_eq!(back.n2.chroma(), palette.n2.chroma(), 1e-9);
+        assert_eq!(
+            Scheme::light_from_core_palette(&back),
+            Scheme::light_from_core_palette(&palette)
+        );
+        assert_eq!(
+            Scheme::dark_from_core_palette(&back),
+            Scheme::dark_from_core_palette(&palette)
+        );
+    }
+
+    #[test]
+    fn options_change_one_palette_each() {
+        let standard = CorePalette::of(BLUE);
+        let defaults = CorePalette::with_options(BLUE, CorePaletteOptions::default());
+        for (a, b) in palettes(&standard).into_iter().zip(palettes(&defaults)) {
+            assert_eq!(a.tone(40), b.tone(40));
+            assert_approx_eq!(a.hue(), b.hue(), 1e-9);
+        }
+        assert_eq!(
+            CorePalette::with_options(0xff4285f4u32, CorePaletteOptions::default()),
+            defaults
+        );
+
+        let knobs: [(CorePaletteOptions, usize); 8] = [
+            (
+                CorePaletteOptions {
+                    primary_chroma: 80.0,
+                    ..Default::default()
+                },
+                0,
+            ),
+            (
+                CorePaletteOptions {
+                    secondary_chroma: 30.0,
+                    ..Default::default()
+                },
+                1,
+            ),
+            (
+                CorePaletteOptions {
+                    tertiary_chroma: 8.0,
+                    ..Default::default()
+                },
+                2,
+            ),
+            (
+                CorePaletteOptions {
+                    tertiary_hue_shift: -60.0,
+                    ..Default::default()
+                },
+                2,
+            ),
+            (
+                CorePaletteOptions {
+                    neutral_chroma: 10.0,
+                    ..Default::default()
+                },
+                3,
+            ),
+            (
+                CorePaletteOptions {
+                    neutral_variant_chroma: 14.0,
+                    ..Default::default()
+                },
+                4,
+            ),
+            (
+                CorePaletteOptions {
+                    error_hue: 50.0,
+                    ..Default::default()
+                },
+                5,
+            ),
+            (
+                CorePaletteOptions {
+                    error_chroma: 40.0,
+                    ..Default::default()
+                },
+                5,
+            ),
+        ];
+        for (options, changed) in knobs {
+            let palette = CorePalette::with_options(BLUE, options);
+            for (index, (a, b)) in palettes(&standard)
+                .into_iter()
+                .zip(palettes(&palette))
+                .enumerate()
+            {
+                assert_eq!(a.tone(50) != b.tone(50), index == changed, "{options:?}");
+            }
+        }
+    }
+
+    fn palettes(palette: &CorePalette) -> [&TonalPalette; 6] {
+        [
+            &palette.a1,
+            &palette.a2,
+            &palette.a3,
+            &palette.n1,
+            &palette.n2,
+            &palette.error,
+        ]
+    }
+
+    #[test]
+    fn of_matches_new() {
+        #[allow(deprecated)]
+        let legacy = [CorePalette::new(BLUE, false), CorePalette::new(BLUE, true)];
+        let current = [
+            CorePalette::of(0xff4285f4u32),
+            CorePalette::content_of(Argb(BLUE)),
+        ];
+        let from_hct = [
+            CorePalette::from_hct(Hct::from_int(BLUE), false),
+            CorePalette::from_hct(Hct::from_int(BLUE), true),
+        ];
+        for ((legacy, current), from_hct) in legacy.iter().zip(&current).zip(&from_hct) {
+            for ((a, b), c) in palettes(legacy)
+                .into_iter()
+                .zip(palettes(current))
+                .zip(palettes(from_hct))
+            {
+                assert_approx_eq!(a.hue(), b.hue(), 1e-9);
+                assert_approx_eq!(a.chroma(), b.chroma(), 1e-9);
+                assert_eq!(a.tone(40), c.tone(40));
+            }
+        }
+    }
+
+    #[test]
+    fn fidelity_keeps_low_chroma() {
+        let pastel = Hct::from(300.0, 12.0, 60.0).to_int();
+        let chroma = Hct::from_int(pastel).chroma();
+        assert_approx_eq!(chroma, 12.0, 0.5);
+
+        let fidelity = CorePalette::fidelity_of(pastel);
+        let standard = CorePalette::of(pastel);
+        assert_approx_eq!(standard.a1.chroma(), 48.0, 1e-9);
+        for tone in [40, 50, 60] {
+            assert_approx_eq!(Hct::from_int(fidelity.a1.tone(tone)).chroma(), chroma, 1.0);
+            assert_approx_eq!(Hct::from_int(standard.a1.tone(tone)).chroma(), 48.0, 1.0);
+        }
+        assert_approx_eq!(fidelity.a2.chroma(), chroma / 2.0, 1e-9);
+        assert_approx_eq!(fidelity.n2.chroma(), chroma / 8.0 + 4.0, 1e-9);
+
+        let vivid = CorePalette::fidelity_of([0xff, 0xff, 0x00, 0x00]);
+        assert_approx_eq!(vivid.a2.chroma(), vivid.a1.chroma() - 32.0, 1e-9);
+    }
+
+    #[test]
+    fn clone_and_compare() {
+        let palette = CorePalette::of(BLUE);
+        let queried = CorePalette::of(BLUE);
+        let tones: Vec<[u8; 4]> = [10, 40, 90].iter().map(|&t| queried.a1.tone(t)).collect();
+        assert_eq!(palette, queried);
+
+        let clone = queried.clone();
+        assert_eq!(clone, queried);
+        for (tone, expected) in [10, 40, 90].into_iter().zip(&tones) {
+            assert_eq!(clone.a1.tone(tone), *expected);
+        }
+        assert_eq!(clone.n2.tone(50), palette.n2.tone(50));
+
+        assert_ne!(CorePalette::of(RED), palette);
+        assert_ne!(CorePalette::content_of(BLUE), palette);
+        let mut tertiary = palette.clone();
+        tertiary.a3 = TonalPalette::from_hue_and_chroma(tertiary.a3.hue(), 30.0);
+        assert_ne!(tertiary, palette);
     }
 }
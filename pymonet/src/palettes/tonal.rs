@@ -1,41 +1,451 @@
 use crate::hct::Hct;
+use crate::utils::math::sanitize_degrees_double;
 use ahash::AHashMap;
+use std::fmt;
+use std::sync::Mutex;
 
 // 0 to 100
 pub type Tone = u8;
 
-#[derive(Debug, Clone)]
+/// Tones a palette caches by default, see [`TonalPalette::with_cache_capacity`].
+pub const DEFAULT_CACHE_CAPACITY: usize = 128;
+
+/// The tone stops of a Material palette, as listed in the Material 3 color documentation.
+pub const STANDARD_TONES: [Tone; 15] = [0, 5, 10, 20, 30, 40, 50, 60, 70, 80, 90, 95, 98, 99, 100];
+
+/// A palette of one hue and chroma at every tone.
+///
+/// Tones are generated on first use and cached behind a lock, so a palette can be shared between
+/// threads, ex. in an `Arc`, and read through `&self`. The cache holds up to
+/// [`DEFAULT_CACHE_CAPACITY`] tones, evicting the least recently used one, so sweeping through
+/// fractional tones keeps its memory bounded.
 pub struct TonalPalette {
-    cache: AHashMap<Tone, [u8; 4]>,
+    cache: Mutex<ToneCache>,
     hue: f64,
     chroma: f64,
+    /// The color the palette was made from, returned as is at its exact tone.
+    key_color: Option<Hct>,
 }
 
 impl TonalPalette {
     pub fn from_int(argb: [u8; 4]) -> TonalPalette {
-        let hct = Hct::from_int(argb);
-        Self::from_hue_and_chroma(hct.hue(), hct.chroma())
+        Self::from_hct(Hct::from_int(argb))
     }
 
+    /// Create a palette with the hue and chroma of a key color.
+    ///
+    /// # Arguments
+    ///
+    /// * `hct`: The key color.
+    ///
+    /// # Returns
+    ///
+    /// * The palette, whose `tone(hct.tone())` is exactly `hct.to_int()`, even where solving the
+    ///   tone from the hue and chroma would round to a neighboring color.
+    pub fn from_hct(hct: Hct) -> TonalPalette {
+        TonalPalette {
+            key_color: Some(hct),
+            ..Self::from_hue_and_chroma(hct.hue(), hct.chroma())
+        }
+    }
+
+    /// Create a palette of one hue and chroma, like `TonalPalette.fromHueAndChroma` in the
+    /// TypeScript library.
+    ///
+    /// # Arguments
+    ///
+    /// * `hue`: Hue in degrees, brought into 0 <= hue < 360.
+    /// * `chroma`: Chroma, clamped to be non-negative. Tones whose chroma is out of gamut get
+    ///   the most chroma they can.
+    ///
+    /// # Returns
+    ///
+    /// * The palette, without a key color.
     pub fn from_hue_and_chroma(hue: f64, chroma: f64) -> TonalPalette {
         TonalPalette {
-            cache: Default::default(),
-            hue,
-            chroma,
+            cache: Mutex::new(ToneCache::new(DEFAULT_CACHE_CAPACITY)),
+            hue: sanitize_degrees_double(hue),
+            chroma: chroma.max(0.0),
+            key_color: None,
         }
     }
 
-    pub fn tone(&mut self, tone: Tone) -> [u8; 4] {
-        if let Some(cached) = self.cache.get(&tone) {
-            *cached
+    /// Changes how many tones the palette caches.
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity`: Most tones to keep; with 0, every tone is generated on each use.
+    ///
+    /// # Returns
+    ///
+    /// * The palette, with its least recently used tones evicted down to `capacity`.
+    pub fn with_cache_capacity(mut self, capacity: usize) -> TonalPalette {
+        self.cache_mut().set_capacity(capacity);
+        self
+    }
+
+    /// Forgets every cached tone, ex. to release memory after an animation.
+    pub fn clear_cache(&self) {
+        self.lock_cache().clear();
+    }
+
+    /// The color the palette was made from with [`TonalPalette::from_hct`] or
+    /// [`TonalPalette::from_int`].
+    pub fn key_color(&self) -> Option<Hct> {
+        self.key_color
+    }
+
+    /// The hue of every tone, in degrees.
+    pub fn hue(&self) -> f64 {
+        self.hue
+    }
+
+    /// The chroma every tone is generated with, though tones out of gamut have less.
+    pub fn chroma(&self) -> f64 {
+        self.chroma
+    }
+
+    /// The color of the palette at a tone.
+    ///
+    /// # Arguments
+    ///
+    /// * `tone`: Tone from 0 to 100, ex. a [`Tone`], or a fractional `f64` such as `49.6`. It is
+    ///   rounded to a tenth.
+    ///
+    /// # Returns
+    ///
+    /// * The color, generated once per tone and then cached. At the key color's exact tone, the
+    ///   key color.
+    pub fn tone(&self, tone: impl Into<f64>) -> [u8; 4] {
+        self.tone_cached(&mut self.lock_cache(), tone.into())
+    }
+
+    /// The colors of the palette at several tones, taking the cache lock once for all of them.
+    ///
+    /// # Arguments
+    ///
+    /// * `tones`: Tones from 0 to 100, as accepted by [`TonalPalette::tone`].
+    ///
+    /// # Returns
+    ///
+    /// * The color at each tone, in the same order.
+    pub fn tones(&self, tones: &[f64]) -> Vec<[u8; 4]> {
+        let mut cache = self.lock_cache();
+        tones
+            .iter()
+            .map(|&tone| self.tone_cached(&mut cache, tone))
+            .collect()
+    }
+
+    /// The colors of the palette at the [`STANDARD_TONES`].
+    ///
+    /// # Returns
+    ///
+    /// * Each standard tone with its color, from 0 to 100.
+    pub fn standard_tones(&self) -> impl Iterator<Item = (Tone, [u8; 4])> {
+        let colors = self.tones(&STANDARD_TONES.map(f64::from));
+        STANDARD_TONES.into_iter().zip(colors)
+    }
+
+    /// Generates tones ahead of time, so that later calls to [`TonalPalette::tone`] for them are
+    /// cache lookups.
+    ///
+    /// # Arguments
+    ///
+    /// * `tones`: Tones from 0 to 100, as accepted by [`TonalPalette::tone`]. Past the cache
+    ///   capacity, the first ones are evicted again.
+    pub fn precompute(&mut self, tones: &[f64]) {
+        let (hue, chroma) = (self.hue, self.chroma);
+        let cache = self.cache_mut();
+        for &tone in tones {
+            let key = cache_key(tone);
+            cache.get_or_insert_with(key, || solve(hue, chroma, key));
+        }
+    }
+
+    /// A shortcut for [`TonalPalette::precompute`] with the [`STANDARD_TONES`].
+    pub fn precompute_standard_tones(&mut self) {
+        self.precompute(&STANDARD_TONES.map(f64::from));
+    }
+
+    fn lock_cache(&self) -> std::sync::MutexGuard<'_, ToneCache> {
+        self.cache
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn cache_mut(&mut self) -> &mut ToneCache {
+        self.cache
+            .get_mut()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn tone_cached(&self, cache: &mut ToneCache, tone: f64) -> [u8; 4] {
+        if let Some(key_color) = self.key_color.filter(|key_color| key_color.tone() == tone) {
+            return key_color.to_int();
+        }
+        let key = cache_key(tone);
+        cache.get_or_insert_with(key, || solve(self.hue, self.chroma, key))
+    }
+}
+
+/// Colors by tone in tenths, see [`cache_key`], evicting the least recently used tone when full.
+#[derive(Debug, Clone)]
+struct ToneCache {
+    /// Each color with the `clock` reading of its last use.
+    entries: AHashMap<i32, ([u8; 4], u64)>,
+    clock: u64,
+    capacity: usize,
+}
+
+impl ToneCache {
+    fn new(capacity: usize) -> ToneCache {
+        ToneCache {
+            entries: AHashMap::new(),
+            clock: 0,
+            capacity,
+        }
+    }
+
+    fn get_or_insert_with(&mut self, key: i32, solve: impl FnOnce() -> [u8; 4]) -> [u8; 4] {
+        self.clock += 1;
+        if let Some((color, last_used)) = self.entries.get_mut(&key) {
+            *last_used = self.clock;
+            return *color;
+        }
+        let color = solve();
+        self.insert(key, color);
+        color
+    }
+
+    fn insert(&mut self, key: i32, color: [u8; 4]) {
+        if self.capacity == 0 {
+            return;
+        }
+        self.clock += 1;
+        self.entries.insert(key, (color, self.clock));
+        self.evict();
+    }
+
+    fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        self.evict();
+    }
+
+    /// Removes the least recently used entries until the cache fits its capacity. With at most
+    /// [`DEFAULT_CACHE_CAPACITY`] entries by default, a linear scan is cheaper than keeping them
+    /// ordered.
+    fn evict(&mut self) {
+        while self.entries.len() > self.capacity {
+            let oldest = self
+                .entries
+                .iter()
+                .min_by_key(|(_, (_, last_used))| *last_used)
+                .map(|(&key, _)| key);
+            match oldest {
+                Some(key) => self.entries.remove(&key),
+                None => break,
+            };
+        }
+    }
+
+    fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+#[cfg(test)]
+thread_local! {
+    /// Tones solved on this thread, so tests can tell cache hits from misses.
+    static SOLVES: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+}
+
+/// Solves the color of a palette at a tone given as a [`cache_key`].
+fn solve(hue: f64, chroma: f64, key: i32) -> [u8; 4] {
+    #[cfg(test)]
+    SOLVES.with(|solves| solves.set(solves.get() + 1));
+    Hct::from(hue, chroma, key as f64 / 10.0).to_int()
+}
+
+/// Rounds a tone to tenths, so that tones differing only by floating point error, or by less
+/// than a visible step, share a cache entry.
+fn cache_key(tone: f64) -> i32 {
+    (tone * 10.0).round() as i32
+}
+
+/// Shows the hue, chroma, and key color, but not the cached tones.
+impl fmt::Debug for TonalPalette {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TonalPalette")
+            .field("hue", &self.hue)
+            .field("chroma", &self.chroma)
+            .field("key_color", &self.key_color)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Palettes are equal when their hue and chroma are, whichever tones have been cached.
+impl PartialEq for TonalPalette {
+    fn eq(&self, other: &TonalPalette) -> bool {
+        self.hue == other.hue && self.chroma == other.chroma
+    }
+}
+
+impl Clone for TonalPalette {
+    fn clone(&self) -> TonalPalette {
+        TonalPalette {
+            cache: Mutex::new(self.lock_cache().clone()),
+            hue: self.hue,
+            chroma: self.chroma,
+            key_color: self.key_color,
+        }
+    }
+}
+
+/// Which tones are written when a palette is serialized.
+#[cfg(feature = "serde")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PaletteTones {
+    /// The [`STANDARD_TONES`], so that the JSON is usable without this crate.
+    #[default]
+    Standard,
+    /// Every tone in the cache, including fractional ones, so that loading the palette back
+    /// needs no tone to be generated again.
+    Cached,
+    /// No tones: only the hue, the chroma, and the key color.
+    Omitted,
+}
+
+#[cfg(feature = "serde")]
+thread_local! {
+    static PALETTE_TONES: std::cell::Cell<PaletteTones> =
+        const { std::cell::Cell::new(PaletteTones::Standard) };
+}
+
+/// Restores the previous setting when dropped, so that a panic inside [`with_palette_tones`]
+/// does not leak it into later serialization.
+#[cfg(feature = "serde")]
+struct PaletteTonesGuard(PaletteTones);
+
+#[cfg(feature = "serde")]
+impl Drop for PaletteTonesGuard {
+    fn drop(&mut self) {
+        PALETTE_TONES.with(|tones| tones.set(self.0));
+    }
+}
+
+/// Runs `f` with palettes serialized with `tones` on the current thread.
+///
+/// # Arguments
+///
+/// * `tones`: Which tones palettes write.
+/// * `f`: Serialization to run, ex. `|| serde_json::to_string(&palette)`.
+///
+/// # Returns
+///
+/// * Whatever `f` returns.
+#[cfg(feature = "serde")]
+pub fn with_palette_tones<R>(tones: PaletteTones, f: impl FnOnce() -> R) -> R {
+    let _guard = PaletteTonesGuard(PALETTE_TONES.with(|current| current.replace(tones)));
+    f()
+}
+
+/// A tone as a map key: a whole tone is written as `"50"`, a fractional one as `"49.6"`.
+#[cfg(feature = "serde")]
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+struct ToneKey(i32);
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for ToneKey {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if self.0 % 10 == 0 {
+            serializer.collect_str(&(self.0 / 10))
         } else {
-            let color = Hct::from(self.hue, self.chroma, tone as f64).to_int();
-            self.cache.insert(tone, color);
-            color
+            serializer.collect_str(&(self.0 as f64 / 10.0))
         }
     }
 }
 
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for ToneKey {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let tone = String::deserialize(deserializer)?;
+        tone.parse::<f64>()
+            .ok()
+            .filter(|tone| (0.0..=100.0).contains(tone))
+            .map(|tone| ToneKey(cache_key(tone)))
+            .ok_or_else(|| {
+                serde::de::Error::invalid_value(
+                    serde::de::Unexpected::Str(&tone),
+                    &"a tone from 0 to 100",
+                )
+            })
+    }
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TonalPaletteJson {
+    hue: f64,
+    chroma: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    key_color: Option<crate::utils::color_format::SerdeColor>,
+    #[serde(default, skip_serializing_if = "std::collections::BTreeMap::is_empty")]
+    tones: std::collections::BTreeMap<ToneKey, crate::utils::color_format::SerdeColor>,
+}
+
+/// Serializes the hue, the chroma, the key color if any, and the tones chosen with
+/// [`with_palette_tones`], by default the standard ones.
+#[cfg(feature = "serde")]
+impl serde::Serialize for TonalPalette {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use crate::utils::color_format::SerdeColor;
+
+        let tones = match PALETTE_TONES.with(std::cell::Cell::get) {
+            PaletteTones::Standard => self
+                .standard_tones()
+                .map(|(tone, color)| (ToneKey(cache_key(tone as f64)), SerdeColor(color)))
+                .collect(),
+            PaletteTones::Cached => self
+                .lock_cache()
+                .entries
+                .iter()
+                .map(|(&key, &(color, _))| (ToneKey(key), SerdeColor(color)))
+                .collect(),
+            PaletteTones::Omitted => Default::default(),
+        };
+        TonalPaletteJson {
+            hue: self.hue,
+            chroma: self.chroma,
+            key_color: self
+                .key_color
+                .map(|key_color| SerdeColor(key_color.to_int())),
+            tones,
+        }
+        .serialize(serializer)
+    }
+}
+
+/// Rebuilds the palette from its hue, chroma, and key color. Any serialized tones are cached,
+/// so they are returned exactly as written; other tones are generated as usual. Unknown fields
+/// are ignored, so files written by later versions still load.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for TonalPalette {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let json = TonalPaletteJson::deserialize(deserializer)?;
+        let mut palette = TonalPalette {
+            key_color: json.key_color.map(|key_color| Hct::from_int(key_color.0)),
+            ..TonalPalette::from_hue_and_chroma(json.hue, json.chroma)
+        };
+        let cache = palette.cache_mut();
+        for (tone, color) in json.tones {
+            cache.insert(tone.0, color.0);
+        }
+        Ok(palette)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     #[test]
@@ -43,4 +453,287 @@ mod tests {
         let sum = 2 + 2;
         assert_eq!(sum, 4);
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        use super::*;
+
+        let palette = TonalPalette::from_hue_and_chroma(282.0, 36.0);
+        let json = serde_json::to_value(&palette).unwrap();
+        assert_eq!(json["hue"], 282.0);
+        assert_eq!(json["chroma"], 36.0);
+        assert_eq!(
+            json["tones"].as_object().unwrap().len(),
+            STANDARD_TONES.len()
+        );
+
+        let back: TonalPalette = serde_json::from_value(json).unwrap();
+        let fresh = TonalPalette::from_hue_and_chroma(282.0, 36.0);
+        for tone in 0..=100 {
+            assert_eq!(back.tone(tone), fresh.tone(tone));
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_cached_tones() {
+        use super::*;
+
+        let palette = TonalPalette::from_int([0xff, 0x00, 0x00, 0xff]);
+        palette.tone(49.6);
+        palette.tone(50);
+        let json = with_palette_tones(PaletteTones::Cached, || {
+            serde_json::to_value(&palette).unwrap()
+        });
+        assert_eq!(json["keyColor"], "#0000ff");
+        let tones = json["tones"].as_object().unwrap();
+        assert_eq!(tones.keys().collect::<Vec<_>>(), ["49.6", "50"]);
+
+        let back: TonalPalette = serde_json::from_value(json).unwrap();
+        let before = SOLVES.with(|solves| solves.get());
+        assert_eq!(back.tone(49.6), palette.tone(49.6));
+        assert_eq!(back.tone(50), palette.tone(50));
+        assert_eq!(SOLVES.with(|solves| solves.get()), before);
+        let key_color = back.key_color().unwrap();
+        assert_eq!(back.tone(key_color.tone()), [0xff, 0x00, 0x00, 0xff]);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_without_tones() {
+        use super::*;
+
+        let palette = TonalPalette::from_hue_and_chroma(282.0, 36.0);
+        let json = with_palette_tones(PaletteTones::Omitted, || {
+            serde_json::to_string(&palette).unwrap()
+        });
+        assert_eq!(json, r#"{"hue":282.0,"chroma":36.0}"#);
+        // The setting only lasts for the closure.
+        let standard = serde_json::to_value(&palette).unwrap();
+        assert_eq!(
+            standard["tones"].as_object().unwrap().len(),
+            STANDARD_TONES.len()
+        );
+
+        let back: TonalPalette = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, palette);
+        assert!(back.key_color().is_none());
+        assert_eq!(back.tone(40), palette.tone(40));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_ignores_unknown_fields() {
+        use super::*;
+
+        let json = r##"{
+            "version": 3,
+            "hue": 282.0,
+            "chroma": 36.0,
+            "keyColor": "#6750a4",
+            "tones": {"40": "#6750a4"},
+            "temperature": {"warm": true}
+        }"##;
+        let palette: TonalPalette = serde_json::from_str(json).unwrap();
+        assert_eq!(palette.hue(), 282.0);
+        assert_eq!(palette.tone(40), [0xff, 0x67, 0x50, 0xa4]);
+        assert_eq!(
+            palette.key_color().unwrap().to_int(),
+            [0xff, 0x67, 0x50, 0xa4]
+        );
+
+        let invalid = r##"{"hue": 282.0, "chroma": 36.0, "tones": {"150": "#ffffff"}}"##;
+        assert!(serde_json::from_str::<TonalPalette>(invalid).is_err());
+    }
+
+    #[test]
+    fn getters_after_sanitization() {
+        use super::*;
+        use assert_approx_eq::assert_approx_eq;
+
+        let palette = TonalPalette::from_hue_and_chroma(282.0, 36.0);
+        assert_eq!(palette.hue(), 282.0);
+        assert_eq!(palette.chroma(), 36.0);
+
+        let wrapped = TonalPalette::from_hue_and_chroma(-78.0, -5.0);
+        assert_approx_eq!(wrapped.hue(), 282.0);
+        assert_eq!(wrapped.chroma(), 0.0);
+        let wrapped = TonalPalette::from_hue_and_chroma(642.0, 36.0);
+        assert_approx_eq!(wrapped.hue(), 282.0);
+        assert_eq!(wrapped.tone(50), palette.tone(50));
+
+        let debug = format!("{palette:?}");
+        assert!(debug.starts_with("TonalPalette { hue: 282.0, chroma: 36.0, key_color: None"));
+        let blue = TonalPalette::from_int([0xff, 0x42, 0x85, 0xf4]);
+        assert!(format!("{blue:?}").contains("key_color: Some("));
+    }
+
+    #[test]
+    fn batch_tones() {
+        use super::*;
+
+        assert_eq!(
+            STANDARD_TONES,
+            [0, 5, 10, 20, 30, 40, 50, 60, 70, 80, 90, 95, 98, 99, 100]
+        );
+
+        let palette = TonalPalette::from_hue_and_chroma(282.0, 36.0);
+        let fresh = TonalPalette::from_hue_and_chroma(282.0, 36.0);
+        let tones = [99.0, 0.0, 49.6, 50.0, 49.6];
+        let colors = palette.tones(&tones);
+        assert_eq!(colors.len(), tones.len());
+        for (tone, color) in tones.iter().zip(colors) {
+            assert_eq!(color, fresh.tone(*tone));
+        }
+        assert!(palette.tones(&[]).is_empty());
+
+        let standard: Vec<_> = palette.standard_tones().collect();
+        assert_eq!(standard.len(), STANDARD_TONES.len());
+        for (tone, color) in standard {
+            assert_eq!(color, fresh.tone(tone));
+        }
+    }
+
+    #[test]
+    fn precompute_avoids_solving() {
+        use super::*;
+
+        let solves = || SOLVES.with(|solves| solves.get());
+        let mut palette = TonalPalette::from_hue_and_chroma(282.0, 36.0);
+        palette.precompute_standard_tones();
+        palette.precompute(&[49.6, 4.0]);
+        let before = solves();
+        for (tone, color) in palette.standard_tones() {
+            assert_eq!(color, Hct::from(282.0, 36.0, tone as f64).to_int());
+        }
+        palette.tone(49.6);
+        palette.tone(4);
+        assert_eq!(solves(), before);
+
+        palette.tone(17);
+        assert_eq!(solves(), before + 1);
+    }
+
+    #[test]
+    fn cache_evicts_least_recently_used() {
+        use super::*;
+
+        let palette = TonalPalette::from_hue_and_chroma(282.0, 36.0);
+        let cached = || palette.lock_cache().entries.len();
+        palette.tone(50);
+        for step in 0..1000 {
+            palette.tone(step as f64 / 10.0);
+            // Keep tone 50 in use while sweeping.
+            palette.tone(50);
+            assert!(cached() <= DEFAULT_CACHE_CAPACITY);
+        }
+        assert_eq!(cached(), DEFAULT_CACHE_CAPACITY);
+
+        let before = SOLVES.with(|solves| solves.get());
+        palette.tone(50);
+        palette.tone(99.9);
+        assert_eq!(SOLVES.with(|solves| solves.get()), before);
+        palette.tone(0);
+        assert_eq!(SOLVES.with(|solves| solves.get()), before + 1);
+
+        palette.clear_cache();
+        assert_eq!(cached(), 0);
+        assert_eq!(palette.tone(50), Hct::from(282.0, 36.0, 50.0).to_int());
+
+        let small = palette.clone().with_cache_capacity(2);
+        assert_eq!(small.lock_cache().entries.len(), 1);
+        small.tone(10);
+        small.tone(20);
+        assert_eq!(small.lock_cache().entries.len(), 2);
+        let uncached = TonalPalette::from_hue_and_chroma(282.0, 36.0).with_cache_capacity(0);
+        assert_eq!(uncached.tone(50), palette.tone(50));
+        assert_eq!(uncached.lock_cache().entries.len(), 0);
+    }
+
+    #[test]
+    fn concurrent_reads_with_eviction() {
+        use super::*;
+        use std::sync::Arc;
+
+        let palette =
+            Arc::new(TonalPalette::from_hue_and_chroma(282.0, 36.0).with_cache_capacity(8));
+        let threads: Vec<_> = (0..4)
+            .map(|offset| {
+                let palette = Arc::clone(&palette);
+                std::thread::spawn(move || {
+                    for step in (offset..1000).step_by(4) {
+                        let tone = step as f64 / 10.0;
+                        assert_eq!(palette.tone(tone), Hct::from(282.0, 36.0, tone).to_int());
+                    }
+                })
+            })
+            .collect();
+        for thread in threads {
+            thread.join().unwrap();
+        }
+        assert_eq!(palette.lock_cache().entries.len(), 8);
+    }
+
+    #[test]
+    fn fractional_tones() {
+        use super::*;
+
+        let palette = TonalPalette::from_hue_and_chroma(282.0, 36.0);
+        assert_ne!(palette.tone(49.6), palette.tone(50));
+        assert_eq!(palette.tone(49.6), Hct::from(282.0, 36.0, 49.6).to_int());
+        assert_eq!(palette.tone(50u8), palette.tone(50.0));
+        assert_eq!(palette.tone(49.6 + 1e-9), palette.tone(49.6));
+    }
+
+    #[test]
+    fn shared_between_threads() {
+        use super::*;
+
+        let palette = TonalPalette::from_hue_and_chroma(282.0, 36.0);
+        let fresh = TonalPalette::from_hue_and_chroma(282.0, 36.0);
+        std::thread::scope(|scope| {
+            for offset in 0..4 {
+                let palette = &palette;
+                let fresh = &fresh;
+                scope.spawn(move || {
+                    for tone in (offset..=100).step_by(4) {
+                        let tone = tone as f64 + 0.5;
+                        assert_eq!(palette.tone(tone), Hct::from(282.0, 36.0, tone).to_int());
+                        assert_eq!(palette.tone(tone), fresh.tone(tone));
+                    }
+                });
+            }
+        });
+    }
+
+    #[test]
+    fn key_color_round_trips() {
+        use super::*;
+
+        for argb in [
+            [0xff, 0x42, 0x85, 0xf4],
+            [0xff, 0xff, 0x00, 0x00],
+            [0xff, 0x00, 0xff, 0x00],
+            [0xff, 0x00, 0x00, 0xff],
+            [0xff, 0xfe, 0xff, 0xfd],
+            [0xff, 0x01, 0x02, 0x00],
+            [0xff, 0x9a, 0x6b, 0x6b],
+        ] {
+            let hct = Hct::from_int(argb);
+            let palette = TonalPalette::from_hct(hct);
+            assert_eq!(palette.tone(hct.tone()), argb);
+            assert_eq!(TonalPalette::from_int(argb).tone(hct.tone()), argb);
+            assert_eq!(palette.hue(), hct.hue());
+            assert_eq!(palette.chroma(), hct.chroma());
+            assert_eq!(palette.key_color().unwrap().to_int(), argb);
+            assert_eq!(palette.clone().tone(hct.tone()), argb);
+        }
+
+        // Without the key color, solving pure blue's tone lands on a neighboring color.
+        let blue = Hct::from_int([0xff, 0x00, 0x00, 0xff]);
+        let solved = TonalPalette::from_hue_and_chroma(blue.hue(), blue.chroma());
+        assert!(solved.key_color().is_none());
+        assert_ne!(solved.tone(blue.tone()), [0xff, 0x00, 0x00, 0xff]);
+    }
 }
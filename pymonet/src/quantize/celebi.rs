@@ -0,0 +1,47 @@
+//! An image quantizer that runs [`wu`] to find starting clusters, then refines them with
+//! [`wsmeans`].
+//!
+//! Wu is fast but cuts boxes along the RGB axes; Wsmeans corrects for that, and starting from
+//! Wu's clusters makes its result deterministic.
+use super::{wsmeans, wu};
+use std::collections::HashMap;
+
+/// Reduces the colors of an image, as used to find source colors for a theme.
+///
+/// # Arguments
+///
+/// * `pixels`: Colors of the image. Callers usually keep only opaque pixels.
+/// * `max_colors`: The number of colors to divide the image into. A lower number of colors may be
+///   returned.
+///
+/// # Returns
+///
+/// * A map from each color to the number of pixels in it, ready for [`crate::score::score`].
+pub fn quantize(pixels: &[[u8; 4]], max_colors: usize) -> HashMap<[u8; 4], u32> {
+    let wu_result = wu::quantize(pixels, max_colors);
+    wsmeans::quantize(pixels, &wu_result, max_colors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn one_color() {
+        let result = quantize(&[[0xff, 0x42, 0x85, 0xf4]; 16], 128);
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[&[0xff, 0x42, 0x85, 0xf4]], 16);
+    }
+
+    #[test]
+    fn three_colors() {
+        let red = [0xff, 0xff, 0x00, 0x00];
+        let green = [0xff, 0x00, 0xff, 0x00];
+        let blue = [0xff, 0x00, 0x00, 0xff];
+        let result = quantize(&[blue, red, green, red, blue, blue], 128);
+        assert_eq!(result.len(), 3);
+        assert_eq!(result[&red], 2);
+        assert_eq!(result[&green], 1);
+        assert_eq!(result[&blue], 3);
+    }
+}
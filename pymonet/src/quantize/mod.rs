@@ -1,8 +1,6 @@
-#[cfg(test)]
-mod tests {
-    #[test]
-    fn placeholder_test() {
-        let sum = 2 + 2;
-        assert_eq!(sum, 4);
-    }
-}
+//! Quantizers reduce the colors of an image to a small palette, for scoring as theme source
+//! colors.
+pub mod celebi;
+pub mod point_provider;
+pub mod wsmeans;
+pub mod wu;
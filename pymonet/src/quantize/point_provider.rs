@@ -0,0 +1,219 @@
+//! The color spaces [`wsmeans`](super::wsmeans) can cluster in. A point provider converts colors
+//! to points in its space and back, and measures how far apart two points are.
+//!
+//! [`PointProviderLab`] is the default and the most accurate. [`PointProviderRgb`] and
+//! [`PointProviderRedmean`] skip the conversion to L*a*b*, trading accuracy for speed.
+use crate::utils::color::{argb_from_lab, lab_from_argb, labs_from_argb, redmean_squared};
+
+/// A color space for clustering colors in.
+pub trait PointProvider {
+    /// Converts a color to a point.
+    ///
+    /// # Arguments
+    ///
+    /// * `argb`: ARGB representation of a color.
+    ///
+    /// # Returns
+    ///
+    /// * The color as a point in this space.
+    fn point_from_argb(&self, argb: [u8; 4]) -> [f64; 3];
+
+    /// Converts a buffer of colors to points, writing one point per color into `out`.
+    ///
+    /// # Arguments
+    ///
+    /// * `argbs`: ARGB representations of colors.
+    /// * `out`: Where to write the points, as long as `argbs`.
+    fn points_from_argbs(&self, argbs: &[[u8; 4]], out: &mut [[f64; 3]]) {
+        for (out, &argb) in out.iter_mut().zip(argbs) {
+            *out = self.point_from_argb(argb);
+        }
+    }
+
+    /// Converts a point, ex. the average of several colors, back to a color.
+    ///
+    /// # Arguments
+    ///
+    /// * `point`: A point in this space.
+    ///
+    /// # Returns
+    ///
+    /// * ARGB representation of the nearest color.
+    fn argb_from_point(&self, point: &[f64; 3]) -> [u8; 4];
+
+    /// Measures how far apart two points are.
+    ///
+    /// # Arguments
+    ///
+    /// * `one`: A point in this space.
+    /// * `two`: A point in this space.
+    ///
+    /// # Returns
+    ///
+    /// * The square of the distance between the points, so that comparisons need no square
+    ///   root.
+    fn distance(&self, one: &[f64; 3], two: &[f64; 3]) -> f64;
+
+    /// Converts an L*a*b* color to a point. Wsmeans places random starting clusters in L*a*b*,
+    /// then converts them with this.
+    ///
+    /// # Arguments
+    ///
+    /// * `lab`: L*, a*, and b* of a color.
+    ///
+    /// # Returns
+    ///
+    /// * The color as a point in this space.
+    fn point_from_lab(&self, lab: [f64; 3]) -> [f64; 3] {
+        self.point_from_argb(argb_from_lab(lab[0], lab[1], lab[2]))
+    }
+}
+
+/// Squared euclidean distance between two points.
+fn euclidean_squared(one: &[f64; 3], two: &[f64; 3]) -> f64 {
+    let d0 = one[0] - two[0];
+    let d1 = one[1] - two[1];
+    let d2 = one[2] - two[2];
+    d0 * d0 + d1 * d1 + d2 * d2
+}
+
+/// Clusters in L*a*b*, where euclidean distance tracks perceived difference.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PointProviderLab;
+
+impl PointProvider for PointProviderLab {
+    fn point_from_argb(&self, argb: [u8; 4]) -> [f64; 3] {
+        lab_from_argb(argb)
+    }
+
+    fn points_from_argbs(&self, argbs: &[[u8; 4]], out: &mut [[f64; 3]]) {
+        labs_from_argb(argbs, out)
+    }
+
+    fn argb_from_point(&self, point: &[f64; 3]) -> [u8; 4] {
+        argb_from_lab(point[0], point[1], point[2])
+    }
+
+    fn distance(&self, one: &[f64; 3], two: &[f64; 3]) -> f64 {
+        euclidean_squared(one, two)
+    }
+
+    fn point_from_lab(&self, lab: [f64; 3]) -> [f64; 3] {
+        lab
+    }
+}
+
+/// Clusters in sRGB by [`distance_squared_rgb`](crate::utils::color::distance_squared_rgb),
+/// which ignores how the eye weighs the channels.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PointProviderRgb;
+
+/// Components of a color, with alpha dropped.
+fn rgb_point(argb: [u8; 4]) -> [f64; 3] {
+    [argb[1] as f64, argb[2] as f64, argb[3] as f64]
+}
+
+/// The color nearest to a point in sRGB, rounding each component.
+fn argb_from_rgb_point(point: &[f64; 3]) -> [u8; 4] {
+    let [r, g, b] = point.map(|component| component.round().clamp(0.0, 255.0) as u8);
+    [255, r, g, b]
+}
+
+impl PointProvider for PointProviderRgb {
+    fn point_from_argb(&self, argb: [u8; 4]) -> [f64; 3] {
+        rgb_point(argb)
+    }
+
+    fn argb_from_point(&self, point: &[f64; 3]) -> [u8; 4] {
+        argb_from_rgb_point(point)
+    }
+
+    fn distance(&self, one: &[f64; 3], two: &[f64; 3]) -> f64 {
+        euclidean_squared(one, two)
+    }
+}
+
+/// Clusters in sRGB by [`distance_redmean`](crate::utils::color::distance_redmean), a weighted
+/// RGB distance that is nearly as cheap as [`PointProviderRgb`] but closer to perceived
+/// difference.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PointProviderRedmean;
+
+impl PointProvider for PointProviderRedmean {
+    fn point_from_argb(&self, argb: [u8; 4]) -> [f64; 3] {
+        rgb_point(argb)
+    }
+
+    fn argb_from_point(&self, point: &[f64; 3]) -> [u8; 4] {
+        argb_from_rgb_point(point)
+    }
+
+    fn distance(&self, one: &[f64; 3], two: &[f64; 3]) -> f64 {
+        redmean_squared(*one, *two)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::color::{distance_redmean, distance_squared_rgb};
+    use assert_approx_eq::assert_approx_eq;
+
+    const COLORS: [[u8; 4]; 5] = [
+        [0xff, 0x00, 0x00, 0x00],
+        [0xff, 0xff, 0xff, 0xff],
+        [0xff, 0x42, 0x85, 0xf4],
+        [0xff, 0xb3, 0x26, 0x1e],
+        [0xff, 0x00, 0xa0, 0x00],
+    ];
+
+    #[test]
+    fn round_trips() {
+        for argb in COLORS {
+            assert_eq!(
+                PointProviderLab.argb_from_point(&PointProviderLab.point_from_argb(argb)),
+                argb
+            );
+            assert_eq!(
+                PointProviderRgb.argb_from_point(&PointProviderRgb.point_from_argb(argb)),
+                argb
+            );
+            assert_eq!(
+                PointProviderRedmean.argb_from_point(&PointProviderRedmean.point_from_argb(argb)),
+                argb
+            );
+        }
+        assert_eq!(
+            PointProviderRgb.argb_from_point(&[-3.0, 127.6, 300.0]),
+            [0xff, 0x00, 0x80, 0xff]
+        );
+    }
+
+    #[test]
+    fn distances_match_color_helpers() {
+        for a in COLORS {
+            for b in COLORS {
+                let (one, two) = (rgb_point(a), rgb_point(b));
+                assert_eq!(
+                    PointProviderRgb.distance(&one, &two),
+                    distance_squared_rgb(a, b) as f64
+                );
+                assert_approx_eq!(
+                    PointProviderRedmean.distance(&one, &two).sqrt(),
+                    distance_redmean(a, b),
+                    1e-9
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn point_from_lab() {
+        let lab = [53.0, 40.0, -20.0];
+        assert_eq!(PointProviderLab.point_from_lab(lab), lab);
+        assert_eq!(
+            PointProviderRgb.point_from_lab(lab),
+            rgb_point(argb_from_lab(lab[0], lab[1], lab[2]))
+        );
+    }
+}
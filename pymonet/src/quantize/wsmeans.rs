@@ -0,0 +1,259 @@
+//! An image quantizer that improves on the speed of a standard K-Means algorithm by implementing
+//! several optimizations, including deduping identical pixels and a triangle inequality rule that
+//! reduces the number of comparisons needed to identify which cluster a point should be moved to.
+//!
+//! Wsmeans stands for Weighted Square Means.
+//!
+//! This algorithm was designed by M. Emre Celebi, and was found in their 2011 paper, Improving
+//! the Performance of K-Means for Color Quantization. <https://arxiv.org/abs/1101.0395>
+use super::point_provider::{PointProvider, PointProviderLab};
+use std::collections::HashMap;
+
+const MAX_ITERATIONS: usize = 10;
+const MIN_MOVEMENT_DISTANCE: f64 = 3.0;
+
+/// Seed shared with the other implementations, so that clusters start from the same indices.
+const SEED: i64 = 0x42688;
+
+/// The `java.util.Random` generator, which the cluster assignment is seeded from.
+struct JavaRandom {
+    seed: i64,
+}
+
+impl JavaRandom {
+    const MULTIPLIER: i64 = 0x5DEECE66D;
+    const MASK: i64 = (1 << 48) - 1;
+
+    fn new(seed: i64) -> JavaRandom {
+        JavaRandom {
+            seed: (seed ^ Self::MULTIPLIER) & Self::MASK,
+        }
+    }
+
+    fn next(&mut self, bits: u32) -> i32 {
+        self.seed = (self.seed.wrapping_mul(Self::MULTIPLIER).wrapping_add(0xB)) & Self::MASK;
+        (self.seed >> (48 - bits)) as i32
+    }
+
+    fn next_int(&mut self, bound: i32) -> i32 {
+        if bound & -bound == bound {
+            return ((bound as i64 * self.next(31) as i64) >> 31) as i32;
+        }
+        loop {
+            let bits = self.next(31);
+            let value = bits % bound;
+            if bits.wrapping_sub(value).wrapping_add(bound - 1) >= 0 {
+                return value;
+            }
+        }
+    }
+
+    fn next_double(&mut self) -> f64 {
+        let high = (self.next(26) as i64) << 27;
+        (high + self.next(27) as i64) as f64 * (1.0 / (1i64 << 53) as f64)
+    }
+}
+
+/// Reduces the colors of an image by clustering its pixels in L*a*b*.
+///
+/// # Arguments
+///
+/// * `pixels`: Colors of the image.
+/// * `starting_clusters`: Colors to start the clusters at, ex. the result of
+///   [`crate::quantize::wu::quantize`]. When empty, clusters start at random colors.
+/// * `max_colors`: The number of colors to divide the image into. A lower number of colors may be
+///   returned.
+///
+/// # Returns
+///
+/// * A map from each cluster's color to the number of pixels in it.
+pub fn quantize(
+    pixels: &[[u8; 4]],
+    starting_clusters: &[[u8; 4]],
+    max_colors: usize,
+) -> HashMap<[u8; 4], u32> {
+    quantize_with_point_provider(pixels, starting_clusters, max_colors, &PointProviderLab)
+}
+
+/// Like [`quantize`], clustering in the space of `point_provider` instead of L*a*b*.
+///
+/// # Arguments
+///
+/// * `pixels`: Colors of the image.
+/// * `starting_clusters`: Colors to start the clusters at. When empty, clusters start at random
+///   colors.
+/// * `max_colors`: The number of colors to divide the image into. A lower number of colors may be
+///   returned.
+/// * `point_provider`: The color space to cluster in, ex.
+///   [`PointProviderRedmean`](super::point_provider::PointProviderRedmean) to trade accuracy for
+///   speed.
+///
+/// # Returns
+///
+/// * A map from each cluster's color to the number of pixels in it.
+pub fn quantize_with_point_provider(
+    pixels: &[[u8; 4]],
+    starting_clusters: &[[u8; 4]],
+    max_colors: usize,
+    point_provider: &impl PointProvider,
+) -> HashMap<[u8; 4], u32> {
+    let mut pixel_to_index: HashMap<[u8; 4], usize> = HashMap::new();
+    let mut unique_pixels: Vec<[u8; 4]> = Vec::new();
+    let mut counts: Vec<u32> = Vec::new();
+    for &pixel in pixels {
+        match pixel_to_index.get(&pixel) {
+            Some(&i) => counts[i] += 1,
+            None => {
+                pixel_to_index.insert(pixel, unique_pixels.len());
+                unique_pixels.push(pixel);
+                counts.push(1);
+            }
+        }
+    }
+    let mut points = vec![[0.0; 3]; unique_pixels.len()];
+    point_provider.points_from_argbs(&unique_pixels, &mut points);
+
+    let mut cluster_count = max_colors.min(points.len());
+    if !starting_clusters.is_empty() {
+        cluster_count = cluster_count.min(starting_clusters.len());
+    }
+    if cluster_count == 0 {
+        return HashMap::new();
+    }
+
+    let mut random = JavaRandom::new(SEED);
+    let mut clusters: Vec<[f64; 3]> = starting_clusters
+        .iter()
+        .take(cluster_count)
+        .map(|&argb| point_provider.point_from_argb(argb))
+        .collect();
+    while clusters.len() < cluster_count {
+        let l = random.next_double() * 100.0;
+        let a = random.next_double() * 200.0 - 100.0;
+        let b = random.next_double() * 200.0 - 100.0;
+        clusters.push(point_provider.point_from_lab([l, a, b]));
+    }
+
+    let mut cluster_indices: Vec<usize> = (0..points.len())
+        .map(|_| random.next_int(cluster_count as i32) as usize)
+        .collect();
+    let mut distances = vec![vec![0.0; cluster_count]; cluster_count];
+    let mut pixel_count_sums = vec![0u32; cluster_count];
+
+    for iteration in 0..MAX_ITERATIONS {
+        for i in 0..cluster_count {
+            for j in i + 1..cluster_count {
+                let d = point_provider.distance(&clusters[i], &clusters[j]);
+                distances[i][j] = d;
+                distances[j][i] = d;
+            }
+        }
+
+        let mut points_moved = 0;
+        for (point, cluster_index) in points.iter().zip(cluster_indices.iter_mut()) {
+            let previous_index = *cluster_index;
+            let previous_distance = point_provider.distance(point, &clusters[previous_index]);
+            let mut minimum_distance = previous_distance;
+            let mut new_index = None;
+            for (j, cluster) in clusters.iter().enumerate() {
+                // By the triangle inequality, a cluster at least twice as far from the current
+                // one as the point is can't be any closer to the point.
+                if distances[previous_index][j] >= 4.0 * previous_distance {
+                    continue;
+                }
+                let d = point_provider.distance(point, cluster);
+                if d < minimum_distance {
+                    minimum_distance = d;
+                    new_index = Some(j);
+                }
+            }
+            if let Some(new_index) = new_index {
+                let distance_change = (minimum_distance.sqrt() - previous_distance.sqrt()).abs();
+                if distance_change > MIN_MOVEMENT_DISTANCE {
+                    points_moved += 1;
+                    *cluster_index = new_index;
+                }
+            }
+        }
+
+        if points_moved == 0 && iteration != 0 {
+            break;
+        }
+
+        let mut sums = vec![[0.0; 3]; cluster_count];
+        pixel_count_sums.iter_mut().for_each(|sum| *sum = 0);
+        for ((point, &cluster_index), &count) in points.iter().zip(&cluster_indices).zip(&counts) {
+            pixel_count_sums[cluster_index] += count;
+            for k in 0..3 {
+                sums[cluster_index][k] += point[k] * count as f64;
+            }
+        }
+        for ((cluster, sum), &count) in clusters.iter_mut().zip(&sums).zip(&pixel_count_sums) {
+            *cluster = if count == 0 {
+                [0.0; 3]
+            } else {
+                sum.map(|component| component / count as f64)
+            };
+        }
+    }
+
+    let mut argb_to_population = HashMap::new();
+    for (cluster, &count) in clusters.iter().zip(&pixel_count_sums) {
+        if count == 0 {
+            continue;
+        }
+        let argb = point_provider.argb_from_point(cluster);
+        argb_to_population.entry(argb).or_insert(count);
+    }
+    argb_to_population
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn java_random() {
+        // Values from `new java.util.Random(0)`.
+        let mut random = JavaRandom::new(0);
+        let ints: Vec<i32> = (0..6).map(|_| random.next_int(10)).collect();
+        assert_eq!(ints, [0, 8, 9, 7, 5, 3]);
+    }
+
+    #[test]
+    fn one_color() {
+        let result = quantize(&[[0xff, 0x14, 0x14, 0x14]; 3], &[], 128);
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[&[0xff, 0x14, 0x14, 0x14]], 3);
+        assert!(quantize(&[], &[], 128).is_empty());
+    }
+
+    #[test]
+    fn keeps_starting_clusters() {
+        let red = [0xff, 0xff, 0x00, 0x00];
+        let blue = [0xff, 0x00, 0x00, 0xff];
+        let pixels = [red, red, blue, [0xff, 0xf0, 0x00, 0x00]];
+        let result = quantize(&pixels, &[red, blue], 128);
+        assert_eq!(result.values().sum::<u32>(), 4);
+        assert_eq!(result[&blue], 1);
+    }
+
+    #[test]
+    fn rgb_point_providers() {
+        use crate::quantize::point_provider::{PointProviderRedmean, PointProviderRgb};
+
+        let red = [0xff, 0xff, 0x00, 0x00];
+        let blue = [0xff, 0x00, 0x00, 0xff];
+        let pixels = [red, red, blue, blue, blue];
+        for result in [
+            quantize_with_point_provider(&pixels, &[red, blue], 128, &PointProviderRgb),
+            quantize_with_point_provider(&pixels, &[red, blue], 128, &PointProviderRedmean),
+        ] {
+            assert_eq!(result.len(), 2);
+            assert_eq!(result[&red], 2);
+            assert_eq!(result[&blue], 3);
+        }
+        let random_start = quantize_with_point_provider(&pixels, &[], 2, &PointProviderRedmean);
+        assert_eq!(random_start.values().sum::<u32>(), 5);
+    }
+}
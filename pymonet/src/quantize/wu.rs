@@ -0,0 +1,407 @@
+//! An image quantizer that divides the image's pixels into clusters by recursively cutting an RGB
+//! cube, based on the weight of pixels in each area of the cube.
+//!
+//! The algorithm was described by Xiaolin Wu in Graphic Gems II, published in 1991.
+use std::collections::HashMap;
+
+const INDEX_BITS: u32 = 5;
+const INDEX_COUNT: usize = (1 << INDEX_BITS) + 1;
+const TOTAL_SIZE: usize = INDEX_COUNT * INDEX_COUNT * INDEX_COUNT;
+
+#[derive(Clone, Copy, Default)]
+struct Cube {
+    r0: usize,
+    r1: usize,
+    g0: usize,
+    g1: usize,
+    b0: usize,
+    b1: usize,
+    vol: usize,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Direction {
+    Red,
+    Green,
+    Blue,
+}
+
+/// Cumulative moments of the color histogram, so that the weight and color sums of any box can
+/// be read in constant time.
+struct Moments {
+    weights: Vec<i64>,
+    moments_r: Vec<i64>,
+    moments_g: Vec<i64>,
+    moments_b: Vec<i64>,
+    moments: Vec<f64>,
+}
+
+fn index(r: usize, g: usize, b: usize) -> usize {
+    (r << (INDEX_BITS * 2)) + (r << (INDEX_BITS + 1)) + r + (g << INDEX_BITS) + g + b
+}
+
+/// Reduces the colors of an image to at most `max_colors`, by cutting the RGB cube into boxes of
+/// low variance.
+///
+/// # Arguments
+///
+/// * `pixels`: Colors of the image. Alpha is ignored.
+/// * `max_colors`: The number of colors to divide the image into. A lower number of colors may be
+///   returned.
+///
+/// # Returns
+///
+/// * The average color of each box, opaque, in the order the boxes were created.
+pub fn quantize(pixels: &[[u8; 4]], max_colors: usize) -> Vec<[u8; 4]> {
+    if max_colors == 0 {
+        return Vec::new();
+    }
+    let mut counts: HashMap<[u8; 4], i64> = HashMap::new();
+    for &pixel in pixels {
+        *counts.entry(pixel).or_insert(0) += 1;
+    }
+    let mut moments = Moments::from_histogram(&counts);
+    moments.accumulate();
+    let (cubes, count) = moments.create_boxes(max_colors);
+    moments.create_result(&cubes[..count])
+}
+
+impl Moments {
+    fn from_histogram(counts: &HashMap<[u8; 4], i64>) -> Moments {
+        let mut moments = Moments {
+            weights: vec![0; TOTAL_SIZE],
+            moments_r: vec![0; TOTAL_SIZE],
+            moments_g: vec![0; TOTAL_SIZE],
+            moments_b: vec![0; TOTAL_SIZE],
+            moments: vec![0.0; TOTAL_SIZE],
+        };
+        let bits_to_remove = 8 - INDEX_BITS;
+        for (&[_, red, green, blue], &count) in counts {
+            let i = index(
+                (red >> bits_to_remove) as usize + 1,
+                (green >> bits_to_remove) as usize + 1,
+                (blue >> bits_to_remove) as usize + 1,
+            );
+            let (red, green, blue) = (red as i64, green as i64, blue as i64);
+            moments.weights[i] += count;
+            moments.moments_r[i] += red * count;
+            moments.moments_g[i] += green * count;
+            moments.moments_b[i] += blue * count;
+            moments.moments[i] += (count * (red * red + green * green + blue * blue)) as f64;
+        }
+        moments
+    }
+
+    fn accumulate(&mut self) {
+        for r in 1..INDEX_COUNT {
+            let mut area = [0i64; INDEX_COUNT];
+            let mut area_r = [0i64; INDEX_COUNT];
+            let mut area_g = [0i64; INDEX_COUNT];
+            let mut area_b = [0i64; INDEX_COUNT];
+            let mut area2 = [0.0f64; INDEX_COUNT];
+            for g in 1..INDEX_COUNT {
+                let mut line = 0;
+                let mut line_r = 0;
+                let mut line_g = 0;
+                let mut line_b = 0;
+                let mut line2 = 0.0;
+                for b in 1..INDEX_COUNT {
+                    let i = index(r, g, b);
+                    line += self.weights[i];
+                    line_r += self.moments_r[i];
+                    line_g += self.moments_g[i];
+                    line_b += self.moments_b[i];
+                    line2 += self.moments[i];
+
+                    area[b] += line;
+                    area_r[b] += line_r;
+                    area_g[b] += line_g;
+                    area_b[b] += line_b;
+                    area2[b] += line2;
+
+                    let previous = index(r - 1, g, b);
+                    self.weights[i] = self.weights[previous] + area[b];
+                    self.moments_r[i] = self.moments_r[previous] + area_r[b];
+                    self.moments_g[i] = self.moments_g[previous] + area_g[b];
+                    self.moments_b[i] = self.moments_b[previous] + area_b[b];
+                    self.moments[i] = self.moments[previous] + area2[b];
+                }
+            }
+        }
+    }
+
+    fn create_boxes(&self, max_colors: usize) -> (Vec<Cube>, usize) {
+        let mut cubes = vec![Cube::default(); max_colors];
+        cubes[0].r1 = INDEX_COUNT - 1;
+        cubes[0].g1 = INDEX_COUNT - 1;
+        cubes[0].b1 = INDEX_COUNT - 1;
+        let mut volume_variance = vec![0.0; max_colors];
+        let mut generated_color_count = max_colors;
+        let mut next = 0;
+        let mut i = 1;
+        while i < max_colors {
+            let (one, two) = cubes.split_at_mut(i);
+            if self.cut(&mut one[next], &mut two[0]) {
+                volume_variance[next] = if cubes[next].vol > 1 {
+                    self.variance(&cubes[next])
+                } else {
+                    0.0
+                };
+                volume_variance[i] = if cubes[i].vol > 1 {
+                    self.variance(&cubes[i])
+                } else {
+                    0.0
+                };
+            } else {
+                volume_variance[next] = 0.0;
+                i -= 1;
+            }
+
+            next = 0;
+            let mut temp = volume_variance[0];
+            for (j, &variance) in volume_variance.iter().enumerate().take(i + 1).skip(1) {
+                if variance > temp {
+                    temp = variance;
+                    next = j;
+                }
+            }
+            if temp <= 0.0 {
+                generated_color_count = i + 1;
+                break;
+            }
+            i += 1;
+        }
+        (cubes, generated_color_count)
+    }
+
+    fn create_result(&self, cubes: &[Cube]) -> Vec<[u8; 4]> {
+        cubes
+            .iter()
+            .filter_map(|cube| {
+                let weight = volume(cube, &self.weights);
+                if weight <= 0 {
+                    return None;
+                }
+                let r = volume(cube, &self.moments_r) / weight;
+                let g = volume(cube, &self.moments_g) / weight;
+                let b = volume(cube, &self.moments_b) / weight;
+                Some([255, r as u8, g as u8, b as u8])
+            })
+            .collect()
+    }
+
+    fn variance(&self, cube: &Cube) -> f64 {
+        let dr = volume(cube, &self.moments_r) as f64;
+        let dg = volume(cube, &self.moments_g) as f64;
+        let db = volume(cube, &self.moments_b) as f64;
+        let m = &self.moments;
+        let xx = m[index(cube.r1, cube.g1, cube.b1)]
+            - m[index(cube.r1, cube.g1, cube.b0)]
+            - m[index(cube.r1, cube.g0, cube.b1)]
+            + m[index(cube.r1, cube.g0, cube.b0)]
+            - m[index(cube.r0, cube.g1, cube.b1)]
+            + m[index(cube.r0, cube.g1, cube.b0)]
+            + m[index(cube.r0, cube.g0, cube.b1)]
+            - m[index(cube.r0, cube.g0, cube.b0)];
+        let hypotenuse = dr * dr + dg * dg + db * db;
+        let volume = volume(cube, &self.weights) as f64;
+        xx - hypotenuse / volume
+    }
+
+    fn cut(&self, one: &mut Cube, two: &mut Cube) -> bool {
+        let whole = [
+            volume(one, &self.moments_r),
+            volume(one, &self.moments_g),
+            volume(one, &self.moments_b),
+            volume(one, &self.weights),
+        ];
+        let (cut_r, max_r) = self.maximize(one, Direction::Red, one.r0 + 1, one.r1, whole);
+        let (cut_g, max_g) = self.maximize(one, Direction::Green, one.g0 + 1, one.g1, whole);
+        let (cut_b, max_b) = self.maximize(one, Direction::Blue, one.b0 + 1, one.b1, whole);
+
+        let direction = if max_r >= max_g && max_r >= max_b {
+            if cut_r.is_none() {
+                return false;
+            }
+            Direction::Red
+        } else if max_g >= max_r && max_g >= max_b {
+            Direction::Green
+        } else {
+            Direction::Blue
+        };
+
+        two.r1 = one.r1;
+        two.g1 = one.g1;
+        two.b1 = one.b1;
+        // A cut of `None` here has a maximum of 0.0, which would have been picked above only if
+        // red also had no cut.
+        match direction {
+            Direction::Red => {
+                one.r1 = cut_r.unwrap_or(one.r1);
+                two.r0 = one.r1;
+                two.g0 = one.g0;
+                two.b0 = one.b0;
+            }
+            Direction::Green => {
+                one.g1 = cut_g.unwrap_or(one.g1);
+                two.r0 = one.r0;
+                two.g0 = one.g1;
+                two.b0 = one.b0;
+            }
+            Direction::Blue => {
+                one.b1 = cut_b.unwrap_or(one.b1);
+                two.r0 = one.r0;
+                two.g0 = one.g0;
+                two.b0 = one.b1;
+            }
+        }
+        one.vol = (one.r1 - one.r0) * (one.g1 - one.g0) * (one.b1 - one.b0);
+        two.vol = (two.r1 - two.r0) * (two.g1 - two.g0) * (two.b1 - two.b0);
+        true
+    }
+
+    /// Finds the position along `direction` that best splits `cube` in two, and the resulting
+    /// sum of squared color means weighted by population.
+    fn maximize(
+        &self,
+        cube: &Cube,
+        direction: Direction,
+        first: usize,
+        last: usize,
+        whole: [i64; 4],
+    ) -> (Option<usize>, f64) {
+        let moments = [
+            &self.moments_r,
+            &self.moments_g,
+            &self.moments_b,
+            &self.weights,
+        ];
+        let base = moments.map(|moment| bottom(cube, direction, moment));
+        let mut max = 0.0;
+        let mut cut = None;
+        for i in first..last {
+            let half: [i64; 4] =
+                [0, 1, 2, 3].map(|k| base[k] + top(cube, direction, i, moments[k]));
+            if half[3] == 0 {
+                continue;
+            }
+            let other: [i64; 4] = [0, 1, 2, 3].map(|k| whole[k] - half[k]);
+            if other[3] == 0 {
+                continue;
+            }
+            let score = |[r, g, b, w]: [i64; 4]| {
+                let (r, g, b) = (r as f64, g as f64, b as f64);
+                (r * r + g * g + b * b) / w as f64
+            };
+            let temp = score(half) + score(other);
+            if temp > max {
+                max = temp;
+                cut = Some(i);
+            }
+        }
+        (cut, max)
+    }
+}
+
+fn volume(cube: &Cube, moment: &[i64]) -> i64 {
+    moment[index(cube.r1, cube.g1, cube.b1)]
+        - moment[index(cube.r1, cube.g1, cube.b0)]
+        - moment[index(cube.r1, cube.g0, cube.b1)]
+        + moment[index(cube.r1, cube.g0, cube.b0)]
+        - moment[index(cube.r0, cube.g1, cube.b1)]
+        + moment[index(cube.r0, cube.g1, cube.b0)]
+        + moment[index(cube.r0, cube.g0, cube.b1)]
+        - moment[index(cube.r0, cube.g0, cube.b0)]
+}
+
+fn bottom(cube: &Cube, direction: Direction, moment: &[i64]) -> i64 {
+    match direction {
+        Direction::Red => {
+            -moment[index(cube.r0, cube.g1, cube.b1)]
+                + moment[index(cube.r0, cube.g1, cube.b0)]
+                + moment[index(cube.r0, cube.g0, cube.b1)]
+                - moment[index(cube.r0, cube.g0, cube.b0)]
+        }
+        Direction::Green => {
+            -moment[index(cube.r1, cube.g0, cube.b1)]
+                + moment[index(cube.r1, cube.g0, cube.b0)]
+                + moment[index(cube.r0, cube.g0, cube.b1)]
+                - moment[index(cube.r0, cube.g0, cube.b0)]
+        }
+        Direction::Blue => {
+            -moment[index(cube.r1, cube.g1, cube.b0)]
+                + moment[index(cube.r1, cube.g0, cube.b0)]
+                + moment[index(cube.r0, cube.g1, cube.b0)]
+                - moment[index(cube.r0, cube.g0, cube.b0)]
+        }
+    }
+}
+
+fn top(cube: &Cube, direction: Direction, position: usize, moment: &[i64]) -> i64 {
+    match direction {
+        Direction::Red => {
+            moment[index(position, cube.g1, cube.b1)]
+                - moment[index(position, cube.g1, cube.b0)]
+                - moment[index(position, cube.g0, cube.b1)]
+                + moment[index(position, cube.g0, cube.b0)]
+        }
+        Direction::Green => {
+            moment[index(cube.r1, position, cube.b1)]
+                - moment[index(cube.r1, position, cube.b0)]
+                - moment[index(cube.r0, position, cube.b1)]
+                + moment[index(cube.r0, position, cube.b0)]
+        }
+        Direction::Blue => {
+            moment[index(cube.r1, cube.g1, position)]
+                - moment[index(cube.r1, cube.g0, position)]
+                - moment[index(cube.r0, cube.g1, position)]
+                + moment[index(cube.r0, cube.g0, position)]
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RED: [u8; 4] = [0xff, 0xff, 0x00, 0x00];
+    const GREEN: [u8; 4] = [0xff, 0x00, 0xff, 0x00];
+    const BLUE: [u8; 4] = [0xff, 0x00, 0x00, 0xff];
+
+    #[test]
+    fn one_color() {
+        assert_eq!(
+            quantize(&[[0xff, 0x14, 0x14, 0x14]], 128),
+            vec![[0xff, 0x14, 0x14, 0x14]]
+        );
+    }
+
+    #[test]
+    fn three_colors() {
+        let result = quantize(&[BLUE, RED, GREEN, RED, BLUE, BLUE], 128);
+        assert_eq!(result.len(), 3);
+        for color in [RED, GREEN, BLUE] {
+            assert!(result.contains(&color));
+        }
+    }
+
+    #[test]
+    fn matches_upstream_order() {
+        // The order the upstream QuantizerWu tests expect.
+        assert_eq!(
+            quantize(&[RED, RED, GREEN, GREEN, GREEN], 256),
+            [GREEN, RED]
+        );
+        assert_eq!(quantize(&[RED, GREEN, BLUE], 256), [BLUE, RED, GREEN]);
+        assert_eq!(quantize(&[BLUE; 5], 256), [BLUE]);
+    }
+
+    #[test]
+    fn limits_color_count() {
+        let pixels: Vec<[u8; 4]> = (0..=255u8).map(|v| [0xff, v, 255 - v, v / 2]).collect();
+        let result = quantize(&pixels, 8);
+        assert!(!result.is_empty() && result.len() <= 8);
+        assert!(quantize(&pixels, 0).is_empty());
+        assert!(quantize(&[], 8).is_empty());
+    }
+}
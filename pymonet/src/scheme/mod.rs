@@ -1,9 +1,19 @@
-use std::ops::Index;
+use std::ops::{Index, IndexMut};
 use self::Role::*;
 use std::slice::Iter;
-use crate::palettes::core::CorePalette;
+use crate::palettes::{core::CorePalette, tonal::TonalPalette};
+use crate::contrast::{darker, darker_unsafe, lighter, lighter_unsafe, ratio_of_tones};
+use crate::hct::Hct;
+use crate::utils::color::{composite_gamma, lstar_from_argb, with_alpha};
+use crate::utils::math::{clamp_double, lerp};
+use crate::utils::string::{hex_from_argb, try_argb_from_hex, ParseColorError};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
 
-#[derive(Debug)]
+pub mod variant;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Role {
     Primary,
     OnPrimary,
@@ -34,55 +44,326 @@ pub enum Role {
     InverseSurface,
     InverseOnSurface,
     InversePrimary,
+    SurfaceTint,
 }
 
+/// camelCase, kebab-case, and snake_case names of each role, in declaration order.
+static ROLE_NAMES: [[&str; 3]; Scheme::ROLE_COUNT] = [
+    ["primary", "primary", "primary"],
+    ["onPrimary", "on-primary", "on_primary"],
+    ["primaryContainer", "primary-container", "primary_container"],
+    [
+        "onPrimaryContainer",
+        "on-primary-container",
+        "on_primary_container",
+    ],
+    ["secondary", "secondary", "secondary"],
+    ["onSecondary", "on-secondary", "on_secondary"],
+    [
+        "secondaryContainer",
+        "secondary-container",
+        "secondary_container",
+    ],
+    [
+        "onSecondaryContainer",
+        "on-secondary-container",
+        "on_secondary_container",
+    ],
+    ["tertiary", "tertiary", "tertiary"],
+    ["onTertiary", "on-tertiary", "on_tertiary"],
+    [
+        "tertiaryContainer",
+        "tertiary-container",
+        "tertiary_container",
+    ],
+    [
+        "onTertiaryContainer",
+        "on-tertiary-container",
+        "on_tertiary_container",
+    ],
+    ["error", "error", "error"],
+    ["onError", "on-error", "on_error"],
+    ["errorContainer", "error-container", "error_container"],
+    [
+        "onErrorContainer",
+        "on-error-container",
+        "on_error_container",
+    ],
+    ["background", "background", "background"],
+    ["onBackground", "on-background", "on_background"],
+    ["surface", "surface", "surface"],
+    ["onSurface", "on-surface", "on_surface"],
+    ["surfaceVariant", "surface-variant", "surface_variant"],
+    [
+        "onSurfaceVariant",
+        "on-surface-variant",
+        "on_surface_variant",
+    ],
+    ["outline", "outline", "outline"],
+    ["outlineVariant", "outline-variant", "outline_variant"],
+    ["shadow", "shadow", "shadow"],
+    ["scrim", "scrim", "scrim"],
+    ["inverseSurface", "inverse-surface", "inverse_surface"],
+    [
+        "inverseOnSurface",
+        "inverse-on-surface",
+        "inverse_on_surface",
+    ],
+    ["inversePrimary", "inverse-primary", "inverse_primary"],
+    ["surfaceTint", "surface-tint", "surface_tint"],
+];
+
 // This was only needed for looping through the "scheme" to see the values
 impl Role {
     pub fn iterator() -> Iter<'static, Role> {
-        static ROLES: [Role; 29] = [
-          Primary, OnPrimary, PrimaryContainer, OnPrimaryContainer, Secondary,
-          OnSecondary, SecondaryContainer, OnSecondaryContainer, Tertiary,
-          OnTertiary, TertiaryContainer, OnTertiaryContainer, Error, OnError,
-          ErrorContainer, OnErrorContainer, Background, OnBackground,
-          Surface, OnSurface, SurfaceVariant, OnSurfaceVariant, Outline,
-          OutlineVariant, Shadow, Scrim, InverseSurface, InverseOnSurface,
-          InversePrimary,];
+        static ROLES: [Role; Scheme::ROLE_COUNT] = Scheme::ROLE_ORDER;
         ROLES.iter()
     }
+
+    /// The role's name in camelCase, ex. `onPrimaryContainer`, as used by [`fmt::Display`].
+    pub fn as_camel(&self) -> &'static str {
+        ROLE_NAMES[*self as usize][0]
+    }
+
+    /// The role's name in kebab-case, ex. `on-primary-container`.
+    pub fn as_kebab(&self) -> &'static str {
+        ROLE_NAMES[*self as usize][1]
+    }
+
+    /// The role's name in snake_case, ex. `on_primary_container`.
+    pub fn as_snake(&self) -> &'static str {
+        ROLE_NAMES[*self as usize][2]
+    }
+
+    /// The palette this role's color is drawn from.
+    pub fn palette<'a>(&self, core: &'a CorePalette) -> &'a TonalPalette {
+        match self {
+            Primary | OnPrimary | PrimaryContainer | OnPrimaryContainer | InversePrimary
+            | SurfaceTint => &core.a1,
+            Secondary | OnSecondary | SecondaryContainer | OnSecondaryContainer => &core.a2,
+            Tertiary | OnTertiary | TertiaryContainer | OnTertiaryContainer => &core.a3,
+            Error | OnError | ErrorContainer | OnErrorContainer => &core.error,
+            SurfaceVariant | OnSurfaceVariant | Outline | OutlineVariant => &core.n2,
+            Background | OnBackground | Surface | OnSurface | Shadow | Scrim | InverseSurface
+            | InverseOnSurface => &core.n1,
+        }
+    }
+
+    /// The role for text and icons drawn on this role, ex. `OnPrimary` for `Primary`.
+    pub fn on_role(&self) -> Option<Role> {
+        match self {
+            Primary => Some(OnPrimary),
+            PrimaryContainer => Some(OnPrimaryContainer),
+            Secondary => Some(OnSecondary),
+            SecondaryContainer => Some(OnSecondaryContainer),
+            Tertiary => Some(OnTertiary),
+            TertiaryContainer => Some(OnTertiaryContainer),
+            Error => Some(OnError),
+            ErrorContainer => Some(OnErrorContainer),
+            Background => Some(OnBackground),
+            Surface => Some(OnSurface),
+            SurfaceVariant => Some(OnSurfaceVariant),
+            InverseSurface => Some(InverseOnSurface),
+            _ => None,
+        }
+    }
+}
+
+/// How the dark scheme of a theme is generated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DarkMode {
+    /// [`Scheme::dark_from_core_palette`].
+    #[default]
+    Standard,
+    /// [`Scheme::dark_from_core_palette_amoled`], with a pure black background and surface.
+    Amoled,
+}
+
+impl fmt::Display for Role {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_camel())
+    }
+}
+
+/// The name given to [`Role::from_str`] is not a role.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseRoleError(pub String);
+
+impl fmt::Display for ParseRoleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "unknown color role {:?}, expected a name like \"onPrimaryContainer\", \
+             \"on-primary-container\", or \"on_primary_container\"",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for ParseRoleError {}
+
+/// Parses a role from its camelCase, kebab-case, or snake_case name, ignoring case.
+impl FromStr for Role {
+    type Err = ParseRoleError;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        let normalized: String = name
+            .trim()
+            .chars()
+            .filter(|c| *c != '-' && *c != '_')
+            .collect();
+        Role::iterator()
+            .find(|role| role.as_camel().eq_ignore_ascii_case(&normalized))
+            .copied()
+            .ok_or_else(|| ParseRoleError(name.to_string()))
+    }
+}
+
+/// A problem with one role of a map given to [`Scheme::from_hex_map`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SchemeProblem {
+    /// The map has no entry for the role.
+    Missing(Role),
+    /// The role's value is not a hex color.
+    Invalid {
+        role: Role,
+        value: String,
+        error: ParseColorError,
+    },
+}
+
+impl fmt::Display for SchemeProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SchemeProblem::Missing(role) => write!(f, "missing role {role}"),
+            SchemeProblem::Invalid { role, value, error } => {
+                write!(f, "invalid color {value:?} for role {role}: {error}")
+            }
+        }
+    }
+}
+
+/// Every problem found by [`Scheme::from_hex_map`], in [`Role::iterator`] order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemeError {
+    pub problems: Vec<SchemeProblem>,
 }
 
+impl fmt::Display for SchemeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, problem) in self.problems.iter().enumerate() {
+            if index > 0 {
+                f.write_str("; ")?;
+            }
+            write!(f, "{problem}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for SchemeError {}
+
 /// Represents a Material color scheme, a mapping of color roles to colors.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+// `remote = "Self"` derives inherent `Scheme::serialize` and `Scheme::deserialize`, which the
+// trait impls below wrap.
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase", remote = "Self"))]
 pub struct Scheme {
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::color_format"))]
     pub primary: [u8; 4],
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::color_format"))]
     pub on_primary: [u8; 4],
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::color_format"))]
     pub primary_container: [u8; 4],
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::color_format"))]
     pub on_primary_container: [u8; 4],
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::color_format"))]
     pub secondary: [u8; 4],
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::color_format"))]
     pub on_secondary: [u8; 4],
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::color_format"))]
     pub secondary_container: [u8; 4],
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::color_format"))]
     pub on_secondary_container: [u8; 4],
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::color_format"))]
     pub tertiary: [u8; 4],
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::color_format"))]
     pub on_tertiary: [u8; 4],
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::color_format"))]
     pub tertiary_container: [u8; 4],
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::color_format"))]
     pub on_tertiary_container: [u8; 4],
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::color_format"))]
     pub error: [u8; 4],
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::color_format"))]
     pub on_error: [u8; 4],
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::color_format"))]
     pub error_container: [u8; 4],
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::color_format"))]
     pub on_error_container: [u8; 4],
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::color_format"))]
     pub background: [u8; 4],
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::color_format"))]
     pub on_background: [u8; 4],
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::color_format"))]
     pub surface: [u8; 4],
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::color_format"))]
     pub on_surface: [u8; 4],
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::color_format"))]
     pub surface_variant: [u8; 4],
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::color_format"))]
     pub on_surface_variant: [u8; 4],
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::color_format"))]
     pub outline: [u8; 4],
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::color_format"))]
     pub outline_variant: [u8; 4],
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::color_format"))]
     pub shadow: [u8; 4],
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::color_format"))]
     pub scrim: [u8; 4],
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::color_format"))]
     pub inverse_surface: [u8; 4],
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::color_format"))]
     pub inverse_on_surface: [u8; 4],
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::color_format"))]
     pub inverse_primary: [u8; 4],
+    /// Tints surfaces by elevation. The same color as `primary`.
+    // Deserialized by the `Deserialize` impl below, which falls back to `primary` when absent.
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::utils::color_format", skip_deserializing)
+    )]
+    pub surface_tint: [u8; 4],
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Scheme {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        Scheme::serialize(self, serializer)
+    }
+}
+
+/// Schemes without a `surfaceTint`, ex. from the TypeScript library, which has no such role, get
+/// theirs from `primary`.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Scheme {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(serde::Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct WithSurfaceTint {
+            surface_tint: Option<crate::utils::color_format::SerdeColor>,
+            #[serde(flatten, with = "Scheme")]
+            scheme: Scheme,
+        }
+
+        let WithSurfaceTint {
+            surface_tint,
+            mut scheme,
+        } = WithSurfaceTint::deserialize(deserializer)?;
+        scheme.surface_tint = surface_tint.map_or(scheme.primary, |tint| tint.0);
+        Ok(scheme)
+    }
 }
 
 impl Index<&Role> for Scheme {
@@ -119,12 +400,250 @@ impl Index<&Role> for Scheme {
       Role::InverseSurface => &self.inverse_surface,
       Role::InverseOnSurface => &self.inverse_on_surface,
       Role::InversePrimary => &self.inverse_primary,
+      Role::SurfaceTint => &self.surface_tint,
     }
   }
 }
 
+impl Index<Role> for Scheme {
+    type Output = [u8; 4];
+
+    fn index(&self, role: Role) -> &Self::Output {
+        &self[&role]
+    }
+}
+
+impl IndexMut<&Role> for Scheme {
+    fn index_mut(&mut self, role: &Role) -> &mut Self::Output {
+        match &role {
+            Role::Primary => &mut self.primary,
+            Role::OnPrimary => &mut self.on_primary,
+            Role::PrimaryContainer => &mut self.primary_container,
+            Role::OnPrimaryContainer => &mut self.on_primary_container,
+            Role::Secondary => &mut self.secondary,
+            Role::OnSecondary => &mut self.on_secondary,
+            Role::SecondaryContainer => &mut self.secondary_container,
+            Role::OnSecondaryContainer => &mut self.on_secondary_container,
+            Role::Tertiary => &mut self.tertiary,
+            Role::OnTertiary => &mut self.on_tertiary,
+            Role::TertiaryContainer => &mut self.tertiary_container,
+            Role::OnTertiaryContainer => &mut self.on_tertiary_container,
+            Role::Error => &mut self.error,
+            Role::OnError => &mut self.on_error,
+            Role::ErrorContainer => &mut self.error_container,
+            Role::OnErrorContainer => &mut self.on_error_container,
+            Role::Background => &mut self.background,
+            Role::OnBackground => &mut self.on_background,
+            Role::Surface => &mut self.surface,
+            Role::OnSurface => &mut self.on_surface,
+            Role::SurfaceVariant => &mut self.surface_variant,
+            Role::OnSurfaceVariant => &mut self.on_surface_variant,
+            Role::Outline => &mut self.outline,
+            Role::OutlineVariant => &mut self.outline_variant,
+            Role::Shadow => &mut self.shadow,
+            Role::Scrim => &mut self.scrim,
+            Role::InverseSurface => &mut self.inverse_surface,
+            Role::InverseOnSurface => &mut self.inverse_on_surface,
+            Role::InversePrimary => &mut self.inverse_primary,
+            Role::SurfaceTint => &mut self.surface_tint,
+        }
+    }
+}
+
+impl IndexMut<Role> for Scheme {
+    fn index_mut(&mut self, role: Role) -> &mut Self::Output {
+        &mut self[&role]
+    }
+}
+
+/// Iterator over the roles of a [`Scheme`] and their colors, in [`Role::iterator`] order.
+pub struct SchemeIter<'a> {
+    scheme: &'a Scheme,
+    roles: Iter<'static, Role>,
+}
+
+impl Iterator for SchemeIter<'_> {
+    type Item = (Role, [u8; 4]);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.roles.next().map(|role| (*role, self.scheme[role]))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.roles.size_hint()
+    }
+}
+
+impl ExactSizeIterator for SchemeIter<'_> {}
+
+impl<'a> IntoIterator for &'a Scheme {
+    type Item = (Role, [u8; 4]);
+    type IntoIter = SchemeIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Roles whose tone follows the contrast level: each with the role it sits on, and the contrast
+/// ratios it reaches against that role at contrast levels -1 and 1. Accents come first, so that
+/// the roles on them are measured against their adjusted tones.
+static CONTRAST_ROLES: [(Role, Role, f64, f64); 19] = [
+    (Primary, Surface, 3.0, 7.0),
+    (Secondary, Surface, 3.0, 7.0),
+    (Tertiary, Surface, 3.0, 7.0),
+    (Error, Surface, 3.0, 7.0),
+    (OnPrimary, Primary, 4.5, 11.0),
+    (OnPrimaryContainer, PrimaryContainer, 4.5, 21.0),
+    (OnSecondary, Secondary, 4.5, 11.0),
+    (OnSecondaryContainer, SecondaryContainer, 4.5, 21.0),
+    (OnTertiary, Tertiary, 4.5, 11.0),
+    (OnTertiaryContainer, TertiaryContainer, 4.5, 21.0),
+    (OnError, Error, 4.5, 11.0),
+    (OnErrorContainer, ErrorContainer, 4.5, 21.0),
+    (OnBackground, Background, 4.5, 21.0),
+    (OnSurface, Surface, 4.5, 21.0),
+    (OnSurfaceVariant, SurfaceVariant, 3.0, 11.0),
+    (Outline, Surface, 1.5, 7.0),
+    (OutlineVariant, Surface, 1.0, 4.5),
+    (InverseOnSurface, InverseSurface, 4.5, 21.0),
+    (InversePrimary, InverseSurface, 3.0, 7.0),
+];
+
+/// Opacity of `surface_tint` over `surface` at elevation levels 0 through 5.
+const ELEVATION_OPACITIES: [f64; 6] = [0.0, 0.05, 0.08, 0.11, 0.12, 0.14];
+
+/// Contrast ratio [`Scheme::with_overrides_and_on_colors`] keeps between a role and its on-role.
+const MIN_ON_COLOR_RATIO: f64 = 4.5;
+
 impl Scheme {
-    pub fn light_from_core_palette(core: &mut CorePalette) -> Scheme {
+    /// The number of roles in a scheme.
+    pub const ROLE_COUNT: usize = 30;
+
+    /// The order of roles in [`Scheme::to_array`] and [`Scheme::from_array`], and of
+    /// [`Role::iterator`].
+    ///
+    /// This order is a stable contract for FFI and GPU buffers: existing roles never move, and
+    /// new roles are only ever appended.
+    pub const ROLE_ORDER: [Role; Scheme::ROLE_COUNT] = [
+      Primary, OnPrimary, PrimaryContainer, OnPrimaryContainer, Secondary,
+      OnSecondary, SecondaryContainer, OnSecondaryContainer, Tertiary,
+      OnTertiary, TertiaryContainer, OnTertiaryContainer, Error, OnError,
+      ErrorContainer, OnErrorContainer, Background, OnBackground,
+      Surface, OnSurface, SurfaceVariant, OnSurfaceVariant, Outline,
+      OutlineVariant, Shadow, Scrim, InverseSurface, InverseOnSurface,
+      InversePrimary, SurfaceTint,];
+
+    /// The colors of the scheme as a flat array in [`Scheme::ROLE_ORDER`], ex. for a uniform
+    /// buffer.
+    pub fn to_array(&self) -> [[u8; 4]; Scheme::ROLE_COUNT] {
+        Scheme::ROLE_ORDER.map(|role| self[role])
+    }
+
+    /// Reads a scheme from colors in [`Scheme::ROLE_ORDER`], the inverse of
+    /// [`Scheme::to_array`].
+    pub fn from_array(colors: [[u8; 4]; Scheme::ROLE_COUNT]) -> Scheme {
+        let mut scheme = Scheme::default();
+        for (role, argb) in Scheme::ROLE_ORDER.into_iter().zip(colors) {
+            scheme[role] = argb;
+        }
+        scheme
+    }
+
+    /// Iterates over every role and its color, in [`Role::iterator`] order.
+    pub fn iter(&self) -> SchemeIter<'_> {
+        SchemeIter {
+            scheme: self,
+            roles: Role::iterator(),
+        }
+    }
+
+    /// Iterates over the color of every role, in [`Role::iterator`] order.
+    pub fn values(&self) -> impl Iterator<Item = [u8; 4]> + '_ {
+        self.iter().map(|(_, argb)| argb)
+    }
+
+    /// Compares two schemes, allowing small differences, ex. from floating point rounding on
+    /// another platform.
+    ///
+    /// # Arguments
+    ///
+    /// * `other`: Scheme to compare with.
+    /// * `max_channel_delta`: Largest difference allowed in any channel of a role's color.
+    ///
+    /// # Returns
+    ///
+    /// * Whether no role differs by more than `max_channel_delta`.
+    pub fn approx_eq(&self, other: &Scheme, max_channel_delta: u8) -> bool {
+        self.differing_roles(other, max_channel_delta).is_empty()
+    }
+
+    /// The roles whose colors differ by more than `max_channel_delta` in any channel, in
+    /// [`Role::iterator`] order. See [`Scheme::approx_eq`].
+    pub fn differing_roles(&self, other: &Scheme, max_channel_delta: u8) -> Vec<Role> {
+        self.iter()
+            .zip(other.iter())
+            .filter(|((_, a), (_, b))| {
+                a.iter()
+                    .zip(b)
+                    .any(|(a, b)| a.abs_diff(*b) > max_channel_delta)
+            })
+            .map(|((role, _), _)| role)
+            .collect()
+    }
+
+    /// The scheme as a map from camelCase role names to hex colors, ex. for template engines.
+    pub fn to_hex_map(&self) -> BTreeMap<String, String> {
+        self.iter()
+            .map(|(role, argb)| (role.to_string(), hex_from_argb(argb)))
+            .collect()
+    }
+
+    /// Reads a scheme from a map of role names to hex colors, the inverse of
+    /// [`Scheme::to_hex_map`].
+    ///
+    /// # Arguments
+    ///
+    /// * `map`: Role names in any spelling [`Role::from_str`] accepts, and hex colors. Entries
+    ///   that are not roles are ignored. A missing `surfaceTint` is taken from `primary`.
+    ///
+    /// # Returns
+    ///
+    /// * The scheme, or every missing role and invalid color.
+    pub fn from_hex_map<K: AsRef<str>, V: AsRef<str>>(
+        map: impl IntoIterator<Item = (K, V)>,
+    ) -> Result<Scheme, SchemeError> {
+        let mut values: BTreeMap<usize, String> = BTreeMap::new();
+        for (key, value) in map {
+            if let Ok(role) = key.as_ref().parse::<Role>() {
+                values.insert(role as usize, value.as_ref().to_string());
+            }
+        }
+        let mut scheme = Scheme::default();
+        let mut problems = Vec::new();
+        for role in Role::iterator() {
+            match values.remove(&(*role as usize)) {
+                // Maps from before the role existed.
+                None if *role == SurfaceTint => scheme.surface_tint = scheme.primary,
+                None => problems.push(SchemeProblem::Missing(*role)),
+                Some(value) => match try_argb_from_hex(&value) {
+                    Ok(argb) => scheme[role] = argb,
+                    Err(error) => problems.push(SchemeProblem::Invalid {
+                        role: *role,
+                        value,
+                        error,
+                    }),
+                },
+            }
+        }
+        if problems.is_empty() {
+            Ok(scheme)
+        } else {
+            Err(SchemeError { problems })
+        }
+    }
+
+    pub fn light_from_core_palette(core: &CorePalette) -> Scheme {
         Scheme {
             primary: core.a1.tone(40),
             on_primary: core.a1.tone(100),
@@ -155,10 +674,11 @@ impl Scheme {
             inverse_surface: core.n1.tone(20),
             inverse_on_surface: core.n1.tone(95),
             inverse_primary: core.a1.tone(80),
+            surface_tint: core.a1.tone(40),
         }
     }
 
-    pub fn dark_from_core_palette(core: &mut CorePalette) -> Scheme {
+    pub fn dark_from_core_palette(core: &CorePalette) -> Scheme {
         Scheme {
             primary: core.a1.tone(80),
             on_primary: core.a1.tone(20),
@@ -189,15 +709,752 @@ impl Scheme {
             inverse_surface: core.n1.tone(90),
             inverse_on_surface: core.n1.tone(20),
             inverse_primary: core.a1.tone(40),
+            surface_tint: core.a1.tone(80),
+        }
+    }
+
+    /// The scheme of the opposite brightness, ex. the dark scheme of a light scheme, without the
+    /// palettes it was generated from.
+    ///
+    /// This is an approximation of regenerating the scheme from its [`CorePalette`]: each
+    /// palette's hue and chroma are estimated from its most colorful role, and the scheme is
+    /// regenerated with the standard tones, ex. primary 40 ↔ 80, containers 90 ↔ 30, and
+    /// surfaces 99 ↔ 10. Overridden colors and contrast adjustments are not kept. Colors are
+    /// usually off by at most one in a channel due to rounding, but may be less colorful when every
+    /// role of a palette was limited by the sRGB gamut, ex. the error roles of a dark scheme.
+    ///
+    /// # Returns
+    ///
+    /// * The [`Scheme::dark_from_core_palette`] scheme when the background is light, otherwise
+    ///   the [`Scheme::light_from_core_palette`] scheme.
+    pub fn inverted(&self) -> Scheme {
+        let core = CorePalette {
+            a1: self.estimate_palette(&[
+                Primary,
+                PrimaryContainer,
+                OnPrimaryContainer,
+                InversePrimary,
+            ]),
+            a2: self.estimate_palette(&[Secondary, SecondaryContainer, OnSecondaryContainer]),
+            a3: self.estimate_palette(&[Tertiary, TertiaryContainer, OnTertiaryContainer]),
+            error: self.estimate_palette(&[Error, ErrorContainer, OnErrorContainer]),
+            n1: self.estimate_palette(&[
+                Background,
+                OnBackground,
+                InverseSurface,
+                InverseOnSurface,
+            ]),
+            n2: self.estimate_palette(&[SurfaceVariant, OnSurfaceVariant, Outline, OutlineVariant]),
+        };
+        if lstar_from_argb(self.background) > 50.0 {
+            Scheme::dark_from_core_palette(&core)
+        } else {
+            Scheme::light_from_core_palette(&core)
+        }
+    }
+
+    /// The palette of the most colorful of `roles`, whose hue and chroma are the least distorted
+    /// by rounding.
+    fn estimate_palette(&self, roles: &[Role]) -> TonalPalette {
+        let hct = roles
+            .iter()
+            .map(|role| Hct::from_int(self[role]))
+            .max_by(|a, b| a.chroma().total_cmp(&b.chroma()))
+            .expect("roles is not empty");
+        TonalPalette::from_hue_and_chroma(hct.hue(), hct.chroma())
+    }
+
+    /// A dark scheme with a pure black background and surface, for AMOLED screens.
+    ///
+    /// Tones that differ from [`Scheme::dark_from_core_palette`]:
+    ///
+    /// | Role | Standard | AMOLED |
+    /// | --- | --- | --- |
+    /// | `background`, `surface` | neutral 10 | neutral 0 |
+    /// | `on_background`, `on_surface` | neutral 90 | neutral 95 |
+    /// | `surface_variant` | neutral variant 30 | neutral variant 20 |
+    /// | `on_surface_variant` | neutral variant 80 | neutral variant 85 |
+    ///
+    /// Accent containers keep their tones, so they stand out from the black surface as elevation.
+    pub fn dark_from_core_palette_amoled(core: &CorePalette) -> Scheme {
+        Scheme {
+            background: core.n1.tone(0),
+            on_background: core.n1.tone(95),
+            surface: core.n1.tone(0),
+            on_surface: core.n1.tone(95),
+            surface_variant: core.n2.tone(20),
+            on_surface_variant: core.n2.tone(85),
+            ..Scheme::dark_from_core_palette(core)
+        }
+    }
+
+    /// Like [`Scheme::light_from_core_palette`], at a contrast level.
+    ///
+    /// # Arguments
+    ///
+    /// * `core`: Palettes of the scheme.
+    /// * `contrast`: Contrast level from -1 (reduced) through 0 (standard) to 1 (high). Values
+    ///   outside are clamped.
+    ///
+    /// # Returns
+    ///
+    /// * The light scheme, with text, icons, and outlines moved away from the roles they sit on
+    ///   for positive levels and toward them for negative levels.
+    pub fn light_from_core_palette_with_contrast(core: &CorePalette, contrast: f64) -> Scheme {
+        let mut scheme = Scheme::light_from_core_palette(core);
+        scheme.apply_contrast(core, contrast);
+        scheme
+    }
+
+    /// Like [`Scheme::dark_from_core_palette`], at a contrast level. See
+    /// [`Scheme::light_from_core_palette_with_contrast`].
+    pub fn dark_from_core_palette_with_contrast(core: &CorePalette, contrast: f64) -> Scheme {
+        let mut scheme = Scheme::dark_from_core_palette(core);
+        scheme.apply_contrast(core, contrast);
+        scheme
+    }
+
+    /// Like [`Scheme::dark_from_core_palette_amoled`], at a contrast level. See
+    /// [`Scheme::light_from_core_palette_with_contrast`].
+    pub fn dark_from_core_palette_amoled_with_contrast(
+        core: &CorePalette,
+        contrast: f64,
+    ) -> Scheme {
+        let mut scheme = Scheme::dark_from_core_palette_amoled(core);
+        scheme.apply_contrast(core, contrast);
+        scheme
+    }
+
+    /// The color of a surface raised to an elevation level, tinted with `surface_tint`.
+    ///
+    /// # Arguments
+    ///
+    /// * `level`: Elevation level from 0 (flat) to 5. Higher levels are treated as 5.
+    ///
+    /// # Returns
+    ///
+    /// * For dark schemes, `surface_tint` composited over `surface` at 0%, 5%, 8%, 11%, 12%, or
+    ///   14% opacity. Light schemes show elevation with shadows, so `surface` is returned as is.
+    pub fn surface_at_elevation(&self, level: u8) -> [u8; 4] {
+        if lstar_from_argb(self.surface) > 50.0 {
+            return self.surface;
+        }
+        let opacity = ELEVATION_OPACITIES[(level as usize).min(5)];
+        let alpha = (opacity * 255.0).round() as u8;
+        composite_gamma(with_alpha(self.surface_tint, alpha), self.surface)
+    }
+
+    /// [`Scheme::surface_at_elevation`] at levels 1 through 5.
+    pub fn elevated_surfaces(&self) -> [[u8; 4]; 5] {
+        [1, 2, 3, 4, 5].map(|level| self.surface_at_elevation(level))
+    }
+
+    /// Copies the scheme with some roles replaced, ex. to force a brand color.
+    ///
+    /// # Arguments
+    ///
+    /// * `overrides`: Roles and their new colors. Later entries win.
+    ///
+    /// # Returns
+    ///
+    /// * The scheme with the roles replaced and every other role untouched.
+    pub fn with_overrides(&self, overrides: &[(Role, [u8; 4])]) -> Scheme {
+        let mut scheme = self.clone();
+        for (role, argb) in overrides {
+            scheme[role] = *argb;
         }
+        scheme
+    }
+
+    /// Like [`Scheme::with_overrides`], but also moves the tone of each replaced role's
+    /// [`Role::on_role`] until it contrasts with the new color by at least 4.5:1.
+    ///
+    /// # Arguments
+    ///
+    /// * `overrides`: Roles and their new colors. Later entries win. On-roles listed here keep the
+    ///   given color.
+    ///
+    /// # Returns
+    ///
+    /// * The scheme with the roles replaced, and their on-roles readable on them. An on-role
+    ///   keeps its hue and chroma, and its tone when it is already readable.
+    pub fn with_overrides_and_on_colors(&self, overrides: &[(Role, [u8; 4])]) -> Scheme {
+        let mut scheme = self.with_overrides(overrides);
+        for (role, argb) in overrides {
+            let Some(on_role) = role.on_role() else {
+                continue;
+            };
+            if overrides
+                .iter()
+                .any(|(overridden, _)| *overridden == on_role)
+            {
+                continue;
+            }
+            let background_tone = lstar_from_argb(*argb);
+            let on_color = Hct::from_int(scheme[on_role]);
+            if ratio_of_tones(on_color.tone(), background_tone) >= MIN_ON_COLOR_RATIO {
+                continue;
+            }
+            let lighter_tone = lighter(background_tone, MIN_ON_COLOR_RATIO);
+            let darker_tone = darker(background_tone, MIN_ON_COLOR_RATIO);
+            let tone = match (lighter_tone, darker_tone) {
+                (Some(lighter), Some(darker)) => {
+                    if on_color.tone() >= background_tone {
+                        lighter
+                    } else {
+                        darker
+                    }
+                }
+                (Some(tone), None) | (None, Some(tone)) => tone,
+                (None, None) => {
+                    let lighter = lighter_unsafe(background_tone, MIN_ON_COLOR_RATIO);
+                    let darker = darker_unsafe(background_tone, MIN_ON_COLOR_RATIO);
+                    if ratio_of_tones(lighter, background_tone)
+                        >= ratio_of_tones(darker, background_tone)
+                    {
+                        lighter
+                    } else {
+                        darker
+                    }
+                }
+            };
+            scheme[on_role] = Hct::from(on_color.hue(), on_color.chroma(), tone).to_int();
+        }
+        scheme
+    }
+
+    fn apply_contrast(&mut self, core: &CorePalette, contrast: f64) {
+        let contrast = clamp_double(-1.0, 1.0, contrast);
+        if contrast == 0.0 {
+            return;
+        }
+        for (role, background, low, high) in &CONTRAST_ROLES {
+            let tone = lstar_from_argb(self[role]);
+            let background_tone = lstar_from_argb(self[background]);
+            let ratio = ratio_of_tones(tone, background_tone);
+            let target = if contrast > 0.0 {
+                lerp(ratio, high.max(ratio), contrast)
+            } else {
+                lerp(ratio, low.min(ratio), -contrast)
+            };
+            let tone = if tone < background_tone {
+                darker_unsafe(background_tone, target)
+            } else {
+                lighter_unsafe(background_tone, target)
+            };
+            self[role] = role.palette(core).tone(tone);
+        }
+        self.surface_tint = self.primary;
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+
+    fn ratio(scheme: &Scheme, role: &Role, background: &Role) -> f64 {
+        ratio_of_tones(
+            lstar_from_argb(scheme[role]),
+            lstar_from_argb(scheme[background]),
+        )
+    }
+
+    #[test]
+    fn contrast_levels() {
+        let core = CorePalette::of([0xff, 0x42, 0x85, 0xf4]);
+        let standard = Scheme::light_from_core_palette(&core);
+        let same = Scheme::light_from_core_palette_with_contrast(&core, 0.0);
+        let high = Scheme::light_from_core_palette_with_contrast(&core, 1.0);
+        let reduced = Scheme::light_from_core_palette_with_contrast(&core, -1.0);
+        assert_eq!(same, standard);
+        for (role, background, low, high_ratio) in &CONTRAST_ROLES {
+            let standard_ratio = ratio(&standard, role, background);
+            assert!(ratio(&high, role, background) >= standard_ratio.min(*high_ratio) - 0.05);
+            assert!(ratio(&reduced, role, background) <= standard_ratio.max(*low) + 0.05);
+        }
+        assert!(ratio(&high, &OnSurfaceVariant, &SurfaceVariant) > 10.5);
+        assert!(ratio(&reduced, &Outline, &Surface) < 2.0);
+        assert_eq!(high.surface, standard.surface);
+    }
+
+    #[test]
+    fn dark_contrast() {
+        let core = CorePalette::of([0xff, 0x42, 0x85, 0xf4]);
+        let standard = Scheme::dark_from_core_palette(&core);
+        let high = Scheme::dark_from_core_palette_with_contrast(&core, 2.0);
+        assert!(lstar_from_argb(high.primary) > lstar_from_argb(standard.primary));
+        assert!(ratio(&high, &OnPrimary, &Primary) >= 10.5);
+    }
+
+    #[test]
+    fn amoled_is_black() {
+        let core = CorePalette::of([0xff, 0x42, 0x85, 0xf4]);
+        let standard = Scheme::dark_from_core_palette(&core);
+        let amoled = Scheme::dark_from_core_palette_amoled(&core);
+        assert_eq!(amoled.background, [0xff, 0x00, 0x00, 0x00]);
+        assert_eq!(amoled.surface, [0xff, 0x00, 0x00, 0x00]);
+        assert_eq!(amoled.primary_container, standard.primary_container);
+        assert!(
+            lstar_from_argb(amoled.surface_variant) < lstar_from_argb(standard.surface_variant)
+        );
+        assert!(lstar_from_argb(amoled.on_surface) > lstar_from_argb(standard.on_surface));
+    }
+
+    #[test]
+    fn amoled_contrast_report() {
+        for source in [
+            [0xff, 0x42, 0x85, 0xf4],
+            [0xff, 0xdb, 0x44, 0x37],
+            [0xff, 0x0f, 0x9d, 0x58],
+            [0xff, 0xf4, 0xb4, 0x00],
+        ] {
+            let core = CorePalette::of(source);
+            for contrast in [0.0, 0.5, 1.0] {
+                let scheme = Scheme::dark_from_core_palette_amoled_with_contrast(&core, contrast);
+                for (role, background, _, _) in &CONTRAST_ROLES {
+                    if !format!("{role:?}").starts_with("On") && !matches!(role, InverseOnSurface) {
+                        continue;
+                    }
+                    let ratio = ratio(&scheme, role, background);
+                    assert!(ratio >= 4.5, "{role:?} on {background:?}: {ratio:.2}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn iterates_roles_in_order() {
+        let core = CorePalette::of([0xff, 0x42, 0x85, 0xf4]);
+        let scheme = Scheme::light_from_core_palette(&core);
+        let pairs: Vec<(Role, [u8; 4])> = (&scheme).into_iter().collect();
+        assert_eq!(pairs.len(), Scheme::ROLE_COUNT);
+        assert_eq!(scheme.iter().len(), Scheme::ROLE_COUNT);
+        let roles: Vec<Role> = pairs.iter().map(|(role, _)| *role).collect();
+        let expected: Vec<Role> = Role::iterator().copied().collect();
+        assert_eq!(roles, expected);
+        let unique: std::collections::HashSet<Role> = roles.iter().copied().collect();
+        assert_eq!(unique.len(), roles.len());
+        assert_eq!(pairs[0], (Primary, scheme.primary));
+        assert_eq!(pairs[28], (InversePrimary, scheme.inverse_primary));
+        for (role, argb) in &scheme {
+            assert_eq!(scheme[role], argb);
+        }
+        assert!(scheme.values().eq(pairs.iter().map(|(_, argb)| *argb)));
+    }
+
     #[test]
-    fn placeholder_test() {
-        let sum = 2 + 2;
-        assert_eq!(sum, 4);
+    fn role_names_round_trip() {
+        for role in Role::iterator() {
+            let camel = role.to_string();
+            assert_eq!(camel, role.as_camel());
+            for name in [
+                camel.clone(),
+                role.as_kebab().to_string(),
+                role.as_snake().to_string(),
+                camel.to_uppercase(),
+                role.as_kebab().to_uppercase(),
+                format!(" {} ", role.as_snake()),
+            ] {
+                assert_eq!(name.parse::<Role>(), Ok(*role), "{name}");
+            }
+            let debug = format!("{role:?}");
+            assert_eq!(camel[..1], debug[..1].to_lowercase());
+            assert_eq!(camel[1..], debug[1..]);
+            assert_eq!(role.as_kebab().replace('-', "_"), role.as_snake());
+        }
+        assert_eq!(OnPrimaryContainer.as_kebab(), "on-primary-container");
+        assert_eq!(OnPrimaryContainer.as_snake(), "on_primary_container");
+        assert_eq!(InverseOnSurface.to_string(), "inverseOnSurface");
+    }
+
+    #[test]
+    fn role_parse_errors() {
+        for name in ["", "primaryish", "on primary", "surfaceContainer"] {
+            let err = name.parse::<Role>().unwrap_err();
+            assert_eq!(err, ParseRoleError(name.to_string()));
+            assert!(err.to_string().contains(&format!("{name:?}")));
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_hex_strings() {
+        let core = CorePalette::of([0xff, 0x42, 0x85, 0xf4]);
+        let mut scheme = Scheme::light_from_core_palette(&core);
+        let json = serde_json::to_value(&scheme).unwrap();
+        assert_eq!(json["primary"], "#005ac1");
+        assert_eq!(json["onPrimaryContainer"], "#001a41");
+        assert_eq!(json.as_object().unwrap().len(), Scheme::ROLE_COUNT);
+
+        scheme.scrim = [0x80, 0x00, 0x00, 0x00];
+        let json = serde_json::to_string(&scheme).unwrap();
+        assert!(json.contains("\"scrim\":\"#00000080\""));
+        let back: Scheme = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, scheme);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_numeric_helper() {
+        #[derive(serde::Serialize, serde::Deserialize)]
+        struct Config {
+            #[serde(with = "crate::utils::color_format::numeric")]
+            scheme: Scheme,
+        }
+        let core = CorePalette::of([0xff, 0x42, 0x85, 0xf4]);
+        let scheme = Scheme::dark_from_core_palette(&core);
+        let json = serde_json::to_value(Config { scheme }).unwrap();
+        assert_eq!(json["scheme"]["shadow"], 0xff000000u32);
+        let back: Config = serde_json::from_value(json).unwrap();
+        let hex = serde_json::to_string(&back.scheme).unwrap();
+        assert!(hex.starts_with("{\"primary\":\"#adc6ff\""));
+        let again: Scheme = serde_json::from_str(&hex).unwrap();
+        assert!(again.values().eq(back.scheme.values()));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    #[ignore = "needs scheme_4285f4.ts.json from utils/testdata/generate_ts_fixtures.mjs"]
+    fn serde_matches_ts_fixture() {
+        let fixture = crate::utils::color_format::ts_fixture("scheme_4285f4.ts.json");
+        let core = CorePalette::of([0xff, 0x42, 0x85, 0xf4]);
+        let light: Scheme = serde_json::from_value(fixture["light"].clone()).unwrap();
+        assert_eq!(light, Scheme::light_from_core_palette(&core));
+        let dark: Scheme = serde_json::from_value(fixture["dark"].clone()).unwrap();
+        assert_eq!(dark, Scheme::dark_from_core_palette(&core));
+    }
+
+    // The colors the upstream TypeScript scheme tests expect for #fa2bec, in the numeric JSON
+    // that TypeScript's Scheme.toJSON writes. Upstream's dark onErrorContainer is error tone
+    // 80, where this crate, like the Java implementation, uses tone 90.
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_reads_upstream_high_chroma_schemes() {
+        let core = CorePalette::of([0xff, 0xfa, 0x2b, 0xec]);
+        let light: Scheme = serde_json::from_value(serde_json::json!({
+            "primary": 0xffab00a2u32, "onPrimary": 0xffffffffu32,
+            "primaryContainer": 0xffffd7f3u32, "onPrimaryContainer": 0xff390035u32,
+            "secondary": 0xff6e5868u32, "onSecondary": 0xffffffffu32,
+            "secondaryContainer": 0xfff8daeeu32, "onSecondaryContainer": 0xff271624u32,
+            "tertiary": 0xff815343u32, "onTertiary": 0xffffffffu32,
+            "tertiaryContainer": 0xffffdbd0u32, "onTertiaryContainer": 0xff321207u32,
+            "error": 0xffba1a1au32, "onError": 0xffffffffu32,
+            "errorContainer": 0xffffdad6u32, "onErrorContainer": 0xff410002u32,
+            "background": 0xfffffbffu32, "onBackground": 0xff1f1a1du32,
+            "surface": 0xfffffbffu32, "onSurface": 0xff1f1a1du32,
+            "surfaceVariant": 0xffeedee7u32, "onSurfaceVariant": 0xff4e444bu32,
+            "outline": 0xff80747bu32, "outlineVariant": 0xffd2c2cbu32,
+            "shadow": 0xff000000u32, "scrim": 0xff000000u32,
+            "inverseSurface": 0xff342f32u32, "inverseOnSurface": 0xfff8eef2u32,
+            "inversePrimary": 0xffffabeeu32,
+        }))
+        .unwrap();
+        assert_eq!(light, Scheme::light_from_core_palette(&core));
+
+        let dark: Scheme = serde_json::from_value(serde_json::json!({
+            "primary": 0xffffabeeu32, "onPrimary": 0xff5c0057u32,
+            "primaryContainer": 0xff83007bu32, "onPrimaryContainer": 0xffffd7f3u32,
+            "secondary": 0xffdbbed1u32, "onSecondary": 0xff3e2a39u32,
+            "secondaryContainer": 0xff554050u32, "onSecondaryContainer": 0xfff8daeeu32,
+            "tertiary": 0xfff5b9a5u32, "onTertiary": 0xff4c2619u32,
+            "tertiaryContainer": 0xff663c2du32, "onTertiaryContainer": 0xffffdbd0u32,
+            "error": 0xffffb4abu32, "onError": 0xff690005u32,
+            "errorContainer": 0xff93000au32, "onErrorContainer": 0xffffb4abu32,
+            "background": 0xff1f1a1du32, "onBackground": 0xffeae0e4u32,
+            "surface": 0xff1f1a1du32, "onSurface": 0xffeae0e4u32,
+            "surfaceVariant": 0xff4e444bu32, "onSurfaceVariant": 0xffd2c2cbu32,
+            "outline": 0xff9a8d95u32, "outlineVariant": 0xff4e444bu32,
+            "shadow": 0xff000000u32, "scrim": 0xff000000u32,
+            "inverseSurface": 0xffeae0e4u32, "inverseOnSurface": 0xff342f32u32,
+            "inversePrimary": 0xffab00a2u32,
+        }))
+        .unwrap();
+        let expected = Scheme::dark_from_core_palette(&core);
+        assert_eq!(dark.differing_roles(&expected, 0), [OnErrorContainer]);
+        assert_eq!(expected.on_error_container, core.error.tone(90));
+    }
+
+    fn hex_map() -> (Scheme, BTreeMap<String, String>) {
+        let core = CorePalette::of([0xff, 0x42, 0x85, 0xf4]);
+        let scheme = Scheme::light_from_core_palette(&core);
+        let map = scheme.to_hex_map();
+        (scheme, map)
+    }
+
+    #[test]
+    fn hex_map_round_trip() {
+        let (scheme, map) = hex_map();
+        assert_eq!(map.len(), Scheme::ROLE_COUNT);
+        assert_eq!(map["onPrimaryContainer"], "#001a41");
+        assert_eq!(map.keys().next().unwrap(), "background");
+        let back = Scheme::from_hex_map(&map).unwrap();
+        assert_eq!(back, scheme);
+
+        let mut kebab: std::collections::HashMap<String, String> = map
+            .iter()
+            .map(|(key, value)| {
+                (
+                    key.parse::<Role>().unwrap().as_kebab().to_string(),
+                    value.clone(),
+                )
+            })
+            .collect();
+        kebab.insert(String::from("not-a-role"), String::from("#nothex"));
+        assert!(Scheme::from_hex_map(kebab)
+            .unwrap()
+            .iter()
+            .eq(scheme.iter()));
+    }
+
+    #[test]
+    fn hex_map_missing_role() {
+        let (_, mut map) = hex_map();
+        map.remove("outlineVariant");
+        let err = Scheme::from_hex_map(&map).unwrap_err();
+        assert_eq!(err.problems, [SchemeProblem::Missing(OutlineVariant)]);
+        assert_eq!(err.to_string(), "missing role outlineVariant");
+    }
+
+    #[test]
+    fn hex_map_lists_every_problem() {
+        let (_, mut map) = hex_map();
+        map.insert(String::from("primary"), String::from("#12345g"));
+        map.remove("scrim");
+        let err = Scheme::from_hex_map(&map).unwrap_err();
+        assert_eq!(err.problems.len(), 2);
+        assert!(matches!(
+            &err.problems[0],
+            SchemeProblem::Invalid { role: Primary, value, .. } if value == "#12345g"
+        ));
+        assert_eq!(err.problems[1], SchemeProblem::Missing(Scrim));
+        let message = err.to_string();
+        assert!(message.starts_with("invalid color \"#12345g\" for role primary: "));
+        assert!(message.ends_with("; missing role scrim"));
+    }
+
+    #[test]
+    fn index_by_value_and_reference() {
+        let core = CorePalette::of([0xff, 0x42, 0x85, 0xf4]);
+        let mut scheme = Scheme::light_from_core_palette(&core);
+        assert_eq!(scheme[Primary], scheme.primary);
+        assert_eq!(scheme[&Primary], scheme.primary);
+
+        scheme[Primary] = [0xff, 0x12, 0x34, 0x56];
+        assert_eq!(scheme.primary, [0xff, 0x12, 0x34, 0x56]);
+        scheme[&OnPrimary] = [0xff, 0x65, 0x43, 0x21];
+        assert_eq!(scheme[OnPrimary], [0xff, 0x65, 0x43, 0x21]);
+
+        for role in Role::iterator() {
+            scheme[*role] = [0xff, 0, 0, 0];
+        }
+        assert!(scheme.values().all(|argb| argb == [0xff, 0, 0, 0]));
+    }
+
+    #[test]
+    fn schemes_from_shared_palette() {
+        use std::sync::Arc;
+
+        let core = Arc::new(CorePalette::of([0xff, 0x42, 0x85, 0xf4]));
+        let threads: Vec<_> = (0..8)
+            .map(|i| {
+                let core = Arc::clone(&core);
+                std::thread::spawn(move || {
+                    if i % 2 == 0 {
+                        Scheme::light_from_core_palette(&core)
+                    } else {
+                        Scheme::dark_from_core_palette(&core)
+                    }
+                })
+            })
+            .collect();
+        let fresh = CorePalette::of([0xff, 0x42, 0x85, 0xf4]);
+        let light = Scheme::light_from_core_palette(&fresh);
+        let dark = Scheme::dark_from_core_palette(&fresh);
+        for (i, thread) in threads.into_iter().enumerate() {
+            let scheme = thread.join().unwrap();
+            let expected = if i % 2 == 0 { &light } else { &dark };
+            assert_eq!(&scheme, expected);
+        }
+    }
+
+    #[test]
+    #[allow(clippy::unnecessary_mut_passed)]
+    fn constructors_accept_mut_core_palette() {
+        // Callers written for the old `&mut CorePalette` signatures keep compiling, since `&mut`
+        // coerces to `&`; that is why no deprecated `&mut` shims exist.
+        let mut core = CorePalette::of([0xff, 0x42, 0x85, 0xf4]);
+        let light = Scheme::light_from_core_palette(&core);
+        let dark = Scheme::dark_from_core_palette(&core);
+        assert_eq!(Scheme::light_from_core_palette(&mut core), light);
+        assert_eq!(Scheme::dark_from_core_palette(&mut core), dark);
+        assert_eq!(
+            Scheme::dark_from_core_palette_amoled(&mut core),
+            Scheme::dark_from_core_palette_amoled(&core)
+        );
+        assert_eq!(
+            Scheme::light_from_core_palette_with_contrast(&mut core, 0.5),
+            Scheme::light_from_core_palette_with_contrast(&core, 0.5)
+        );
+        assert_eq!(
+            Scheme::dark_from_core_palette_with_contrast(&mut core, 0.5),
+            Scheme::dark_from_core_palette_with_contrast(&core, 0.5)
+        );
+    }
+
+    #[test]
+    fn overrides_keep_on_colors_by_default() {
+        let core = CorePalette::of([0xff, 0x42, 0x85, 0xf4]);
+        let scheme = Scheme::light_from_core_palette(&core);
+        let brand = [0xff, 0xff, 0xd5, 0x00];
+        let overridden = scheme.with_overrides(&[(Primary, brand), (Scrim, brand)]);
+        assert_eq!(overridden.primary, brand);
+        assert_eq!(overridden.scrim, brand);
+        assert_eq!(overridden.on_primary, scheme.on_primary);
+        assert!(overridden
+            .iter()
+            .zip(scheme.iter())
+            .all(|((role, new), (_, old))| role == Primary || role == Scrim || new == old));
+        assert!(
+            ratio_of_tones(
+                lstar_from_argb(overridden.on_primary),
+                lstar_from_argb(brand)
+            ) < 4.5
+        );
+    }
+
+    #[test]
+    fn overrides_recompute_on_colors() {
+        let core = CorePalette::of([0xff, 0x42, 0x85, 0xf4]);
+        let scheme = Scheme::light_from_core_palette(&core);
+        for brand in [
+            [0xff, 0xff, 0xd5, 0x00],
+            [0xff, 0x77, 0x77, 0x77],
+            [0xff, 0x00, 0x20, 0x60],
+        ] {
+            let overridden = scheme.with_overrides_and_on_colors(&[(Primary, brand)]);
+            assert_eq!(overridden.primary, brand);
+            let ratio = ratio_of_tones(
+                lstar_from_argb(overridden.on_primary),
+                lstar_from_argb(brand),
+            );
+            assert!(ratio >= 4.5, "{ratio} for {brand:?}");
+            assert_eq!(overridden.on_secondary, scheme.on_secondary);
+        }
+
+        // White is already readable on a dark navy, so it is kept.
+        let navy = [0xff, 0x00, 0x20, 0x60];
+        let overridden = scheme.with_overrides_and_on_colors(&[(Primary, navy)]);
+        assert_eq!(overridden.on_primary, scheme.on_primary);
+
+        // An explicit on-color wins over the recomputed one.
+        let gray = [0xff, 0x80, 0x80, 0x80];
+        let overridden = scheme.with_overrides_and_on_colors(&[(Primary, navy), (OnPrimary, gray)]);
+        assert_eq!(overridden.on_primary, gray);
+    }
+
+    #[test]
+    fn inverted_matches_generated() {
+        for source in [
+            [0xff, 0x42, 0x85, 0xf4],
+            [0xff, 0x67, 0x50, 0xa4],
+            [0xff, 0xb3, 0x26, 0x1e],
+        ] {
+            let core = CorePalette::of(source);
+            let light = Scheme::light_from_core_palette(&core);
+            let dark = Scheme::dark_from_core_palette(&core);
+            assert_eq!(light.inverted().differing_roles(&dark, 1), []);
+            assert!(lstar_from_argb(dark.inverted().background) > 90.0);
+        }
+    }
+
+    #[test]
+    fn approx_eq_tolerance() {
+        let core = CorePalette::of([0xff, 0x42, 0x85, 0xf4]);
+        let scheme = Scheme::light_from_core_palette(&core);
+        let mut nudged = scheme.clone();
+        nudged.surface[3] ^= 1;
+        nudged.on_error[1] = nudged.on_error[1].wrapping_sub(1);
+        assert_ne!(scheme, nudged);
+        assert!(scheme.approx_eq(&nudged, 1));
+        assert!(!scheme.approx_eq(&nudged, 0));
+        assert_eq!(scheme.differing_roles(&nudged, 0), [OnError, Surface]);
+        assert!(scheme.differing_roles(&nudged, 1).is_empty());
+        assert!(scheme.approx_eq(&scheme, 0));
+    }
+
+    #[test]
+    fn array_round_trip() {
+        assert!(Scheme::ROLE_ORDER.iter().eq(Role::iterator()));
+        for (index, role) in Scheme::ROLE_ORDER.iter().enumerate() {
+            assert_eq!(*role as usize, index);
+        }
+
+        let core = CorePalette::of([0xff, 0x42, 0x85, 0xf4]);
+        let scheme = Scheme::dark_from_core_palette(&core);
+        let array = scheme.to_array();
+        assert_eq!(array[0], scheme.primary);
+        assert_eq!(array[Scheme::ROLE_COUNT - 1], scheme.surface_tint);
+        assert!(array.iter().copied().eq(scheme.values()));
+        assert_eq!(Scheme::from_array(array), scheme);
+    }
+
+    #[test]
+    fn surface_tint_is_primary() {
+        let core = CorePalette::of([0xff, 0x42, 0x85, 0xf4]);
+        let light = Scheme::light_from_core_palette(&core);
+        let dark = Scheme::dark_from_core_palette(&core);
+        assert_eq!(light.surface_tint, light.primary);
+        assert_eq!(dark.surface_tint, dark.primary);
+        assert_eq!(light[SurfaceTint], core.a1.tone(40));
+        assert_eq!(dark[SurfaceTint], core.a1.tone(80));
+        for contrast in [-1.0, 1.0] {
+            let scheme = Scheme::light_from_core_palette_with_contrast(&core, contrast);
+            assert_eq!(scheme.surface_tint, scheme.primary);
+            let scheme = Scheme::dark_from_core_palette_amoled_with_contrast(&core, contrast);
+            assert_eq!(scheme.surface_tint, scheme.primary);
+        }
+        assert_eq!("surface-tint".parse::<Role>(), Ok(SurfaceTint));
+
+        let mut map = light.to_hex_map();
+        assert_eq!(map["surfaceTint"], map["primary"]);
+        map.remove("surfaceTint");
+        assert_eq!(Scheme::from_hex_map(&map).unwrap(), light);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_without_surface_tint() {
+        let core = CorePalette::of([0xff, 0x42, 0x85, 0xf4]);
+        let dark = Scheme::dark_from_core_palette(&core);
+        let mut json = serde_json::to_value(&dark).unwrap();
+        assert_eq!(json["surfaceTint"], json["primary"]);
+        json.as_object_mut().unwrap().remove("surfaceTint");
+        let back: Scheme = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(back, dark);
+        // A tint that is present is kept, even transparent black.
+        json["surfaceTint"] = "#00000000".into();
+        let back: Scheme = serde_json::from_value(json).unwrap();
+        assert_eq!(back.surface_tint, [0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn surface_elevation() {
+        let core = CorePalette::of([0xff, 0x42, 0x85, 0xf4]);
+        let dark = Scheme::dark_from_core_palette(&core);
+        assert_eq!(dark.surface, [0xff, 0x1b, 0x1b, 0x1f]);
+        assert_eq!(dark.surface_tint, [0xff, 0xad, 0xc6, 0xff]);
+        // 11% is an alpha of 28, so each channel is surface + (tint - surface) * 28 / 255.
+        assert_eq!(dark.surface_at_elevation(3), [0xff, 0x2b, 0x2e, 0x38]);
+        assert_eq!(dark.surface_at_elevation(0), dark.surface);
+        assert_eq!(dark.surface_at_elevation(9), dark.surface_at_elevation(5));
+
+        let surfaces = dark.elevated_surfaces();
+        assert_eq!(surfaces[2], dark.surface_at_elevation(3));
+        assert!(surfaces
+            .windows(2)
+            .all(|pair| lstar_from_argb(pair[0]) < lstar_from_argb(pair[1])));
+
+        let light = Scheme::light_from_core_palette(&core);
+        assert_eq!(light.elevated_surfaces(), [light.surface; 5]);
     }
 }
@@ -0,0 +1,179 @@
+use crate::hct::Hct;
+use crate::palettes::{core::CorePalette, tonal::TonalPalette};
+use crate::utils::math::sanitize_degrees_double;
+
+/// The style of a theme: how far the palettes stray from the source color's hue, and how
+/// colorful they are.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Variant {
+    /// All palettes are grayscale.
+    Monochrome,
+    /// Close to grayscale, with a hint of the source hue.
+    Neutral,
+    /// The palettes of [`CorePalette::of`]: a colorful primary, and calm secondary and tertiary
+    /// palettes.
+    #[default]
+    TonalSpot,
+    /// Maximum chroma for the primary, with secondary and tertiary hues rotated away from it.
+    Vibrant,
+    /// Hues rotated far from the source color, for a playful theme.
+    Expressive,
+    /// The palettes of [`CorePalette::content_of`]: the source color's own chroma is kept,
+    /// so the theme stays faithful to images.
+    Content,
+    /// The palettes of [`CorePalette::fidelity_of`]: as colorful as the source color, and no more.
+    Fidelity,
+}
+
+const VIBRANT_HUES: [f64; 9] = [0.0, 41.0, 61.0, 101.0, 131.0, 181.0, 251.0, 301.0, 360.0];
+const VIBRANT_SECONDARY_ROTATIONS: [f64; 9] =
+    [18.0, 15.0, 10.0, 12.0, 15.0, 18.0, 15.0, 12.0, 12.0];
+const VIBRANT_TERTIARY_ROTATIONS: [f64; 9] = [35.0, 30.0, 20.0, 25.0, 30.0, 35.0, 30.0, 25.0, 25.0];
+
+const EXPRESSIVE_HUES: [f64; 9] = [0.0, 21.0, 51.0, 121.0, 151.0, 191.0, 271.0, 321.0, 360.0];
+const EXPRESSIVE_SECONDARY_ROTATIONS: [f64; 9] =
+    [45.0, 95.0, 45.0, 20.0, 45.0, 90.0, 45.0, 45.0, 45.0];
+const EXPRESSIVE_TERTIARY_ROTATIONS: [f64; 9] =
+    [120.0, 120.0, 20.0, 45.0, 20.0, 15.0, 20.0, 120.0, 120.0];
+
+/// Rotates `source_hue` by the rotation of the hue range it falls in.
+///
+/// # Arguments
+///
+/// * `source_hue`: Hue to rotate.
+/// * `hues`: Ascending hue boundaries from 0 to 360.
+/// * `rotations`: Degrees to rotate by for the range starting at the same index in `hues`.
+///
+/// # Returns
+///
+/// * The rotated hue, or `source_hue` itself if it lies on a boundary.
+fn rotated_hue(source_hue: f64, hues: &[f64; 9], rotations: &[f64; 9]) -> f64 {
+    for i in 0..hues.len() - 1 {
+        if hues[i] < source_hue && source_hue < hues[i + 1] {
+            return sanitize_degrees_double(source_hue + rotations[i]);
+        }
+    }
+    source_hue
+}
+
+impl Variant {
+    /// Creates the palettes of this variant.
+    ///
+    /// # Arguments
+    ///
+    /// * `source`: The source color of the theme.
+    ///
+    /// # Returns
+    ///
+    /// * The palettes of the theme. The error palette is the same for every variant.
+    pub fn core_palette(self, source: [u8; 4]) -> CorePalette {
+        let hct = Hct::from_int(source);
+        let hue = hct.hue();
+        let palette = |hue: f64, chroma: f64| TonalPalette::from_hue_and_chroma(hue, chroma);
+        let error = palette(25.0, 84.0);
+        match self {
+            Variant::TonalSpot => CorePalette::of(source),
+            Variant::Content => CorePalette::content_of(source),
+            Variant::Fidelity => CorePalette::fidelity_of(source),
+            Variant::Monochrome => CorePalette {
+                a1: palette(hue, 0.0),
+                a2: palette(hue, 0.0),
+                a3: palette(hue, 0.0),
+                n1: palette(hue, 0.0),
+                n2: palette(hue, 0.0),
+                error,
+            },
+            Variant::Neutral => CorePalette {
+                a1: palette(hue, 12.0),
+                a2: palette(hue, 8.0),
+                a3: palette(hue, 16.0),
+                n1: palette(hue, 2.0),
+                n2: palette(hue, 2.0),
+                error,
+            },
+            Variant::Vibrant => CorePalette {
+                a1: palette(hue, 200.0),
+                a2: palette(
+                    rotated_hue(hue, &VIBRANT_HUES, &VIBRANT_SECONDARY_ROTATIONS),
+                    24.0,
+                ),
+                a3: palette(
+                    rotated_hue(hue, &VIBRANT_HUES, &VIBRANT_TERTIARY_ROTATIONS),
+                    32.0,
+                ),
+                n1: palette(hue, 10.0),
+                n2: palette(hue, 12.0),
+                error,
+            },
+            Variant::Expressive => CorePalette {
+                a1: palette(sanitize_degrees_double(hue + 240.0), 40.0),
+                a2: palette(
+                    rotated_hue(hue, &EXPRESSIVE_HUES, &EXPRESSIVE_SECONDARY_ROTATIONS),
+                    24.0,
+                ),
+                a3: palette(
+                    rotated_hue(hue, &EXPRESSIVE_HUES, &EXPRESSIVE_TERTIARY_ROTATIONS),
+                    32.0,
+                ),
+                n1: palette(sanitize_degrees_double(hue + 15.0), 8.0),
+                n2: palette(sanitize_degrees_double(hue + 15.0), 12.0),
+                error,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::math::difference_degrees;
+    use assert_approx_eq::assert_approx_eq;
+
+    const BLUE: [u8; 4] = [0xff, 0x42, 0x85, 0xf4];
+
+    #[test]
+    fn rotated_hue_ranges() {
+        assert_approx_eq!(
+            rotated_hue(50.0, &VIBRANT_HUES, &VIBRANT_TERTIARY_ROTATIONS),
+            80.0
+        );
+        assert_approx_eq!(
+            rotated_hue(350.0, &VIBRANT_HUES, &VIBRANT_TERTIARY_ROTATIONS),
+            15.0
+        );
+        assert_approx_eq!(
+            rotated_hue(41.0, &VIBRANT_HUES, &VIBRANT_TERTIARY_ROTATIONS),
+            41.0
+        );
+    }
+
+    #[test]
+    fn monochrome_is_gray() {
+        let palette = Variant::Monochrome.core_palette(BLUE);
+        for tone in [10, 40, 90] {
+            for [_, r, g, b] in [palette.a1.tone(tone), palette.n2.tone(tone)] {
+                assert!(r == g && g == b);
+            }
+        }
+        assert!(Hct::from_int(palette.error.tone(40)).chroma() > 40.0);
+    }
+
+    #[test]
+    fn chroma_by_variant() {
+        // A muted red, whose hue has room in the sRGB gamut for far more chroma than it has.
+        let rose = [0xff, 0x9a, 0x6b, 0x6b];
+        let chroma = |variant: Variant| {
+            let palette = variant.core_palette(rose);
+            Hct::from_int(palette.a1.tone(50)).chroma()
+        };
+        assert!(chroma(Variant::Neutral) < chroma(Variant::TonalSpot));
+        assert!(chroma(Variant::TonalSpot) < chroma(Variant::Vibrant));
+    }
+
+    #[test]
+    fn expressive_rotates_primary() {
+        let source_hue = Hct::from_int(BLUE).hue();
+        let palette = Variant::Expressive.core_palette(BLUE);
+        assert_approx_eq!(difference_degrees(palette.a1.hue(), source_hue), 120.0);
+    }
+}
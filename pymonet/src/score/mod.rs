@@ -134,4 +134,45 @@ mod tests {
         assert_eq!(ranked[1], [0xff, 0x00, 0xff, 0x00]);
         assert_eq!(ranked[2], [0xff, 0x00, 0x00, 0xff]);
     }
+
+    // The remaining cases and their expected rankings are from the upstream score tests.
+    fn ranked(colors: &[(u32, u32)]) -> Vec<u32> {
+        let colors_to_population = colors
+            .iter()
+            .map(|&(argb, population)| (argb.to_be_bytes(), population))
+            .collect();
+        score(&colors_to_population)
+            .into_iter()
+            .map(u32::from_be_bytes)
+            .collect()
+    }
+
+    #[test]
+    fn prioritizes_chroma() {
+        let colors = [(0xff000000, 1), (0xffffffff, 1), (0xff0000ff, 1)];
+        assert_eq!(ranked(&colors), [0xff0000ff]);
+    }
+
+    #[test]
+    fn generates_google_blue_when_no_colors_available() {
+        assert_eq!(ranked(&[(0xff000000, 1)]), [0xff4285f4]);
+    }
+
+    #[test]
+    fn dedupes_nearby_hues() {
+        // H 180 C 42 T 50, and H 184 C 35 T 50.
+        let colors = [(0xff008772, 1), (0xff318477, 1)];
+        assert_eq!(ranked(&colors), [0xff008772]);
+    }
+
+    #[test]
+    fn generated_scenario() {
+        let colors = [
+            (0xffd33881, 14),
+            (0xff3205cc, 77),
+            (0xff0b48cf, 36),
+            (0xffa08f5d, 81),
+        ];
+        assert_eq!(ranked(&colors), [0xff3205cc, 0xffa08f5d, 0xffd33881]);
+    }
 }
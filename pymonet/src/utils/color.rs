@@ -1,8 +1,14 @@
 //! Collection of commonly used color calculations and transformations
 
 // rustimport:pyo3
-use crate::utils::math::matrix_multiply;
+use crate::hct::Hct;
+use crate::utils::math::{lerp, matrix_multiply};
+use crate::utils::string::{argb_from_css, hex_from_argb, ParseColorError};
+use lazy_static::lazy_static;
 use pyo3::prelude::*;
+use std::fmt;
+use std::ops::Deref;
+use std::str::FromStr;
 
 /// Maps calculation values from sRGB color space to XYZ
 pub const SRGB_TO_XYZ: [[f64; 3]; 3] = [
@@ -29,6 +35,58 @@ pub const XYZ_TO_SRGB: [[f64; 3]; 3] = [
 /// A fixed shade of white; white on a sunny day.
 pub const WHITE_POINT_D65: [f64; 3] = [95.047, 100.0, 108.883];
 
+/// A warmer shade of white; horizon light. The reference white of ICC profiles.
+pub const WHITE_POINT_D50: [f64; 3] = [96.422, 100.0, 82.521];
+
+/// CIE epsilon: the relative luminance (Y / 100) at L* = 8, where L*a*b* switches from a linear
+/// to a cube-root curve
+const LAB_EPSILON: f64 = 216.0 / 24389.0;
+
+/// CIE kappa: the slope of the linear part of the L* curve
+const LAB_KAPPA: f64 = 24389.0 / 27.0;
+
+/// Maps XYZ to the cone responses used by Bradford chromatic adaptation
+const XYZ_TO_BRADFORD: [[f64; 3]; 3] = [
+    [0.8951, 0.2664, -0.1614],
+    [-0.7502, 1.7135, 0.0367],
+    [0.0389, -0.0685, 1.0296],
+];
+
+/// Maps Bradford cone responses back to XYZ
+const BRADFORD_TO_XYZ: [[f64; 3]; 3] = [
+    [0.9869929054667121, -0.1470542564209901, 0.15996265166373125],
+    [0.4323052697233945, 0.5183602715367776, 0.049291228212855615],
+    [-0.008528664575177331, 0.04004282165408486, 0.96848669578755],
+];
+
+/// Maps linear sRGB, scaled to [0, 1], to the cone responses used by Oklab
+const LINRGB_TO_OKLAB_LMS: [[f64; 3]; 3] = [
+    [0.4122214708, 0.5363325363, 0.0514459929],
+    [0.2119034982, 0.6806995451, 0.1073969566],
+    [0.0883024619, 0.2817188376, 0.6299787005],
+];
+
+/// Maps cube-rooted Oklab cone responses to L, a, b
+const OKLAB_LMS_TO_OKLAB: [[f64; 3]; 3] = [
+    [0.2104542553, 0.7936177850, -0.0040720468],
+    [1.9779984951, -2.4285922050, 0.4505937099],
+    [0.0259040371, 0.7827717662, -0.8086757660],
+];
+
+/// Maps L, a, b to cube-rooted Oklab cone responses
+const OKLAB_TO_OKLAB_LMS: [[f64; 3]; 3] = [
+    [1.0, 0.3963377774, 0.2158037573],
+    [1.0, -0.1055613458, -0.0638541728],
+    [1.0, -0.0894841775, -1.2914855480],
+];
+
+/// Maps Oklab cone responses to linear sRGB, scaled to [0, 1]
+const OKLAB_LMS_TO_LINRGB: [[f64; 3]; 3] = [
+    [4.0767416621, -3.3077115913, 0.2309699292],
+    [-1.2684380046, 2.6097574011, -0.3413193965],
+    [-0.0041960863, -0.7034186147, 1.7076147010],
+];
+
 /// Converts a color from RGB components to ARGB format
 ///
 /// # Arguments
@@ -43,6 +101,270 @@ pub fn argb_from_rgb(rgb: [u8; 3]) -> [u8; 4] {
     [255, rgb[0], rgb[1], rgb[2]]
 }
 
+/// Squared euclidean distance between two colors' RGB components, ignoring alpha
+///
+/// A cheap approximation that does not account for how the eye weighs the channels; use the
+/// distance between [`lab_from_argb`] values, or [`crate::hct::cam16::Cam16::distance`], when
+/// accuracy matters.
+///
+/// # Arguments
+///
+/// * `a`: ARGB representation of a color
+/// * `b`: ARGB representation of a color
+///
+/// # Returns
+/// * The squared distance, from 0 for identical colors to 195075 for black and white
+#[pyfunction]
+pub fn distance_squared_rgb(a: [u8; 4], b: [u8; 4]) -> u32 {
+    (1..4)
+        .map(|channel| (a[channel] as i32 - b[channel] as i32).pow(2) as u32)
+        .sum()
+}
+
+/// The "redmean" distance between two colors, ignoring alpha
+///
+/// A weighted euclidean RGB distance whose weights shift with the average amount of red, which
+/// tracks perceived difference much better than [`distance_squared_rgb`] at nearly the same
+/// cost. It is still an approximation; use the distance between [`lab_from_argb`] values, or
+/// [`crate::hct::cam16::Cam16::distance`], when accuracy matters.
+///
+/// # Arguments
+///
+/// * `a`: ARGB representation of a color
+/// * `b`: ARGB representation of a color
+///
+/// # Returns
+/// * The distance, from 0.0 for identical colors to about 765 for black and white
+#[pyfunction]
+pub fn distance_redmean(a: [u8; 4], b: [u8; 4]) -> f64 {
+    let rgb = |argb: [u8; 4]| [argb[1] as f64, argb[2] as f64, argb[3] as f64];
+    redmean_squared(rgb(a), rgb(b)).sqrt()
+}
+
+/// The square of [`distance_redmean`], for RGB components that need not be whole numbers, ex.
+/// the average of several colors
+///
+/// # Arguments
+///
+/// * `a`: Red, green, and blue components of a color, each from 0.0 to 255.0
+/// * `b`: Red, green, and blue components of a color, each from 0.0 to 255.0
+///
+/// # Returns
+/// * The squared distance
+pub(crate) fn redmean_squared(a: [f64; 3], b: [f64; 3]) -> f64 {
+    let red_mean = (a[0] + b[0]) / 2.0;
+    let dr = a[0] - b[0];
+    let dg = a[1] - b[1];
+    let db = a[2] - b[2];
+    (2.0 + red_mean / 256.0) * dr * dr
+        + 4.0 * dg * dg
+        + (2.0 + (255.0 - red_mean) / 256.0) * db * db
+}
+
+/// Replaces the alpha component of a color
+///
+/// # Arguments
+///
+/// * `argb`: ARGB representation of a color
+/// * `alpha`: The new alpha, from 0 (transparent) to 255 (opaque)
+///
+/// # Returns
+/// * `argb` with its alpha set to `alpha`
+#[pyfunction]
+pub fn with_alpha(argb: [u8; 4], alpha: u8) -> [u8; 4] {
+    [alpha, argb[1], argb[2], argb[3]]
+}
+
+/// Composites a translucent color over another (source-over), mixing in linear light
+///
+/// # Arguments
+///
+/// * `foreground`: ARGB representation of the color on top
+/// * `background`: ARGB representation of the color underneath
+///
+/// # Returns
+/// * The color seen when `foreground` is drawn over `background`
+#[pyfunction]
+pub fn composite(foreground: [u8; 4], background: [u8; 4]) -> [u8; 4] {
+    composite_with(foreground, background, linearized, delinearized)
+}
+
+/// Composites a translucent color over another (source-over), mixing the gamma-encoded sRGB
+/// values directly. This is less physically accurate than [`composite`], but matches how
+/// browsers blend CSS colors.
+///
+/// # Arguments
+///
+/// * `foreground`: ARGB representation of the color on top
+/// * `background`: ARGB representation of the color underneath
+///
+/// # Returns
+/// * The color seen when `foreground` is drawn over `background`
+#[pyfunction]
+pub fn composite_gamma(foreground: [u8; 4], background: [u8; 4]) -> [u8; 4] {
+    composite_with(
+        foreground,
+        background,
+        |channel| channel as f64,
+        |channel| channel.round().clamp(0.0, 255.0) as u8,
+    )
+}
+
+/// Source-over compositing, mixing channels in the space given by `decode` and `encode`.
+fn composite_with(
+    foreground: [u8; 4],
+    background: [u8; 4],
+    decode: impl Fn(u8) -> f64,
+    encode: impl Fn(f64) -> u8,
+) -> [u8; 4] {
+    let fg_alpha = alpha_from_argb(foreground) as f64 / 255.0;
+    let bg_alpha = alpha_from_argb(background) as f64 / 255.0 * (1.0 - fg_alpha);
+    let alpha = fg_alpha + bg_alpha;
+    if alpha == 0.0 {
+        return [0, 0, 0, 0];
+    }
+    let mut composited = [(alpha * 255.0).round() as u8, 0, 0, 0];
+    for channel in 1..4 {
+        let mixed = decode(foreground[channel]) * fg_alpha + decode(background[channel]) * bg_alpha;
+        composited[channel] = encode(mixed / alpha);
+    }
+    composited
+}
+
+/// The relative luminance of a color, as defined by WCAG 2
+///
+/// # Arguments
+///
+/// * `argb`: ARGB representation of a color
+///
+/// # Returns
+/// * The luminance of the color, from 0.0 for black to 1.0 for white
+#[pyfunction]
+pub fn relative_luminance(argb: [u8; 4]) -> f64 {
+    let [r, g, b] = linrgb_from_argb(argb);
+    (0.2126 * r + 0.7152 * g + 0.0722 * b) / 100.0
+}
+
+/// The WCAG 2 contrast ratio between two colors, as used for accessibility compliance
+///
+/// Unlike the functions in [`crate::contrast`], which work on tones, this follows the WCAG
+/// formula exactly, including its 0.05 flare terms.
+///
+/// # Arguments
+///
+/// * `a`: ARGB representation of a color
+/// * `b`: ARGB representation of a color
+///
+/// # Returns
+/// * The contrast ratio, from 1.0 for identical luminances to 21.0 for black against white.
+///   The order of the colors does not matter.
+#[pyfunction]
+pub fn contrast_ratio(a: [u8; 4], b: [u8; 4]) -> f64 {
+    let a = relative_luminance(a);
+    let b = relative_luminance(b);
+    (a.max(b) + 0.05) / (a.min(b) + 0.05)
+}
+
+/// Converts a color packed into a `u32` as 0xAARRGGBB, the representation used by upstream
+/// material-color-utilities, to ARGB format
+///
+/// # Arguments
+///
+/// * `packed`: A color packed as 0xAARRGGBB
+///
+/// # Returns
+///
+/// * An ARGB color value mapped to distinct ARGB values
+#[pyfunction]
+pub fn argb_from_u32(packed: u32) -> [u8; 4] {
+    packed.to_be_bytes()
+}
+
+/// Packs an ARGB color into a `u32` as 0xAARRGGBB
+///
+/// # Arguments
+///
+/// * `argb`: ARGB representation of a color
+///
+/// # Returns
+///
+/// * The color packed as 0xAARRGGBB
+#[pyfunction]
+pub fn u32_from_argb(argb: [u8; 4]) -> u32 {
+    u32::from_be_bytes(argb)
+}
+
+/// An ARGB color, with alpha first. Wraps the `[u8; 4]` used throughout this crate so that
+/// colors can be parsed, printed, and built from a `u32` packed as 0xAARRGGBB.
+///
+/// APIs that take `impl Into<Argb>` accept a `[u8; 4]`, a `u32`, or an `Argb`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Argb(pub [u8; 4]);
+
+impl Argb {
+    pub fn alpha(&self) -> u8 {
+        alpha_from_argb(self.0)
+    }
+    pub fn red(&self) -> u8 {
+        red_from_argb(self.0)
+    }
+    pub fn green(&self) -> u8 {
+        green_from_argb(self.0)
+    }
+    pub fn blue(&self) -> u8 {
+        blue_from_argb(self.0)
+    }
+}
+
+impl From<[u8; 4]> for Argb {
+    fn from(argb: [u8; 4]) -> Self {
+        Argb(argb)
+    }
+}
+
+impl From<u32> for Argb {
+    fn from(argb: u32) -> Self {
+        Argb(argb_from_u32(argb))
+    }
+}
+
+impl From<Argb> for [u8; 4] {
+    fn from(argb: Argb) -> Self {
+        argb.0
+    }
+}
+
+impl From<Argb> for u32 {
+    fn from(argb: Argb) -> Self {
+        u32_from_argb(argb.0)
+    }
+}
+
+impl Deref for Argb {
+    type Target = [u8; 4];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl FromStr for Argb {
+    type Err = ParseColorError;
+
+    /// Parses any color accepted by [`argb_from_css`]: hex, named colors, or CSS functional
+    /// notation.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        argb_from_css(s).map(Argb)
+    }
+}
+
+impl fmt::Display for Argb {
+    /// Writes the color as hex, in the same format as [`hex_from_argb`].
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&hex_from_argb(self.0))
+    }
+}
+
 /// Converts a color from linear RGB components to ARGB format
 ///
 /// # Arguments
@@ -59,6 +381,44 @@ pub fn argb_from_linrgb(linrgb: [f64; 3]) -> [u8; 4] {
     argb_from_rgb([r, g, b])
 }
 
+/// Converts a color from ARGB format to linear RGB components
+///
+/// # Arguments
+///
+/// * `argb`: ARGB representation of a color
+///
+/// # Returns
+/// * The linear RGB components of the color, each in [0, 100]
+#[pyfunction]
+pub fn linrgb_from_argb(argb: [u8; 4]) -> [f64; 3] {
+    [
+        linearized(red_from_argb(argb)),
+        linearized(green_from_argb(argb)),
+        linearized(blue_from_argb(argb)),
+    ]
+}
+
+/// Interpolates between two colors in linear light, which is how light physically mixes
+///
+/// # Arguments
+///
+/// * `a`: ARGB representation of the starting color
+/// * `b`: ARGB representation of the ending color
+/// * `t`: How far to move from `a` towards `b`; 0.0 gives `a` and 1.0 gives `b`
+///
+/// # Returns
+/// * An opaque ARGB color between `a` and `b`
+#[pyfunction]
+pub fn lerp_linrgb(a: [u8; 4], b: [u8; 4], t: f64) -> [u8; 4] {
+    let a = linrgb_from_argb(a);
+    let b = linrgb_from_argb(b);
+    argb_from_linrgb([
+        lerp(a[0], b[0], t),
+        lerp(a[1], b[1], t),
+        lerp(a[2], b[2], t),
+    ])
+}
+
 /// Returns the alpha component of a color in ARGB format
 ///
 /// # Arguments
@@ -186,6 +546,59 @@ pub fn argb_from_lab(l: f64, a: f64, b: f64) -> [u8; 4] {
     argb_from_xyz([x, y, z])
 }
 
+/// Converts XYZ coordinates measured under one white point to the coordinates of the
+/// corresponding color under another, using Bradford chromatic adaptation
+///
+/// # Arguments
+///
+/// * `xyz`: The color in the XYZ color space, relative to `from_wp`
+/// * `from_wp`: The white point `xyz` is relative to, e.g. [`WHITE_POINT_D50`]
+/// * `to_wp`: The white point to adapt to, e.g. [`WHITE_POINT_D65`]
+///
+/// # Returns
+///
+/// * The adapted color in the XYZ color space, relative to `to_wp`
+#[pyfunction]
+pub fn adapt_xyz(xyz: [f64; 3], from_wp: [f64; 3], to_wp: [f64; 3]) -> [f64; 3] {
+    let cone = matrix_multiply(xyz, XYZ_TO_BRADFORD);
+    let from_cone = matrix_multiply(from_wp, XYZ_TO_BRADFORD);
+    let to_cone = matrix_multiply(to_wp, XYZ_TO_BRADFORD);
+    let adapted = [
+        cone[0] * to_cone[0] / from_cone[0],
+        cone[1] * to_cone[1] / from_cone[1],
+        cone[2] * to_cone[2] / from_cone[2],
+    ];
+    matrix_multiply(adapted, BRADFORD_TO_XYZ)
+}
+
+/// Converts a color from ARGB to XYZ relative to the D50 white point, as used by ICC profiles
+///
+/// # Arguments
+///
+/// * `argb`: ARGB representation of a color
+///
+/// # Returns
+///
+/// * The color in the XYZ color space, relative to [`WHITE_POINT_D50`]
+#[pyfunction]
+pub fn xyz_d50_from_argb(argb: [u8; 4]) -> [f64; 3] {
+    adapt_xyz(xyz_from_argb(argb), WHITE_POINT_D65, WHITE_POINT_D50)
+}
+
+/// Converts a color from XYZ relative to the D50 white point, as used by ICC profiles, to ARGB
+///
+/// # Arguments
+///
+/// * `xyz`: The color in the XYZ color space, relative to [`WHITE_POINT_D50`]
+///
+/// # Returns
+///
+/// * An ARGB equivalent of the supplied color
+#[pyfunction]
+pub fn argb_from_xyz_d50(xyz: [f64; 3]) -> [u8; 4] {
+    argb_from_xyz(adapt_xyz(xyz, WHITE_POINT_D50, WHITE_POINT_D65))
+}
+
 /// Converts a color from ARGB color space to L*a*b*
 ///
 /// # Arguments
@@ -207,6 +620,230 @@ pub fn lab_from_argb(argb: [u8; 4]) -> [f64; 3] {
     [l, a, b]
 }
 
+/// Converts a color from ARGB to HSL
+///
+/// # Arguments
+///
+/// * `argb`: ARGB representation of a color
+///
+/// # Returns
+///
+/// * The hue in degrees [0, 360), and the saturation and lightness in [0, 1]. Grays have a hue
+///   and saturation of 0.
+#[pyfunction]
+pub fn hsl_from_argb(argb: [u8; 4]) -> [f64; 3] {
+    let [r, g, b] = unit_rgb_from_argb(argb);
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let l = (max + min) / 2.0;
+    let delta = max - min;
+    if delta == 0.0 {
+        return [0.0, 0.0, l];
+    }
+    let s = delta / (1.0 - (2.0 * l - 1.0).abs());
+    [hue_from_unit_rgb([r, g, b], max, delta), s, l]
+}
+
+/// Converts a color from HSL to ARGB
+///
+/// # Arguments
+///
+/// * `h`: Hue in degrees, wrapped into [0, 360)
+/// * `s`: Saturation in [0, 1]
+/// * `l`: Lightness in [0, 1]
+///
+/// # Returns
+///
+/// * An opaque ARGB equivalent of the supplied color
+#[pyfunction]
+pub fn argb_from_hsl(h: f64, s: f64, l: f64) -> [u8; 4] {
+    let s = s.clamp(0.0, 1.0);
+    let l = l.clamp(0.0, 1.0);
+    let chroma = (1.0 - (2.0 * l - 1.0).abs()) * s;
+    argb_from_hue_and_chroma(h, chroma, l - chroma / 2.0)
+}
+
+/// Converts a color from HWB (hue, whiteness, blackness) to ARGB
+///
+/// # Arguments
+///
+/// * `h`: Hue in degrees, wrapped into [0, 360)
+/// * `w`: Whiteness in [0, 1]
+/// * `b`: Blackness in [0, 1]
+///
+/// # Returns
+///
+/// * An opaque ARGB equivalent of the supplied color. When whiteness and blackness add up to
+///   more than 1, they are scaled down proportionally, giving a gray.
+#[pyfunction]
+pub fn argb_from_hwb(h: f64, w: f64, b: f64) -> [u8; 4] {
+    let w = w.clamp(0.0, 1.0);
+    let b = b.clamp(0.0, 1.0);
+    if w + b >= 1.0 {
+        let gray = w / (w + b);
+        return argb_from_hue_and_chroma(0.0, 0.0, gray);
+    }
+    argb_from_hue_and_chroma(h, 1.0 - w - b, w)
+}
+
+/// Converts a color from ARGB to HSV
+///
+/// # Arguments
+///
+/// * `argb`: ARGB representation of a color
+///
+/// # Returns
+///
+/// * The hue in degrees [0, 360), and the saturation and value in [0, 1]. Grays have a hue and
+///   saturation of 0.
+#[pyfunction]
+pub fn hsv_from_argb(argb: [u8; 4]) -> [f64; 3] {
+    let [r, g, b] = unit_rgb_from_argb(argb);
+    let max = r.max(g).max(b);
+    let delta = max - r.min(g).min(b);
+    if delta == 0.0 {
+        return [0.0, 0.0, max];
+    }
+    [hue_from_unit_rgb([r, g, b], max, delta), delta / max, max]
+}
+
+/// Converts a color from HSV to ARGB
+///
+/// # Arguments
+///
+/// * `h`: Hue in degrees, wrapped into [0, 360)
+/// * `s`: Saturation in [0, 1]
+/// * `v`: Value in [0, 1]
+///
+/// # Returns
+///
+/// * An opaque ARGB equivalent of the supplied color
+#[pyfunction]
+pub fn argb_from_hsv(h: f64, s: f64, v: f64) -> [u8; 4] {
+    let v = v.clamp(0.0, 1.0);
+    let chroma = v * s.clamp(0.0, 1.0);
+    argb_from_hue_and_chroma(h, chroma, v - chroma)
+}
+
+/// Converts a color from ARGB to Oklab
+///
+/// # Arguments
+///
+/// * `argb`: ARGB representation of a color
+///
+/// # Returns
+///
+/// * The Oklab equivalent of the supplied color; L is in [0, 1]
+#[pyfunction]
+pub fn oklab_from_argb(argb: [u8; 4]) -> [f64; 3] {
+    let linrgb = [
+        linearized(red_from_argb(argb)) / 100.0,
+        linearized(green_from_argb(argb)) / 100.0,
+        linearized(blue_from_argb(argb)) / 100.0,
+    ];
+    let lms = matrix_multiply(linrgb, LINRGB_TO_OKLAB_LMS).map(f64::cbrt);
+    matrix_multiply(lms, OKLAB_LMS_TO_OKLAB)
+}
+
+/// Converts a color from Oklab to ARGB
+///
+/// # Arguments
+///
+/// * `l`: Lightness, in [0, 1]
+/// * `a`: Green/Red value of the color
+/// * `b`: Blue/Yellow value of the color
+///
+/// # Returns
+///
+/// * An ARGB equivalent of the supplied color, clipped to the sRGB gamut
+#[pyfunction]
+pub fn argb_from_oklab(l: f64, a: f64, b: f64) -> [u8; 4] {
+    let lms = matrix_multiply([l, a, b], OKLAB_TO_OKLAB_LMS).map(|x| x * x * x);
+    argb_from_linrgb(matrix_multiply(lms, OKLAB_LMS_TO_LINRGB).map(|x| x * 100.0))
+}
+
+/// Converts a color from ARGB to Oklch, the polar form of Oklab
+///
+/// # Arguments
+///
+/// * `argb`: ARGB representation of a color
+///
+/// # Returns
+///
+/// * Lightness in [0, 1], chroma, and hue in degrees [0, 360)
+#[pyfunction]
+pub fn oklch_from_argb(argb: [u8; 4]) -> [f64; 3] {
+    let [l, a, b] = oklab_from_argb(argb);
+    [l, a.hypot(b), b.atan2(a).to_degrees().rem_euclid(360.0)]
+}
+
+/// Converts a color from Oklch to ARGB
+///
+/// # Arguments
+///
+/// * `l`: Lightness, in [0, 1]
+/// * `c`: Chroma
+/// * `h`: Hue in degrees
+///
+/// # Returns
+///
+/// * An ARGB equivalent of the supplied color, clipped to the sRGB gamut
+#[pyfunction]
+pub fn argb_from_oklch(l: f64, c: f64, h: f64) -> [u8; 4] {
+    let (sin, cos) = h.to_radians().sin_cos();
+    argb_from_oklab(l, c * cos, c * sin)
+}
+
+/// Scales the RGB channels of `argb` to [0, 1].
+fn unit_rgb_from_argb(argb: [u8; 4]) -> [f64; 3] {
+    [
+        red_from_argb(argb) as f64 / 255.0,
+        green_from_argb(argb) as f64 / 255.0,
+        blue_from_argb(argb) as f64 / 255.0,
+    ]
+}
+
+/// The hexcone hue shared by HSL and HSV, in degrees [0, 360).
+///
+/// # Arguments
+///
+/// * `rgb`: RGB channels in [0, 1]
+/// * `max`: The largest channel
+/// * `delta`: The largest channel minus the smallest, which must not be 0
+fn hue_from_unit_rgb(rgb: [f64; 3], max: f64, delta: f64) -> f64 {
+    let [r, g, b] = rgb;
+    let sector = if max == r {
+        ((g - b) / delta).rem_euclid(6.0)
+    } else if max == g {
+        (b - r) / delta + 2.0
+    } else {
+        (r - g) / delta + 4.0
+    };
+    sector * 60.0
+}
+
+/// Builds an opaque ARGB color on the hexcone shared by HSL and HSV.
+///
+/// # Arguments
+///
+/// * `h`: Hue in degrees, wrapped into [0, 360)
+/// * `chroma`: Difference between the largest and smallest channel, in [0, 1]
+/// * `min`: The smallest channel, in [0, 1]
+fn argb_from_hue_and_chroma(h: f64, chroma: f64, min: f64) -> [u8; 4] {
+    let sector = h.rem_euclid(360.0) / 60.0;
+    let x = chroma * (1.0 - (sector.rem_euclid(2.0) - 1.0).abs());
+    let (r, g, b) = match sector as u8 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    let channel = |unit: f64| ((unit + min) * 255.0).round().clamp(0.0, 255.0) as u8;
+    argb_from_rgb([channel(r), channel(g), channel(b)])
+}
+
 /// Converts an L* value to an ARGB representation.
 ///
 /// # Arguments
@@ -234,8 +871,22 @@ pub fn argb_from_lstar(lstar: f64) -> [u8; 4] {
 /// * L*, from L*a*b*, coordinate of the color
 #[pyfunction]
 pub fn lstar_from_argb(argb: [u8; 4]) -> f64 {
-    let y = xyz_from_argb(argb)[1];
-    116.0 * lab_f(y / 100.0) - 16.0
+    lstar_from_y(xyz_from_argb(argb)[1])
+}
+
+/// Single-precision [`lstar_from_argb`], for pipelines that keep their buffers in `f32`.
+///
+/// Agrees with [`lstar_from_argb`] to within 0.001.
+pub fn lstar_from_argb_f32(argb: [u8; 4]) -> f32 {
+    let [_, r, g, b] = argb.map(linearized_f32);
+    let [wr, wg, wb] = SRGB_TO_XYZ[1].map(|weight| weight as f32);
+    let y = wr * r + wg * g + wb * b;
+    let t = y / 100.0;
+    if t > LAB_EPSILON as f32 {
+        116.0 * t.cbrt() - 16.0
+    } else {
+        LAB_KAPPA as f32 * t
+    }
 }
 
 /// Converts an L* value to a Y value.
@@ -256,6 +907,24 @@ pub fn y_from_lstar(lstar: f64) -> f64 {
     100.0 * lab_invf((lstar + 16.0) / 116.0)
 }
 
+/// Converts a Y value to an L* value.
+///
+/// L* in L*a*b* and Y in XYZ measure the same quantity, luminance. L* measures
+/// perceptual luminance, a linear scale. Y in XYZ measures relative luminance,
+/// a logarithmic scale.
+///
+/// # Arguments
+///
+/// * `y`: The value of Y from the XYZ color space
+///
+/// # Returns
+///
+/// * The Lightness value of an L*a*b* color that corresponds to the Y value
+#[pyfunction]
+pub fn lstar_from_y(y: f64) -> f64 {
+    116.0 * lab_f(y / 100.0) - 16.0
+}
+
 /// Linearizes an RGB component.
 ///
 /// # Arguments
@@ -267,6 +936,29 @@ pub fn y_from_lstar(lstar: f64) -> f64 {
 /// * 0.0 <= output <= 100.0, color channel converted to linear RGB space
 #[pyfunction]
 pub fn linearized(rgb_comp: u8) -> f64 {
+    LINEARIZED[rgb_comp as usize]
+}
+
+/// Single-precision [`linearized`], for pipelines that keep their buffers in `f32`.
+///
+/// The lookup table value rounded to `f32`, so it is within one `f32` rounding step (about 6e-6
+/// at 100.0) of [`linearized`].
+pub fn linearized_f32(rgb_comp: u8) -> f32 {
+    LINEARIZED[rgb_comp as usize] as f32
+}
+
+/// Linearizes an RGB component by evaluating the sRGB transfer function, rather than looking
+/// the answer up like [`linearized`] does.
+///
+/// # Arguments
+///
+/// * `rgb_comp`: RGB channel component to normalize
+///
+/// # Returns
+///
+/// * 0.0 <= output <= 100.0, color channel converted to linear RGB space
+#[pyfunction]
+pub fn linearized_exact(rgb_comp: u8) -> f64 {
     let normalized = rgb_comp as f64 / 255.0;
     if normalized <= 0.040449936 {
         normalized / 12.92 * 100.0
@@ -286,6 +978,22 @@ pub fn linearized(rgb_comp: u8) -> f64 {
 /// * 0 <= output <= 255, color channel converted to regular RGB space
 #[pyfunction]
 pub fn delinearized(rgb_comp: f64) -> u8 {
+    DELINEARIZED_THRESHOLDS.partition_point(|&threshold| threshold <= rgb_comp) as u8
+}
+
+/// Delinearizes an RGB component by evaluating the sRGB transfer function, rather than
+/// searching precomputed thresholds like [`delinearized`] does.
+///
+/// # Arguments
+///
+/// * `rgb_comp`: RGB channel component to normalize
+///
+/// # Returns
+///
+/// * 0 <= output <= 255, color channel converted to regular RGB space
+#[pyfunction]
+pub fn delinearized_exact(rgb_comp: f64) -> u8 {
+    // Same threshold, and the same round-then-clamp order, as the reference implementation.
     let normalized = rgb_comp / 100.0;
     let delinearized = if normalized <= 0.0031308 {
         normalized * 12.92
@@ -295,6 +1003,38 @@ pub fn delinearized(rgb_comp: f64) -> u8 {
     (delinearized * 255.0).round().clamp(0.0, 255.0) as u8
 }
 
+lazy_static! {
+    /// [`linearized_exact`] for every channel value.
+    static ref LINEARIZED: [f64; 256] = {
+        let mut table = [0.0; 256];
+        for (rgb_comp, linear) in table.iter_mut().enumerate() {
+            *linear = linearized_exact(rgb_comp as u8);
+        }
+        table
+    };
+
+    /// The smallest linear component that [`delinearized_exact`] maps to each of 1 to 255.
+    static ref DELINEARIZED_THRESHOLDS: [f64; 255] = {
+        let mut table = [0.0; 255];
+        for (index, threshold) in table.iter_mut().enumerate() {
+            let target = index as u8 + 1;
+            // Non-negative floats order the same way as their bits, so bisecting the bits finds
+            // the exact float where the output steps up.
+            let (mut low, mut high) = (0u64, 100.0f64.to_bits());
+            while low < high {
+                let mid = low + (high - low) / 2;
+                if delinearized_exact(f64::from_bits(mid)) >= target {
+                    high = mid;
+                } else {
+                    low = mid + 1;
+                }
+            }
+            *threshold = f64::from_bits(low);
+        }
+        table
+    };
+}
+
 /// Returns the standard white point
 ///
 /// # Returns
@@ -319,12 +1059,10 @@ pub fn white_point_d65() -> [f64; 3] {
 /// * The perceived luminance of `t`.
 #[pyfunction]
 fn lab_f(t: f64) -> f64 {
-    let e = 216.0 / 24389.0;
-    let kappa = 24389.0 / 27.0;
-    if t > e {
+    if t > LAB_EPSILON {
         t.powf(1.0 / 3.0)
     } else {
-        (kappa * t + 16.0) / 116.0
+        (LAB_KAPPA * t + 16.0) / 116.0
     }
 }
 
@@ -342,24 +1080,114 @@ fn lab_f(t: f64) -> f64 {
 ///   of WHITE_POINT_D65.
 #[pyfunction]
 fn lab_invf(ft: f64) -> f64 {
-    let e = 216.0 / 24389.0;
-    let kappa = 24389.0 / 27.0;
     let ft3 = ft * ft * ft;
-    if ft3 > e {
+    if ft3 > LAB_EPSILON {
         ft3
     } else {
-        (116.0 * ft - 16.0) / kappa
+        (116.0 * ft - 16.0) / LAB_KAPPA
+    }
+}
+
+/// Converts a buffer of ARGB pixels to L*, writing one value per pixel into `out`.
+///
+/// Equivalent to calling [`lstar_from_argb`] on every pixel, but only computes the Y component
+/// of XYZ. With the `rayon` feature the pixels are processed in parallel.
+///
+/// # Panics
+///
+/// If `pixels` and `out` have different lengths.
+pub fn lstars_from_argb(pixels: &[[u8; 4]], out: &mut [f64]) {
+    let linearized = &*LINEARIZED;
+    let [kr, kg, kb] = SRGB_TO_XYZ[1].map(|weight| weight / 100.0);
+    map_pixels(pixels, out, |argb| {
+        let y = linearized[argb[1] as usize] * kr
+            + linearized[argb[2] as usize] * kg
+            + linearized[argb[3] as usize] * kb;
+        116.0 * lab_f(y) - 16.0
+    });
+}
+
+/// Converts a buffer of ARGB pixels to L*a*b*, writing one value per pixel into `out`.
+///
+/// Equivalent to calling [`lab_from_argb`] on every pixel. With the `rayon` feature the pixels
+/// are processed in parallel.
+///
+/// # Panics
+///
+/// If `pixels` and `out` have different lengths.
+pub fn labs_from_argb(pixels: &[[u8; 4]], out: &mut [[f64; 3]]) {
+    let linearized = &*LINEARIZED;
+    // Each row of the sRGB to XYZ matrix divided by its white point component, so a pixel
+    // needs one matrix multiply rather than a multiply and three divisions.
+    let to_xyz: [[f64; 3]; 3] =
+        std::array::from_fn(|row| SRGB_TO_XYZ[row].map(|weight| weight / WHITE_POINT_D65[row]));
+    map_pixels(pixels, out, |argb| {
+        let rgb = [
+            linearized[argb[1] as usize],
+            linearized[argb[2] as usize],
+            linearized[argb[3] as usize],
+        ];
+        let [x, y, z] = matrix_multiply(rgb, to_xyz);
+        let (fx, fy, fz) = (lab_f(x), lab_f(y), lab_f(z));
+        [116.0 * fy - 16.0, 500.0 * (fx - fy), 200.0 * (fy - fz)]
+    });
+}
+
+/// Converts a buffer of HCT colors to ARGB, writing one value per color into `out`.
+///
+/// With the `rayon` feature the colors are processed in parallel.
+///
+/// # Panics
+///
+/// If `hcts` and `out` have different lengths.
+pub fn argbs_from_hcts(hcts: &[Hct], out: &mut [[u8; 4]]) {
+    map_pixels(hcts, out, |hct| hct.to_int());
+}
+
+fn map_pixels<T, U>(input: &[T], out: &mut [U], convert: impl Fn(T) -> U + Sync)
+where
+    T: Copy + Sync,
+    U: Send,
+{
+    assert_eq!(
+        input.len(),
+        out.len(),
+        "input and output buffers differ in length"
+    );
+    #[cfg(feature = "rayon")]
+    {
+        use rayon::prelude::*;
+        out.par_iter_mut()
+            .zip(input.par_iter())
+            .for_each(|(out, &pixel)| *out = convert(pixel));
+    }
+    #[cfg(not(feature = "rayon"))]
+    for (out, &pixel) in out.iter_mut().zip(input) {
+        *out = convert(pixel);
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use super::{
+        BRADFORD_TO_XYZ, LINRGB_TO_OKLAB_LMS, OKLAB_LMS_TO_LINRGB, OKLAB_LMS_TO_OKLAB,
+        OKLAB_TO_OKLAB_LMS, SRGB_TO_XYZ, XYZ_TO_BRADFORD, XYZ_TO_SRGB,
+    };
+    use crate::hct::Hct;
     use crate::utils::color::{
-        alpha_from_argb, argb_from_lab, argb_from_linrgb, argb_from_lstar, argb_from_rgb,
-        argb_from_xyz, blue_from_argb, delinearized, green_from_argb, is_opaque, lab_from_argb,
-        linearized, lstar_from_argb, red_from_argb, white_point_d65, xyz_from_argb, y_from_lstar,
-        WHITE_POINT_D65,
+        adapt_xyz, alpha_from_argb, argb_from_hsl, argb_from_hsv, argb_from_hwb, argb_from_lab,
+        argb_from_linrgb, argb_from_lstar, argb_from_oklab, argb_from_oklch, argb_from_rgb,
+        argb_from_u32, argb_from_xyz, argb_from_xyz_d50, argbs_from_hcts, blue_from_argb,
+        composite, composite_gamma, contrast_ratio, delinearized, delinearized_exact,
+        distance_redmean, distance_squared_rgb, green_from_argb, hsl_from_argb, hsv_from_argb,
+        is_opaque, lab_from_argb, labs_from_argb, lerp_linrgb, linearized, linearized_exact,
+        linearized_f32, linrgb_from_argb, lstar_from_argb, lstar_from_argb_f32, lstar_from_y,
+        lstars_from_argb, oklab_from_argb, oklch_from_argb, red_from_argb, relative_luminance,
+        u32_from_argb, white_point_d65, with_alpha, xyz_d50_from_argb, xyz_from_argb, y_from_lstar,
+        Argb, WHITE_POINT_D50, WHITE_POINT_D65,
     };
+    use crate::utils::math::assert_matrices_are_inverses;
+    use assert_approx_eq::assert_approx_eq;
 
     #[test]
     fn test_argb_from_rgb() {
@@ -404,6 +1232,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::bool_assert_comparison)]
     fn test_is_opaque() {
         let fixed_argb_one = [255, 119, 0, 153];
         let fixed_argb_two = [160, 72, 102, 190];
@@ -463,6 +1292,17 @@ mod tests {
         assert_eq!(lstar, 29.965403607253286);
     }
 
+    #[test]
+    fn test_lstar_y_round_trip() {
+        // Every 0.01 of L*, plus a tight sweep around the kink at L* = 8 where the curve switches
+        // from linear to cubic.
+        let sweep = (0..=10000).map(|i| i as f64 / 100.0);
+        let kink = (-100..=100).map(|i| 8.0 + i as f64 * 1e-6);
+        for lstar in sweep.chain(kink) {
+            assert_approx_eq!(lstar, lstar_from_y(y_from_lstar(lstar)), 1e-9);
+        }
+    }
+
     #[test]
     fn test_y_from_lstar() {
         let y = y_from_lstar(29.965403607253286);
@@ -486,4 +1326,503 @@ mod tests {
         let wp = white_point_d65();
         assert_eq!(wp, WHITE_POINT_D65);
     }
+
+    #[test]
+    fn test_lstar_from_y() {
+        let lstar = lstar_from_y(6.221846121142539);
+        assert_eq!(lstar, 29.965403607253286);
+    }
+
+    #[test]
+    fn test_hsl_from_argb() {
+        assert_eq!(hsl_from_argb([255, 255, 0, 0]), [0.0, 1.0, 0.5]);
+        assert_eq!(hsl_from_argb([255, 0, 255, 255]), [180.0, 1.0, 0.5]);
+        assert_eq!(hsl_from_argb([255, 255, 0, 255]), [300.0, 1.0, 0.5]);
+        let hsl = hsl_from_argb([255, 119, 0, 153]);
+        assert_approx_eq!(286.667, hsl[0], 0.001);
+        assert_approx_eq!(1.0, hsl[1], 0.001);
+        assert_approx_eq!(0.3, hsl[2], 0.001);
+    }
+
+    #[test]
+    fn test_hsl_achromatic() {
+        assert_eq!(hsl_from_argb([255, 0, 0, 0]), [0.0, 0.0, 0.0]);
+        assert_eq!(hsl_from_argb([255, 255, 255, 255]), [0.0, 0.0, 1.0]);
+        assert_eq!(hsl_from_argb([255, 128, 128, 128])[1], 0.0);
+        assert_eq!(argb_from_hsl(123.0, 0.0, 0.5), [255, 128, 128, 128]);
+    }
+
+    #[test]
+    fn test_argb_from_hsl_wraps_hue() {
+        assert_eq!(argb_from_hsl(360.0, 1.0, 0.5), [255, 255, 0, 0]);
+        assert_eq!(argb_from_hsl(-120.0, 1.0, 0.5), [255, 0, 0, 255]);
+        assert_eq!(argb_from_hsl(480.0, 1.0, 0.5), [255, 0, 255, 0]);
+    }
+
+    #[test]
+    fn test_hsl_round_trip() {
+        for argb in [
+            [255, 0, 0, 0],
+            [255, 255, 0, 0],
+            [255, 0, 255, 0],
+            [255, 0, 0, 255],
+            [255, 255, 255, 0],
+            [255, 0, 255, 255],
+            [255, 255, 0, 255],
+            [255, 255, 255, 255],
+            [255, 119, 0, 153],
+            [255, 66, 133, 244],
+            [255, 76, 95, 158],
+            [255, 200, 180, 90],
+        ] {
+            let [h, s, l] = hsl_from_argb(argb);
+            let round_trip = argb_from_hsl(h, s, l);
+            for channel in 1..4 {
+                assert!((argb[channel] as i16 - round_trip[channel] as i16).abs() <= 1);
+            }
+        }
+    }
+
+    #[test]
+    fn test_hsv_corners() {
+        for (argb, hue) in [
+            ([255, 255, 0, 0], 0.0),
+            ([255, 255, 255, 0], 60.0),
+            ([255, 0, 255, 0], 120.0),
+            ([255, 0, 255, 255], 180.0),
+            ([255, 0, 0, 255], 240.0),
+            ([255, 255, 0, 255], 300.0),
+        ] {
+            assert_eq!(hsv_from_argb(argb), [hue, 1.0, 1.0]);
+            assert_eq!(argb_from_hsv(hue, 1.0, 1.0), argb);
+        }
+    }
+
+    #[test]
+    fn test_hsv_grays() {
+        assert_eq!(hsv_from_argb([255, 0, 0, 0]), [0.0, 0.0, 0.0]);
+        assert_eq!(hsv_from_argb([255, 255, 255, 255]), [0.0, 0.0, 1.0]);
+        let hsv = hsv_from_argb([255, 119, 119, 119]);
+        assert_eq!(hsv[0], 0.0);
+        assert_eq!(hsv[1], 0.0);
+        assert_approx_eq!(0.467, hsv[2], 0.001);
+        assert_eq!(argb_from_hsv(200.0, 0.0, hsv[2]), [255, 119, 119, 119]);
+    }
+
+    #[test]
+    fn test_hsv_round_trip() {
+        for r in (0..=255).step_by(17) {
+            for g in (0..=255).step_by(51) {
+                for b in (0..=255).step_by(85) {
+                    let argb = [255, r as u8, g as u8, b as u8];
+                    let [h, s, v] = hsv_from_argb(argb);
+                    assert_eq!(argb_from_hsv(h, s, v), argb);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_oklab_primaries() {
+        let red = oklab_from_argb([255, 255, 0, 0]);
+        assert_approx_eq!(0.627955, red[0], 1e-5);
+        assert_approx_eq!(0.224863, red[1], 1e-5);
+        assert_approx_eq!(0.125846, red[2], 1e-5);
+        let green = oklab_from_argb([255, 0, 255, 0]);
+        assert_approx_eq!(0.866440, green[0], 1e-5);
+        assert_approx_eq!(-0.233888, green[1], 1e-5);
+        assert_approx_eq!(0.179498, green[2], 1e-5);
+        let blue = oklab_from_argb([255, 0, 0, 255]);
+        assert_approx_eq!(0.452014, blue[0], 1e-5);
+        assert_approx_eq!(-0.032457, blue[1], 1e-5);
+        assert_approx_eq!(-0.311528, blue[2], 1e-5);
+        let white = oklab_from_argb([255, 255, 255, 255]);
+        assert_approx_eq!(1.0, white[0], 1e-5);
+        assert_approx_eq!(0.0, white[1], 1e-5);
+        assert_approx_eq!(0.0, white[2], 1e-5);
+    }
+
+    #[test]
+    fn test_oklch_from_argb() {
+        let red = oklch_from_argb([255, 255, 0, 0]);
+        assert_approx_eq!(0.627955, red[0], 1e-5);
+        assert_approx_eq!(0.257683, red[1], 1e-5);
+        assert_approx_eq!(29.2339, red[2], 1e-3);
+        let blue = oklch_from_argb([255, 0, 0, 255]);
+        assert_approx_eq!(264.052, blue[2], 1e-3);
+    }
+
+    #[test]
+    fn test_oklab_round_trip() {
+        for argb in [
+            [255, 0, 0, 0],
+            [255, 255, 255, 255],
+            [255, 255, 0, 0],
+            [255, 0, 255, 0],
+            [255, 0, 0, 255],
+            [255, 119, 0, 153],
+            [255, 66, 133, 244],
+            [255, 200, 180, 90],
+        ] {
+            let [l, a, b] = oklab_from_argb(argb);
+            assert_eq!(argb_from_oklab(l, a, b), argb);
+            let [l, c, h] = oklch_from_argb(argb);
+            assert_eq!(argb_from_oklch(l, c, h), argb);
+        }
+    }
+
+    #[test]
+    fn test_packed_u32() {
+        assert_eq!(argb_from_u32(0xff4285f4), [0xff, 0x42, 0x85, 0xf4]);
+        assert_eq!(u32_from_argb([0xff, 0x42, 0x85, 0xf4]), 0xff4285f4);
+        assert_eq!(u32_from_argb(argb_from_u32(0x80123456)), 0x80123456);
+    }
+
+    #[test]
+    fn test_linrgb_from_argb() {
+        let linrgb = linrgb_from_argb([255, 119, 119, 119]);
+        assert_approx_eq!(18.447, linrgb[0], 0.001);
+        assert_eq!(linrgb[0], linrgb[1]);
+        assert_eq!(linrgb_from_argb([255, 255, 0, 255]), [100.0, 0.0, 100.0]);
+        for argb in [[255, 119, 0, 153], [255, 66, 133, 244], [255, 1, 2, 3]] {
+            assert_eq!(argb_from_linrgb(linrgb_from_argb(argb)), argb);
+        }
+    }
+
+    #[test]
+    fn test_lerp_linrgb() {
+        let red = [255, 255, 0, 0];
+        let green = [255, 0, 255, 0];
+        assert_eq!(lerp_linrgb(red, green, 0.0), red);
+        assert_eq!(lerp_linrgb(red, green, 1.0), green);
+        let linear = lerp_linrgb(red, green, 0.5);
+        assert_eq!(linear, [255, 188, 188, 0]);
+        // Averaging the encoded sRGB values darkens the midpoint.
+        let naive = [255, 128, 128, 0];
+        assert!(lstar_from_argb(linear) > lstar_from_argb(naive));
+    }
+
+    #[test]
+    fn test_relative_luminance() {
+        assert_eq!(relative_luminance([255, 0, 0, 0]), 0.0);
+        assert_approx_eq!(1.0, relative_luminance([255, 255, 255, 255]), 1e-9);
+        assert_approx_eq!(0.2126, relative_luminance([255, 255, 0, 0]), 1e-9);
+        assert_approx_eq!(0.0722, relative_luminance([255, 0, 0, 255]), 1e-9);
+    }
+
+    #[test]
+    fn test_contrast_ratio() {
+        // Reference values from the WebAIM contrast checker, which truncates to two decimals.
+        let webaim = |expected: f64, ratio: f64| {
+            assert!(
+                ratio > expected - 1e-9 && ratio < expected + 0.01,
+                "{ratio}"
+            );
+        };
+        let white = [255, 255, 255, 255];
+        let black = [255, 0, 0, 0];
+        webaim(21.0, contrast_ratio(black, white));
+        webaim(4.47, contrast_ratio([255, 0x77, 0x77, 0x77], white));
+        webaim(4.54, contrast_ratio([255, 0x76, 0x76, 0x76], white));
+        webaim(8.59, contrast_ratio([255, 0, 0, 255], white));
+        webaim(3.99, contrast_ratio([255, 255, 0, 0], white));
+        webaim(19.55, contrast_ratio([255, 255, 255, 0], black));
+        assert_eq!(contrast_ratio(white, black), contrast_ratio(black, white));
+        assert_eq!(contrast_ratio(white, white), 1.0);
+    }
+
+    #[test]
+    fn test_with_alpha() {
+        assert_eq!(with_alpha([255, 1, 2, 3], 128), [128, 1, 2, 3]);
+    }
+
+    #[test]
+    fn test_composite() {
+        let white = [255, 255, 255, 255];
+        let half_black = [128, 0, 0, 0];
+        // Half of white's linear light survives, which encodes well above the sRGB midpoint.
+        assert_eq!(composite(half_black, white), [255, 187, 187, 187]);
+        assert_eq!(composite_gamma(half_black, white), [255, 127, 127, 127]);
+        let opaque = [255, 66, 133, 244];
+        assert_eq!(composite(opaque, white), opaque);
+        assert_eq!(composite_gamma(opaque, white), opaque);
+        assert_eq!(composite([0, 66, 133, 244], white), white);
+    }
+
+    #[test]
+    fn test_composite_translucent_background() {
+        let red = [128, 255, 0, 0];
+        let blue = [128, 0, 0, 255];
+        let composited = composite_gamma(red, blue);
+        assert_eq!(composited[0], 192);
+        assert!(composited[1] > composited[3]);
+        assert_eq!(composite([0, 0, 0, 0], [0, 0, 0, 0]), [0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_adapt_xyz_white_points() {
+        let d50 = adapt_xyz(WHITE_POINT_D65, WHITE_POINT_D65, WHITE_POINT_D50);
+        let d65 = adapt_xyz(WHITE_POINT_D50, WHITE_POINT_D50, WHITE_POINT_D65);
+        for axis in 0..3 {
+            assert_approx_eq!(WHITE_POINT_D50[axis], d50[axis], 1e-9);
+            assert_approx_eq!(WHITE_POINT_D65[axis], d65[axis], 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_adapt_xyz_round_trip() {
+        let xyz = [13.356723824257475, 6.221846121142539, 30.629358478049];
+        let there = adapt_xyz(xyz, WHITE_POINT_D65, WHITE_POINT_D50);
+        let back = adapt_xyz(there, WHITE_POINT_D50, WHITE_POINT_D65);
+        for axis in 0..3 {
+            assert_approx_eq!(xyz[axis], back[axis], 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_xyz_d50() {
+        // Bradford-adapted sRGB red. SRGB_TO_XYZ differs slightly from the matrix usually
+        // published alongside this value, hence the loose tolerance.
+        let red = xyz_d50_from_argb([255, 255, 0, 0]);
+        assert_approx_eq!(43.607, red[0], 0.02);
+        assert_approx_eq!(22.249, red[1], 0.02);
+        assert_approx_eq!(1.392, red[2], 0.02);
+        for argb in [[255, 255, 0, 0], [255, 119, 0, 153], [255, 255, 255, 255]] {
+            assert_eq!(argb_from_xyz_d50(xyz_d50_from_argb(argb)), argb);
+        }
+    }
+
+    fn sample_pixels() -> Vec<[u8; 4]> {
+        (0..4096u32)
+            .map(|i| {
+                let mixed = i.wrapping_mul(2_654_435_761);
+                [
+                    255,
+                    (mixed >> 24) as u8,
+                    (mixed >> 16) as u8,
+                    (mixed >> 8) as u8,
+                ]
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_batch_conversions() {
+        let pixels = sample_pixels();
+        let mut lstars = vec![0.0; pixels.len()];
+        let mut labs = vec![[0.0; 3]; pixels.len()];
+        lstars_from_argb(&pixels, &mut lstars);
+        labs_from_argb(&pixels, &mut labs);
+        for (i, &argb) in pixels.iter().enumerate() {
+            assert_approx_eq!(lstars[i], lstar_from_argb(argb), 1e-9);
+            let lab = lab_from_argb(argb);
+            for axis in 0..3 {
+                assert_approx_eq!(labs[i][axis], lab[axis], 1e-9);
+            }
+        }
+
+        let hcts: Vec<Hct> = (0..36)
+            .map(|i| Hct::from(i as f64 * 10.0, 40.0, 50.0))
+            .collect();
+        let mut argbs = vec![[0; 4]; hcts.len()];
+        argbs_from_hcts(&hcts, &mut argbs);
+        for (hct, argb) in hcts.iter().zip(argbs) {
+            assert_eq!(hct.to_int(), argb);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "differ in length")]
+    fn test_batch_length_mismatch() {
+        labs_from_argb(&[[255, 0, 0, 0]], &mut []);
+    }
+
+    #[test]
+    fn test_linearized_table() {
+        for rgb_comp in 0..=255u8 {
+            assert_eq!(
+                linearized(rgb_comp).to_bits(),
+                linearized_exact(rgb_comp).to_bits()
+            );
+            assert_eq!(delinearized(linearized(rgb_comp)), rgb_comp);
+        }
+    }
+
+    #[test]
+    fn test_delinearized_thresholds() {
+        for step in -1000..=101_000 {
+            let linear = step as f64 / 1000.0;
+            assert_eq!(delinearized(linear), delinearized_exact(linear), "{linear}");
+        }
+        for linear in [
+            f64::NEG_INFINITY,
+            -0.0,
+            1e-300,
+            100.0,
+            1e9,
+            f64::INFINITY,
+            f64::NAN,
+        ] {
+            assert_eq!(delinearized(linear), delinearized_exact(linear), "{linear}");
+        }
+    }
+
+    // Upstream's rgb_to_xyz_to_rgb test allows each channel to be off by 1.5. Both conversions
+    // are exact inverses up to rounding, so here every channel must come back unchanged.
+    #[test]
+    fn test_rgb_xyz_round_trip() {
+        let steps: Vec<u8> = (0..=255).step_by(5).collect();
+        for &r in &steps {
+            for &g in &steps {
+                for &b in &steps {
+                    let argb = [255, r, g, b];
+                    assert_eq!(argb_from_xyz(xyz_from_argb(argb)), argb);
+                }
+            }
+        }
+        for channel in 0..=255 {
+            for argb in [
+                [255, channel, 0, 0],
+                [255, 0, channel, 0],
+                [255, 0, 0, channel],
+            ] {
+                assert_eq!(argb_from_xyz(xyz_from_argb(argb)), argb);
+            }
+        }
+    }
+
+    // Regenerate the argb column from the upstream library with
+    // testdata/generate_ts_fixtures.mjs.
+    #[test]
+    fn test_argb_from_xyz_matches_reference() {
+        let reference = include_str!("testdata/argb_from_xyz.csv");
+        let mut checked = 0;
+        for line in reference.lines().filter(|line| !line.starts_with('#')) {
+            let fields: Vec<&str> = line.split(',').collect();
+            let xyz = [0, 1, 2].map(|axis| fields[axis].parse::<f64>().unwrap());
+            let expected = argb_from_u32(u32::from_str_radix(fields[3], 16).unwrap());
+            assert_eq!(argb_from_xyz(xyz), expected, "{xyz:?}");
+            checked += 1;
+        }
+        assert_eq!(checked, 300);
+    }
+
+    #[test]
+    fn test_rgb_distances() {
+        let black = [255, 0, 0, 0];
+        let white = [255, 255, 255, 255];
+        assert_eq!(distance_squared_rgb(black, white), 195075);
+        assert_eq!(distance_squared_rgb([0, 1, 2, 3], [255, 1, 2, 3]), 0);
+        assert_eq!(distance_redmean(white, white), 0.0);
+        assert_approx_eq!(764.833, distance_redmean(black, white), 0.001);
+        assert_eq!(
+            distance_redmean(black, white),
+            distance_redmean(white, black)
+        );
+    }
+
+    #[test]
+    fn test_rgb_distances_order_like_lab() {
+        let lab_distance = |a: [u8; 4], b: [u8; 4]| {
+            let (a, b) = (lab_from_argb(a), lab_from_argb(b));
+            ((a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2) + (a[2] - b[2]).powi(2)).sqrt()
+        };
+        let colors: Vec<[u8; 4]> = (0..64u32)
+            .map(|i| {
+                let mixed = i.wrapping_mul(2_654_435_761);
+                [
+                    255,
+                    (mixed >> 24) as u8,
+                    (mixed >> 16) as u8,
+                    (mixed >> 8) as u8,
+                ]
+            })
+            .collect();
+        // For every pair of candidates, check whether each metric agrees with L*a*b* about which
+        // one is closer to the reference color.
+        let (mut squared_agree, mut redmean_agree, mut total) = (0, 0, 0);
+        for &reference in &colors[..8] {
+            for (i, &x) in colors.iter().enumerate() {
+                for &y in &colors[i + 1..] {
+                    let lab = lab_distance(reference, x) < lab_distance(reference, y);
+                    let squared =
+                        distance_squared_rgb(reference, x) < distance_squared_rgb(reference, y);
+                    let redmean = distance_redmean(reference, x) < distance_redmean(reference, y);
+                    squared_agree += (squared == lab) as u32;
+                    redmean_agree += (redmean == lab) as u32;
+                    total += 1;
+                }
+            }
+        }
+        assert!(squared_agree as f64 / total as f64 > 0.7);
+        assert!(redmean_agree > squared_agree);
+    }
+
+    #[test]
+    fn test_argb_from_hwb() {
+        assert_eq!(argb_from_hwb(0.0, 0.0, 0.0), [255, 255, 0, 0]);
+        assert_eq!(argb_from_hwb(120.0, 0.0, 0.0), [255, 0, 255, 0]);
+        assert_eq!(argb_from_hwb(0.0, 1.0, 0.0), [255, 255, 255, 255]);
+        assert_eq!(argb_from_hwb(0.0, 0.0, 1.0), [255, 0, 0, 0]);
+        assert_eq!(argb_from_hwb(0.0, 0.75, 0.75), [255, 128, 128, 128]);
+        assert_eq!(argb_from_hwb(240.0, 0.2, 0.2), [255, 51, 51, 204]);
+    }
+
+    #[test]
+    fn test_argb_conversions() {
+        let blue = Argb::from([0xff, 0x42, 0x85, 0xf4]);
+        assert_eq!(Argb::from(0xff4285f4_u32), blue);
+        assert_eq!(<[u8; 4]>::from(blue), [0xff, 0x42, 0x85, 0xf4]);
+        assert_eq!(u32::from(blue), 0xff4285f4);
+        assert_eq!(*blue, [0xff, 0x42, 0x85, 0xf4]);
+        assert_eq!(blue[1], 0x42);
+        assert_eq!(
+            (blue.alpha(), blue.red(), blue.green(), blue.blue()),
+            (0xff, 0x42, 0x85, 0xf4)
+        );
+    }
+
+    #[test]
+    fn test_argb_string_round_trip() {
+        for text in ["#4285f4", "#4285f480", "#000000", "#ffffff"] {
+            let argb: Argb = text.parse().unwrap();
+            assert_eq!(argb.to_string(), text);
+        }
+        assert_eq!("rebeccapurple".parse(), Ok(Argb([255, 102, 51, 153])));
+        assert_eq!("rgb(66 133 244)".parse(), Ok(Argb([255, 66, 133, 244])));
+        assert_eq!("#FFF".parse::<Argb>().unwrap().to_string(), "#ffffff");
+        assert_eq!(
+            "#12".parse::<Argb>(),
+            Err(crate::utils::string::ParseColorError::InvalidLength(2))
+        );
+    }
+
+    #[test]
+    fn test_matrices_are_inverses() {
+        for (forward, inverse) in [
+            (SRGB_TO_XYZ, XYZ_TO_SRGB),
+            (XYZ_TO_BRADFORD, BRADFORD_TO_XYZ),
+            (LINRGB_TO_OKLAB_LMS, OKLAB_LMS_TO_LINRGB),
+            (OKLAB_LMS_TO_OKLAB, OKLAB_TO_OKLAB_LMS),
+        ] {
+            assert_matrices_are_inverses(forward, inverse, 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_f32_variants_match_f64() {
+        for channel in 0..=255 {
+            let exact = linearized(channel);
+            assert!((linearized_f32(channel) as f64 - exact).abs() <= exact * 1e-7);
+        }
+        for r in (0..=255).step_by(15) {
+            for g in (0..=255).step_by(15) {
+                for b in (0..=255).step_by(15) {
+                    let argb = [255, r, g, b];
+                    let lstar = lstar_from_argb_f32(argb) as f64;
+                    assert_approx_eq!(lstar, lstar_from_argb(argb), 1e-3);
+                }
+            }
+        }
+    }
 }
@@ -0,0 +1,161 @@
+//! Serde support for ARGB colors, used by the `serde` feature.
+//!
+//! Colors are written as hex strings like `"#4285f4"` by default, or as ARGB numbers like
+//! `4282549748` (the shape the TypeScript library produces) inside [`with_color_format`].
+//! Either form, or any other string accepted by [`argb_from_css`], is read back.
+
+use super::color::{argb_from_u32, u32_from_argb};
+use super::string::{argb_from_css, hex_from_argb};
+use serde::de::{self, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::cell::Cell;
+use std::fmt;
+
+/// How colors are written when serialized.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorFormat {
+    /// Hex strings, as produced by [`hex_from_argb`].
+    #[default]
+    Hex,
+    /// ARGB packed into a number, as produced by [`u32_from_argb`].
+    Number,
+}
+
+thread_local! {
+    static FORMAT: Cell<ColorFormat> = Cell::new(ColorFormat::default());
+}
+
+/// Restores the previous format when dropped, so that a panic inside [`with_color_format`]
+/// does not leak its format into later serialization.
+struct FormatGuard(ColorFormat);
+
+impl Drop for FormatGuard {
+    fn drop(&mut self) {
+        FORMAT.with(|format| format.set(self.0));
+    }
+}
+
+/// Runs `f` with colors serialized in `format` on the current thread.
+///
+/// # Arguments
+///
+/// * `format`: How colors are written.
+/// * `f`: Serialization to run, ex. `|| serde_json::to_string(&theme)`.
+///
+/// # Returns
+///
+/// * Whatever `f` returns.
+pub fn with_color_format<R>(format: ColorFormat, f: impl FnOnce() -> R) -> R {
+    let _guard = FormatGuard(FORMAT.with(|current| current.replace(format)));
+    f()
+}
+
+/// Serializes an ARGB color in the current [`ColorFormat`]. For use with `#[serde(with)]`.
+pub fn serialize<S: Serializer>(argb: &[u8; 4], serializer: S) -> Result<S::Ok, S::Error> {
+    match FORMAT.with(Cell::get) {
+        ColorFormat::Hex => serializer.serialize_str(&hex_from_argb(*argb)),
+        ColorFormat::Number => serializer.serialize_u32(u32_from_argb(*argb)),
+    }
+}
+
+/// Deserializes an ARGB color from either a number or a color string. For use with
+/// `#[serde(with)]`.
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<[u8; 4], D::Error> {
+    deserializer.deserialize_any(ColorVisitor)
+}
+
+struct ColorVisitor;
+
+impl<'de> Visitor<'de> for ColorVisitor {
+    type Value = [u8; 4];
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an ARGB number or a color string")
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<Self::Value, E> {
+        u32::try_from(value)
+            .map(argb_from_u32)
+            .map_err(|_| E::invalid_value(de::Unexpected::Unsigned(value), &self))
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<Self::Value, E> {
+        // JavaScript's bitwise operators produce ARGB numbers as signed 32-bit integers.
+        i32::try_from(value)
+            .map(|value| argb_from_u32(value as u32))
+            .map_err(|_| E::invalid_value(de::Unexpected::Signed(value), &self))
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+        argb_from_css(value).map_err(E::custom)
+    }
+}
+
+/// Serializes any value with colors as ARGB numbers, regardless of the current format. For use
+/// with `#[serde(with)]` on a field holding a [`Scheme`](crate::scheme::Scheme),
+/// [`Theme`](crate::utils::theme::Theme), or similar.
+///
+/// Deserialization accepts either form, as everywhere else.
+pub mod numeric {
+    use super::{with_color_format, ColorFormat};
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<T: Serialize, S: Serializer>(
+        value: &T,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        with_color_format(ColorFormat::Number, || value.serialize(serializer))
+    }
+
+    pub fn deserialize<'de, T: Deserialize<'de>, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<T, D::Error> {
+        T::deserialize(deserializer)
+    }
+}
+
+/// A color as a standalone serde value, for maps and other places a field attribute can't reach.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub(crate) struct SerdeColor(#[serde(with = "self")] pub [u8; 4]);
+
+/// Reads a reference fixture written by `testdata/generate_ts_fixtures.mjs` from the TypeScript
+/// library.
+#[cfg(test)]
+pub(crate) fn ts_fixture(name: &str) -> serde_json::Value {
+    let path = format!("{}/src/utils/testdata/{}", env!("CARGO_MANIFEST_DIR"), name);
+    let json = std::fs::read_to_string(&path).unwrap_or_else(|_| {
+        panic!("{path} is missing; generate it with src/utils/testdata/generate_ts_fixtures.mjs")
+    });
+    serde_json::from_str(&json).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats() {
+        let blue = SerdeColor([0xff, 0x42, 0x85, 0xf4]);
+        assert_eq!(serde_json::to_string(&blue).unwrap(), "\"#4285f4\"");
+        let number = with_color_format(ColorFormat::Number, || serde_json::to_string(&blue));
+        assert_eq!(number.unwrap(), "4282549748");
+        assert_eq!(serde_json::to_string(&blue).unwrap(), "\"#4285f4\"");
+    }
+
+    #[test]
+    fn parses() {
+        for json in [
+            "\"#4285f4\"",
+            "4282549748",
+            "-12417548",
+            "\"rgb(66 133 244)\"",
+        ] {
+            let color: SerdeColor = serde_json::from_str(json).unwrap();
+            assert_eq!(color, SerdeColor([0xff, 0x42, 0x85, 0xf4]));
+        }
+        assert!(serde_json::from_str::<SerdeColor>("\"#4285g4\"").is_err());
+        assert!(serde_json::from_str::<SerdeColor>("4294967296").is_err());
+        assert!(serde_json::from_str::<SerdeColor>("1.5").is_err());
+    }
+}
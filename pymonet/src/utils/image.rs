@@ -1,48 +1,126 @@
-//import {QuantizerCelebi} from '../quantize/quantizer_celebi';
+//! Finding source colors in images, used by the `image` feature.
+
+use crate::quantize::celebi;
 use crate::score::score;
-use color::argbFromRgb;
-
-/**
- * Get the source color from an image.
- *
- * @param image The image element
- * @return Source color - the color most suitable for creating a UI theme
- */
-/* export async function sourceColorFromImage(image: HTMLImageElement) { */
-
-  //pub fn sourceColorFromImage(image) -> {
-  /* Convert Image data to Pixel Array */
-  //const imageBytes = await new Promise<Uint8ClampedArray>((resolve, reject) => {
-  //  const canvas = document.createElement('canvas');
-  //  const context = canvas.getContext('2d');
-  //  if (!context) {
-  //      return reject(new Error('Could not get canvas context'));
-  //  }
-  //  image.onload = () => {
-  //    canvas.width = image.width;
-  //    canvas.height = image.height;
-  //    context.drawImage(image, 0, 0);
-  //    resolve(context.getImageData(0, 0, image.width, image.height).data);
-  //  };
-  //});
-
-  /* Convert Image data to Pixel Array */
-  //const pixels: number[] = [];
-  //for (let i = 0; i < imageBytes.length; i += 4) {
-  //  const r = imageBytes[i];
-  //  const g = imageBytes[i + 1];
-  //  const b = imageBytes[i + 2];
-  //  const a = imageBytes[i + 3];
-  //  if (a < 255) {
-  //    continue;
-  //  }
-  //  const argb = argbFromRgb(r, g, b);
-  //  pixels.push(argb);
-  //}
-
-  /* Convert Pixels to Material Colors */
-  // const result = QuantizerCelebi.quantize(pixels, 128);
-  // const ranked = Score.score(result);
-  // const top = ranked[0];
-  // return top;
-//}
+use std::fmt;
+
+/// Images are shrunk to fit in a square this size before quantizing. The result barely changes,
+/// and quantizing a full-size photo would take most of the time.
+const MAX_DIMENSION: u32 = 128;
+
+/// Why a source color could not be found in an image.
+#[derive(Debug)]
+pub enum ImageError {
+    /// The bytes are not an image in a supported format (PNG or JPEG).
+    Decode(::image::ImageError),
+    /// The image has no fully opaque pixels, ex. it is empty or transparent.
+    NoOpaquePixels,
+}
+
+impl fmt::Display for ImageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImageError::Decode(err) => write!(f, "could not decode image: {err}"),
+            ImageError::NoOpaquePixels => write!(f, "image has no opaque pixels"),
+        }
+    }
+}
+
+impl std::error::Error for ImageError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ImageError::Decode(err) => Some(err),
+            ImageError::NoOpaquePixels => None,
+        }
+    }
+}
+
+impl From<::image::ImageError> for ImageError {
+    fn from(err: ::image::ImageError) -> Self {
+        ImageError::Decode(err)
+    }
+}
+
+/// Get the source color from an image.
+///
+/// # Arguments
+///
+/// * `bytes`: An encoded image, ex. the contents of a PNG file.
+///
+/// # Returns
+///
+/// * Source color - the color most suitable for creating a UI theme, or why there isn't one.
+pub fn source_color_from_image_bytes(bytes: &[u8]) -> Result<[u8; 4], ImageError> {
+    let image = ::image::load_from_memory(bytes)?;
+    let image = if image.width() > MAX_DIMENSION || image.height() > MAX_DIMENSION {
+        image.thumbnail(MAX_DIMENSION, MAX_DIMENSION)
+    } else {
+        image
+    };
+    let pixels: Vec<[u8; 4]> = image
+        .to_rgba8()
+        .pixels()
+        .filter(|pixel| pixel[3] == 255)
+        .map(|pixel| [pixel[3], pixel[0], pixel[1], pixel[2]])
+        .collect();
+    source_color_from_pixels(&pixels).ok_or(ImageError::NoOpaquePixels)
+}
+
+/// Get the source color from the pixels of an image.
+///
+/// # Arguments
+///
+/// * `pixels`: Opaque colors of the image. Pixels with any transparency are ignored.
+///
+/// # Returns
+///
+/// * Source color, or `None` if there are no opaque pixels.
+pub fn source_color_from_pixels(pixels: &[[u8; 4]]) -> Option<[u8; 4]> {
+    let opaque: Vec<[u8; 4]> = pixels.iter().copied().filter(|p| p[0] == 255).collect();
+    if opaque.is_empty() {
+        return None;
+    }
+    let result = celebi::quantize(&opaque, 128);
+    score(&result).first().copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_errors() {
+        let err = source_color_from_image_bytes(b"not an image").unwrap_err();
+        assert!(matches!(err, ImageError::Decode(_)));
+        assert!(err.to_string().starts_with("could not decode image"));
+    }
+
+    #[test]
+    fn seed_image_keeps_its_own_colors() {
+        // QuantizerCelebi returns every color of an image with fewer than `max_colors` colors,
+        // with its pixel count, so upstream scores exactly these three colors.
+        let image = ::image::load_from_memory(include_bytes!("testdata/seed_2e7d32.png")).unwrap();
+        let pixels: Vec<[u8; 4]> = image
+            .to_rgba8()
+            .pixels()
+            .map(|pixel| [pixel[3], pixel[0], pixel[1], pixel[2]])
+            .collect();
+        let mut counts = std::collections::HashMap::new();
+        for &pixel in &pixels {
+            *counts.entry(pixel).or_insert(0) += 1;
+        }
+        assert_eq!(counts.len(), 3);
+        assert_eq!(celebi::quantize(&pixels, 128), counts);
+        assert_eq!(score(&counts)[0], [0xff, 0x2e, 0x7d, 0x32]);
+    }
+
+    #[test]
+    fn transparent_pixels() {
+        assert_eq!(source_color_from_pixels(&[]), None);
+        assert_eq!(source_color_from_pixels(&[[0x80, 0x42, 0x85, 0xf4]]), None);
+        assert_eq!(
+            source_color_from_pixels(&[[0x80, 0xff, 0x00, 0x00], [0xff, 0x42, 0x85, 0xf4]]),
+            Some([0xff, 0x42, 0x85, 0xf4])
+        );
+    }
+}
@@ -3,6 +3,23 @@
 //! Just what was said above. There are some calculations that are used in many
 //! of the other functions of this library. Instead of reinventing the wheel,...
 
+/// The signum function.
+///
+/// Unlike [`f64::signum`], zero (of either sign) maps to 0.0, as in the upstream utilities.
+///
+/// # Returns
+///
+/// * 1.0 if `num` > 0, -1.0 if `num` < 0, and 0.0 if `num` = 0 or is NaN
+pub fn signum(num: f64) -> f64 {
+    if num < 0.0 {
+        -1.0
+    } else if num > 0.0 {
+        1.0
+    } else {
+        0.0
+    }
+}
+
 /// Linear Interpolation function
 ///
 /// # Returns
@@ -12,6 +29,50 @@ pub fn lerp(start: f64, stop: f64, amount: f64) -> f64 {
     (1.0 - amount) * start + amount * stop
 }
 
+/// Single-precision [`lerp`], for pipelines that keep their buffers in `f32`.
+pub fn lerp_f32(start: f32, stop: f32, amount: f32) -> f32 {
+    (1.0 - amount) * start + amount * stop
+}
+
+/// Linear interpolation between two angles, along the shorter way around the circle.
+///
+/// For angles that are 180 degrees apart from each other, the path increases from `start`, as
+/// with [`rotation_direction`].
+///
+/// # Arguments
+///
+/// * `start`: The angle at `amount` = 0, in degrees
+/// * `stop`: The angle at `amount` = 1, in degrees
+/// * `amount`: How far to travel from `start` towards `stop`
+///
+/// # Returns
+///
+/// * An angle between 0.0 (inclusive) and 360.0 (exclusive). Going from 350 to 10 passes
+///   through 0, not 180.
+pub fn lerp_degrees(start: f64, stop: f64, amount: f64) -> f64 {
+    let start = sanitize_degrees_double(start);
+    let stop = sanitize_degrees_double(stop);
+    let distance = difference_degrees(start, stop) * rotation_direction(start, stop);
+    sanitize_degrees_double(start + distance * amount)
+}
+
+/// Clamps a floating-point number between two floating-point numbers.
+///
+/// Unlike [`f64::clamp`], this does not panic if `min` > `max`.
+///
+/// # Returns
+///
+/// * `input` when `min` <= `input` <= `max`, and either `min` or `max` otherwise.
+pub fn clamp_double(min: f64, max: f64, input: f64) -> f64 {
+    if input < min {
+        min
+    } else if input > max {
+        max
+    } else {
+        input
+    }
+}
+
 /// Sign of direction change needed to travel from one angle to another.
 ///
 /// For angles that are 180 degrees apart from each other, both directions have
@@ -27,7 +88,11 @@ pub fn lerp(start: f64, stop: f64, amount: f64) -> f64 {
 ///
 /// * -1 if decreasing from leads to the shortest travel distance,
 /// * 1 if increasing from leadsto the shortest travel distance.
+///
+/// Like [`sanitize_degrees_double`], an angle that is not finite is treated as 0.
 pub fn rotation_direction(from: f64, to: f64) -> f64 {
+    let from = sanitize_degrees_double(from);
+    let to = sanitize_degrees_double(to);
     let increasing_difference = sanitize_degrees_double(to - from);
     if increasing_difference <= 180.0 {
         1.0
@@ -46,8 +111,9 @@ pub fn rotation_direction(from: f64, to: f64) -> f64 {
 /// # Returns
 ///
 /// * The distance between the first position and second position as plotted on
-///   a circle.
+///   a circle. Like [`sanitize_degrees_double`], a position that is not finite is treated as 0.
 pub fn difference_degrees(a: f64, b: f64) -> f64 {
+    let (a, b) = (sanitize_degrees_double(a), sanitize_degrees_double(b));
     180.0 - ((a - b).abs() - 180.0).abs()
 }
 
@@ -59,13 +125,11 @@ pub fn difference_degrees(a: f64, b: f64) -> f64 {
 ///
 /// # Returns
 ///
-/// * A degree measure between 0 (inclusive) and 360 (exclusive).
-pub fn sanitize_degrees_int(mut degrees: i32) -> u32 {
-    degrees %= 360;
-    if degrees < 0 {
-        degrees += 360;
-    }
-    degrees as u32
+/// * A degree measure between 0 (inclusive) and 360 (exclusive), for every `i32` including
+///   `i32::MIN`. Multiples of 360 map to 0. The result stays `u32` rather than `u16` so that it
+///   can index hue histograms and be added to other `u32` hues without casts.
+pub fn sanitize_degrees_int(degrees: i32) -> u32 {
+    degrees.rem_euclid(360) as u32
 }
 
 /// Sanitizes a degree measure as a floating-point number.
@@ -76,8 +140,13 @@ pub fn sanitize_degrees_int(mut degrees: i32) -> u32 {
 ///
 /// # Returns
 ///
-/// * A degree measure between 0.0 (inclusive) and 360.0 (exclusive).
+/// * A degree measure between 0.0 (inclusive) and 360.0 (exclusive). NaN and infinite angles
+///   have no position on the circle, and are treated as 0.0 so that they cannot spread NaN
+///   through hue math.
 pub fn sanitize_degrees_double(mut degrees: f64) -> f64 {
+    if !degrees.is_finite() {
+        return 0.0;
+    }
     degrees %= 360.0;
     if degrees < 0.0 {
         degrees += 360.0;
@@ -96,6 +165,7 @@ pub fn sanitize_degrees_double(mut degrees: f64) -> f64 {
 ///
 /// * Some voodoo
 /// @TODO: Need to understand / document this a little better.
+#[allow(clippy::doc_lazy_continuation)]
 pub fn matrix_multiply(row: [f64; 3], matrix: [[f64; 3]; 3]) -> [f64; 3] {
     let a = row[0] * matrix[0][0] + row[1] * matrix[0][1] + row[2] * matrix[0][2];
     let b = row[0] * matrix[1][0] + row[1] * matrix[1][1] + row[2] * matrix[1][2];
@@ -103,12 +173,113 @@ pub fn matrix_multiply(row: [f64; 3], matrix: [[f64; 3]; 3]) -> [f64; 3] {
     [a, b, c]
 }
 
+/// Single-precision [`matrix_multiply`], for pipelines that keep their buffers in `f32`.
+pub fn matrix_multiply_f32(row: [f32; 3], matrix: [[f32; 3]; 3]) -> [f32; 3] {
+    let a = row[0] * matrix[0][0] + row[1] * matrix[0][1] + row[2] * matrix[0][2];
+    let b = row[0] * matrix[1][0] + row[1] * matrix[1][1] + row[2] * matrix[1][2];
+    let c = row[0] * matrix[2][0] + row[1] * matrix[2][1] + row[2] * matrix[2][2];
+    [a, b, c]
+}
+
+/// Rounds each element of a matrix to `f32`, for use with [`matrix_multiply_f32`]. Being a
+/// `const fn`, it can build `f32` matrix constants.
+pub const fn matrix_to_f32(m: [[f64; 3]; 3]) -> [[f32; 3]; 3] {
+    [
+        [m[0][0] as f32, m[0][1] as f32, m[0][2] as f32],
+        [m[1][0] as f32, m[1][1] as f32, m[1][2] as f32],
+        [m[2][0] as f32, m[2][1] as f32, m[2][2] as f32],
+    ]
+}
+
+/// Multiplies two 3x3 matrices.
+///
+/// # Arguments
+///
+/// * `a`: Left-hand matrix
+/// * `b`: Right-hand matrix
+///
+/// # Returns
+///
+/// * The product `a * b`, so that `matrix_multiply(row, multiply_matrices(a, b))` applies `b`
+///   first and then `a`.
+pub fn multiply_matrices(a: [[f64; 3]; 3], b: [[f64; 3]; 3]) -> [[f64; 3]; 3] {
+    let mut product = [[0.0; 3]; 3];
+    for (i, row) in product.iter_mut().enumerate() {
+        for (j, value) in row.iter_mut().enumerate() {
+            *value = a[i][0] * b[0][j] + a[i][1] * b[1][j] + a[i][2] * b[2][j];
+        }
+    }
+    product
+}
+
+/// Inverts a 3x3 matrix.
+///
+/// # Arguments
+///
+/// * `m`: Matrix to invert
+///
+/// # Returns
+///
+/// * The inverse of `m`, or `None` if `m` is singular, or so close to singular that its inverse
+///   would be dominated by rounding error.
+pub fn invert_matrix(m: [[f64; 3]; 3]) -> Option<[[f64; 3]; 3]> {
+    let cofactor =
+        |r0: usize, r1: usize, c0: usize, c1: usize| m[r0][c0] * m[r1][c1] - m[r0][c1] * m[r1][c0];
+    // Transposed cofactors, so that `adjugate * m` is `det * I`.
+    let adjugate = [
+        [
+            cofactor(1, 2, 1, 2),
+            -cofactor(0, 2, 1, 2),
+            cofactor(0, 1, 1, 2),
+        ],
+        [
+            -cofactor(1, 2, 0, 2),
+            cofactor(0, 2, 0, 2),
+            -cofactor(0, 1, 0, 2),
+        ],
+        [
+            cofactor(1, 2, 0, 1),
+            -cofactor(0, 2, 0, 1),
+            cofactor(0, 1, 0, 1),
+        ],
+    ];
+    let det = m[0][0] * adjugate[0][0] + m[0][1] * adjugate[1][0] + m[0][2] * adjugate[2][0];
+    let scale = m.iter().flatten().fold(0.0_f64, |max, v| max.max(v.abs()));
+    if !det.is_finite() || det.abs() <= 1e-12 * scale * scale * scale {
+        return None;
+    }
+    Some(adjugate.map(|row| row.map(|v| v / det)))
+}
+
+/// Asserts that two matrices undo each other: their product is the identity, and inverting
+/// `forward` gives `inverse` to within `tolerance`.
+#[cfg(test)]
+pub(crate) fn assert_matrices_are_inverses(
+    forward: [[f64; 3]; 3],
+    inverse: [[f64; 3]; 3],
+    tolerance: f64,
+) {
+    use assert_approx_eq::assert_approx_eq;
+
+    let product = multiply_matrices(forward, inverse);
+    let derived = invert_matrix(forward).unwrap();
+    for i in 0..3 {
+        for j in 0..3 {
+            let identity = if i == j { 1.0 } else { 0.0 };
+            assert_approx_eq!(product[i][j], identity, 1e-7);
+            assert_approx_eq!(derived[i][j], inverse[i][j], tolerance);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::utils::math::{
-        difference_degrees, lerp, matrix_multiply, rotation_direction, sanitize_degrees_double,
-        sanitize_degrees_int,
+        clamp_double, difference_degrees, invert_matrix, lerp, lerp_degrees, lerp_f32,
+        matrix_multiply, matrix_multiply_f32, matrix_to_f32, multiply_matrices, rotation_direction,
+        sanitize_degrees_double, sanitize_degrees_int, signum,
     };
+    use assert_approx_eq::assert_approx_eq;
 
     #[test]
     fn validate_lerp() {
@@ -116,6 +287,53 @@ mod tests {
         assert_eq!(lerp_val, 39.76777842);
     }
 
+    #[test]
+    fn validate_lerp_degrees() {
+        assert_eq!(lerp_degrees(10.0, 50.0, 0.25), 20.0);
+        assert_eq!(lerp_degrees(350.0, 10.0, 0.25), 355.0);
+        assert_eq!(lerp_degrees(350.0, 10.0, 0.5), 0.0);
+        assert_eq!(lerp_degrees(350.0, 10.0, 0.75), 5.0);
+        assert_eq!(lerp_degrees(10.0, 350.0, 0.75), 355.0);
+        assert_eq!(lerp_degrees(10.0, 350.0, 1.0), 350.0);
+        assert_eq!(lerp_degrees(-10.0, 370.0, 0.5), 0.0);
+        // 180 degrees apart: either way is shortest, and the path increases.
+        assert_eq!(lerp_degrees(0.0, 180.0, 0.5), 90.0);
+        assert_eq!(lerp_degrees(180.0, 0.0, 0.5), 270.0);
+    }
+
+    #[test]
+    fn validate_signum() {
+        assert_eq!(signum(0.0), 0.0);
+        assert_eq!(signum(-0.0), 0.0);
+        assert_eq!(signum(f64::NAN), 0.0);
+        assert_eq!(signum(1e-300), 1.0);
+        assert_eq!(signum(-1e-300), -1.0);
+        assert_eq!(signum(f64::INFINITY), 1.0);
+        assert_eq!(signum(f64::NEG_INFINITY), -1.0);
+        // The trap: the standard library disagrees at zero.
+        assert_eq!(0.0_f64.signum(), 1.0);
+    }
+
+    #[test]
+    fn validate_clamp() {
+        assert_eq!(clamp_double(0.0, 1.0, -0.5), 0.0);
+        assert_eq!(clamp_double(0.0, 1.0, 0.25), 0.25);
+        assert_eq!(clamp_double(0.0, 1.0, 1.5), 1.0);
+        assert_eq!(clamp_double(0.0, 100.0, f64::INFINITY), 100.0);
+        assert!(clamp_double(0.0, 1.0, f64::NAN).is_nan());
+    }
+
+    #[test]
+    fn validate_f32_variants() {
+        assert_eq!(lerp_f32(12.34567, 34.5678, 1.234), 39.767776);
+        let row = [1.25, 2.50, 3.75];
+        let matrix = [[1.0, 1.0, 1.0], [2.0, 2.0, 2.0], [3.0, 3.0, 3.0]];
+        assert_eq!(
+            matrix_multiply_f32(row, matrix_to_f32(matrix)),
+            [7.5, 15.0, 22.5]
+        );
+    }
+
     #[test]
     fn validate_rotation_direction() {
         let pos_rotation = rotation_direction(12.34567, 160.99876);
@@ -142,6 +360,23 @@ mod tests {
         assert_eq!(sanitized_int, 264);
     }
 
+    #[test]
+    fn validate_sanitize_degrees_int_extremes() {
+        assert_eq!(sanitize_degrees_int(0), 0);
+        assert_eq!(sanitize_degrees_int(359), 359);
+        assert_eq!(sanitize_degrees_int(-1), 359);
+        assert_eq!(sanitize_degrees_int(i32::MAX), 127);
+        assert_eq!(sanitize_degrees_int(i32::MIN), 232);
+        assert_eq!(sanitize_degrees_int(i32::MIN + 1), 233);
+        for multiple in [-720, -360, 360, 720, 360 * 5_965_232, -360 * 5_965_232] {
+            assert_eq!(sanitize_degrees_int(multiple), 0);
+        }
+        for degrees in -3600..=3600 {
+            let expected = (degrees as i64 % 360 + 360) % 360;
+            assert_eq!(sanitize_degrees_int(degrees) as i64, expected);
+        }
+    }
+
     #[test]
     fn validate_sanitize_degrees_double_pos() {
         let sanitized_dbl = sanitize_degrees_double(15.1234);
@@ -154,6 +389,19 @@ mod tests {
         assert_eq!(sanitized_dbl, 263.6544);
     }
 
+    #[test]
+    fn validate_non_finite_angles() {
+        for bad in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            assert_eq!(sanitize_degrees_double(bad), 0.0);
+            assert_eq!(difference_degrees(bad, 10.0), 10.0);
+            assert_eq!(difference_degrees(350.0, bad), 10.0);
+            assert_eq!(rotation_direction(bad, 10.0), 1.0);
+            assert_eq!(rotation_direction(10.0, bad), -1.0);
+            assert_eq!(lerp_degrees(bad, 350.0, 0.5), 355.0);
+        }
+        assert_eq!(difference_degrees(720.0, 10.0), 10.0);
+    }
+
     #[test]
     fn validate_matrix_multiply() {
         let row = [1.25, 2.50, 3.75];
@@ -163,4 +411,59 @@ mod tests {
         assert_eq!(multiplied[1], 15.0);
         assert_eq!(multiplied[2], 22.5);
     }
+
+    const IDENTITY: [[f64; 3]; 3] = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+
+    #[test]
+    fn validate_multiply_matrices() {
+        let a = [[1.0, 2.0, 3.0], [4.0, 5.0, 6.0], [7.0, 8.0, 9.0]];
+        let b = [[9.0, 8.0, 7.0], [6.0, 5.0, 4.0], [3.0, 2.0, 1.0]];
+        assert_eq!(
+            multiply_matrices(a, b),
+            [[30.0, 24.0, 18.0], [84.0, 69.0, 54.0], [138.0, 114.0, 90.0]]
+        );
+        assert_eq!(multiply_matrices(a, IDENTITY), a);
+        let row = [1.25, 2.50, 3.75];
+        assert_eq!(
+            matrix_multiply(row, multiply_matrices(a, b)),
+            matrix_multiply(matrix_multiply(row, b), a)
+        );
+    }
+
+    #[test]
+    fn validate_invert_matrix() {
+        let m = [[2.0, 0.0, 1.0], [1.0, 3.0, 2.0], [1.0, 1.0, 2.0]];
+        let inverse = invert_matrix(m).unwrap();
+        // det(m) = 6, so the inverse is the adjugate over 6.
+        let expected = [[4.0, 1.0, -3.0], [0.0, 3.0, -3.0], [-2.0, -2.0, 6.0]];
+        for (row, expected_row) in inverse.iter().zip(expected) {
+            for (value, expected_value) in row.iter().zip(expected_row) {
+                assert_approx_eq!(*value, expected_value / 6.0, 1e-15);
+            }
+        }
+        for (product, identity) in [multiply_matrices(m, inverse), multiply_matrices(inverse, m)]
+            .iter()
+            .flatten()
+            .flatten()
+            .zip(IDENTITY.iter().flatten().cycle())
+        {
+            assert_approx_eq!(*product, *identity, 1e-15);
+        }
+    }
+
+    #[test]
+    fn validate_invert_matrix_singular() {
+        assert_eq!(invert_matrix([[0.0; 3]; 3]), None);
+        assert_eq!(
+            invert_matrix([[1.0, 2.0, 3.0], [2.0, 4.0, 6.0], [0.0, 1.0, 1.0]]),
+            None
+        );
+        // Singular, but rounding leaves a tiny nonzero determinant.
+        assert_eq!(
+            invert_matrix([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0], [7.0, 8.0, 9.0]]),
+            None
+        );
+        assert_eq!(invert_matrix([[f64::NAN; 3]; 3]), None);
+        assert_eq!(invert_matrix(IDENTITY), Some(IDENTITY));
+    }
 }
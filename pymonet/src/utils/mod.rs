@@ -1,4 +1,8 @@
 pub mod color;
+#[cfg(feature = "serde")]
+pub mod color_format;
+#[cfg(feature = "image")]
+pub mod image;
 pub mod math;
 pub mod string;
 pub mod theme;
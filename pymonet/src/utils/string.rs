@@ -2,20 +2,114 @@
 //!
 //! This library makes the assumption that all hex strings supplied and returned
 //! adhere to CSS standards for hex color strings. This means that the library
-//! supports short-code colors (3 characters like #FFF for white), standard RGB
-//! color strings (6 characters like #FF0000 for red), and RGBA color strings to
-//! support an alpha channel (8 characters like #C6C6C680 for a gray that is
-//! partly translucent).
+//! supports short-code colors (3 characters like #FFF for white, or 4 like
+//! #09C8 for a translucent teal), standard RGB color strings (6 characters like
+//! #FF0000 for red), and RGBA color strings to support an alpha channel (8
+//! characters like #C6C6C680 for a gray that is partly translucent).
 //!
 //! NOTE: Any alpha channel in hex colors supplied and returned is expected to
 //! be the last value in the string. This is compliant with the standard form
-//! used in CSS / HTML.
+//! used in CSS / HTML. The one exception is a `0x` prefix, as in `0xFF4285F4`,
+//! which is read like a Rust integer literal with alpha first.
+//!
+//! [`argb_from_css`] additionally accepts CSS `rgb()`, `hsl()` and `hwb()`
+//! functional notation, as copied out of browser devtools, and CSS named colors.
 
 // rustimport:pyo3
-use super::color::{alpha_from_argb, blue_from_argb, green_from_argb, red_from_argb};
-use hex::FromHex;
+use super::color::{
+    alpha_from_argb, argb_from_hsl, argb_from_hwb, argb_from_rgb, blue_from_argb, green_from_argb,
+    hsl_from_argb, red_from_argb, with_alpha,
+};
+use super::math::sanitize_degrees_double;
+use crate::hct::cam16::Cam16;
 use pyo3::prelude::*;
+use std::fmt;
+
+/// Error returned when a color string cannot be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseColorError {
+    /// The hex string does not contain 3, 4, 6, or 8 hex characters.
+    InvalidLength(usize),
+    /// The hex string contains a character that is not a hex digit, at the given byte offset
+    /// into the string.
+    InvalidHexDigit { digit: char, position: usize },
+    /// The CSS functional notation is malformed. Holds the offending part of the string.
+    InvalidCss(String),
+    /// The hex string starts with more than one `#`.
+    RepeatedHash,
+}
+
+impl fmt::Display for ParseColorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseColorError::InvalidLength(len) => {
+                write!(f, "invalid hex color length {len}, expected 3, 4, 6, or 8")
+            }
+            ParseColorError::InvalidHexDigit { digit, position } => {
+                write!(f, "invalid hex digit '{digit}' at position {position}")
+            }
+            ParseColorError::InvalidCss(part) => write!(f, "invalid CSS color '{part}'"),
+            ParseColorError::RepeatedHash => write!(f, "hex color starts with more than one '#'"),
+        }
+    }
+}
+
+impl std::error::Error for ParseColorError {}
+
+/// When [`hex_from_argb_with`] writes the alpha channel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HexAlpha {
+    /// Never write alpha, dropping any transparency.
+    Never,
+    /// Write alpha only when the color is not fully opaque.
+    #[default]
+    Auto,
+    /// Always write alpha, even for opaque colors.
+    Always,
+}
+
+/// Where [`hex_from_argb_with`] writes the alpha channel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HexAlphaPosition {
+    /// `#AARRGGBB`, as used by Android resources.
+    Leading,
+    /// `#RRGGBBAA`, as used by CSS.
+    #[default]
+    Trailing,
+}
+
+/// Options for [`hex_from_argb_with`]. The default matches [`hex_from_argb`]: lowercase, with a
+/// leading `#`, and a trailing alpha only for translucent colors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HexFormat {
+    pub uppercase: bool,
+    pub include_hash: bool,
+    pub alpha: HexAlpha,
+    pub alpha_position: HexAlphaPosition,
+}
 
+impl HexFormat {
+    /// `#AARRGGBB` in uppercase, as used by Android color resources.
+    pub fn android() -> HexFormat {
+        HexFormat {
+            uppercase: true,
+            include_hash: true,
+            alpha: HexAlpha::Always,
+            alpha_position: HexAlphaPosition::Leading,
+        }
+    }
+}
+
+impl Default for HexFormat {
+    fn default() -> Self {
+        HexFormat {
+            uppercase: false,
+            include_hash: true,
+            alpha: HexAlpha::Auto,
+            alpha_position: HexAlphaPosition::Trailing,
+        }
+    }
+}
 
 /// Returns a hex RGB string representation of an ARGB numeric.
 ///
@@ -28,55 +122,548 @@ use pyo3::prelude::*;
 /// * Hex string representing color, ex. #ff0000 for red.
 #[pyfunction]
 pub fn hex_from_argb(argb: [u8; 4]) -> String {
-    let a: u8 = alpha_from_argb(argb);
-    let r = red_from_argb(argb);
-    let g = green_from_argb(argb);
-    let b = blue_from_argb(argb);
-    let hex_value = if a < 255 {
-        hex::encode([r, g, b, a])
+    hex_from_argb_with(argb, HexFormat::default())
+}
+
+/// Returns a hex string representation of an ARGB numeric, formatted as configured.
+///
+/// # Arguments
+///
+/// * `argb`: ARGB representation of a color.
+/// * `options`: Case, `#` prefix, and whether and where to write alpha.
+///
+/// # Returns
+///
+/// * Hex string representing color, ex. #FFFF0000 for red with [`HexFormat::android`].
+pub fn hex_from_argb_with(argb: [u8; 4], options: HexFormat) -> String {
+    let a = alpha_from_argb(argb);
+    let rgb = [
+        red_from_argb(argb),
+        green_from_argb(argb),
+        blue_from_argb(argb),
+    ];
+    let with_alpha = match options.alpha {
+        HexAlpha::Never => false,
+        HexAlpha::Auto => a < 255,
+        HexAlpha::Always => true,
+    };
+    let hex_value = match (with_alpha, options.alpha_position) {
+        (false, _) => hex::encode(rgb),
+        (true, HexAlphaPosition::Leading) => hex::encode([a, rgb[0], rgb[1], rgb[2]]),
+        (true, HexAlphaPosition::Trailing) => hex::encode([rgb[0], rgb[1], rgb[2], a]),
+    };
+    let hex_value = if options.uppercase {
+        hex_value.to_ascii_uppercase()
     } else {
-        hex::encode([r, g, b])
+        hex_value
     };
-    String::from("#") + &hex_value
+    if options.include_hash {
+        String::from("#") + &hex_value
+    } else {
+        hex_value
+    }
+}
+
+/// Returns CSS `rgb()` notation for an ARGB numeric, in the modern space-separated syntax.
+///
+/// # Arguments
+///
+/// * `argb`: ARGB representation of a color.
+/// * `precision`: Number of decimal places written for alpha.
+///
+/// # Returns
+///
+/// * CSS string representing color, ex. `rgb(66 133 244)`, or `rgb(66 133 244 / 0.50)` for a
+///   translucent color with a precision of 2.
+#[pyfunction]
+pub fn css_rgb_from_argb(argb: [u8; 4], precision: usize) -> String {
+    let (r, g, b) = (
+        red_from_argb(argb),
+        green_from_argb(argb),
+        blue_from_argb(argb),
+    );
+    format!("rgb({r} {g} {b}{})", css_alpha(argb, precision))
+}
+
+/// Returns CSS `hsl()` notation for an ARGB numeric, in the modern space-separated syntax.
+///
+/// # Arguments
+///
+/// * `argb`: ARGB representation of a color.
+/// * `precision`: Number of decimal places written for hue, saturation, lightness, and alpha.
+///
+/// # Returns
+///
+/// * CSS string representing color, ex. `hsl(217.4 89.0% 60.8%)` with a precision of 1.
+#[pyfunction]
+pub fn css_hsl_from_argb(argb: [u8; 4], precision: usize) -> String {
+    let [h, s, l] = hsl_from_argb(argb);
+    format!(
+        "hsl({h:.precision$} {:.precision$}% {:.precision$}%{})",
+        s * 100.0,
+        l * 100.0,
+        css_alpha(argb, precision)
+    )
+}
+
+/// The ` / alpha` suffix of CSS functional notation, empty for opaque colors.
+fn css_alpha(argb: [u8; 4], precision: usize) -> String {
+    let a = alpha_from_argb(argb);
+    if a < 255 {
+        format!(" / {:.precision$}", a as f64 / 255.0)
+    } else {
+        String::new()
+    }
 }
 
 /// Returns an ARGB numeric representation of a hex RGB(A) string
 ///
 /// # Arguments
 ///
-/// * `hex`: String representing color as hex code. Accepts strings with or without leading #, and string representing the color using 3, 6, or 8 hex characters.
+/// * `hex`: String representing color as hex code. Accepts strings with or without leading #, and string representing the color using 3, 4, 6, or 8 hex characters.
 ///
 /// # Returns
 ///
 /// * ARGB representation of color in a [u8; 4] package.
+///
+/// # Panics
+///
+/// * If the string is not a valid hex color. Use [`try_argb_from_hex`] to
+///   handle invalid input instead.
 #[pyfunction]
 pub fn argb_from_hex(hex: String) -> [u8; 4] {
-    let trimmed_hex = hex.replace('#', "");
-    let mut a: u8 = 255;
-    let r: u8;
-    let g: u8;
-    let b: u8;
-
-    match trimmed_hex.len() {
-        3 => {
-            r = <[u8; 1]>::from_hex(trimmed_hex[0..1].repeat(2)).unwrap()[0];
-            g = <[u8; 1]>::from_hex(trimmed_hex[1..2].repeat(2)).unwrap()[0];
-            b = <[u8; 1]>::from_hex(trimmed_hex[2..].repeat(2)).unwrap()[0];
+    match try_argb_from_hex(&hex) {
+        Ok(argb) => argb,
+        Err(err) => panic!("Invalid hex color string supplied: {err}"),
+    }
+}
+
+/// Returns an ARGB numeric representation of a hex RGB(A) string, or an error
+/// if the string is not a valid hex color.
+///
+/// # Arguments
+///
+/// * `hex`: String representing color as hex code. Accepts strings with or without leading #, and string representing the color using 3, 4, 6, or 8 hex characters.
+///   Bare digits, ex. `4285f4`, are read as if they had the `#`, but more than one `#` is an
+///   error. A `0x` prefix is also accepted, in which case the string is read like a Rust integer
+///   literal: 6 characters as 0xRRGGBB, or 8 characters as 0xAARRGGBB with alpha first.
+///
+/// # Returns
+///
+/// * ARGB representation of color in a [u8; 4] package, or the reason the
+///   string could not be parsed. Invalid digits are reported with their byte offset into `hex`.
+pub fn try_argb_from_hex(hex: &str) -> Result<[u8; 4], ParseColorError> {
+    let (prefix_len, alpha_first) = if hex.starts_with("0x") || hex.starts_with("0X") {
+        (2, true)
+    } else if hex.starts_with("##") {
+        return Err(ParseColorError::RepeatedHash);
+    } else if hex.starts_with('#') {
+        (1, false)
+    } else {
+        (0, false)
+    };
+    let digits = hex[prefix_len..]
+        .char_indices()
+        .map(|(i, c)| {
+            c.to_digit(16)
+                .map(|d| d as u8)
+                .ok_or(ParseColorError::InvalidHexDigit {
+                    digit: c,
+                    position: prefix_len + i,
+                })
+        })
+        .collect::<Result<Vec<u8>, ParseColorError>>()?;
+
+    if alpha_first {
+        return match digits[..] {
+            [r1, r2, g1, g2, b1, b2] => Ok([255, r1 << 4 | r2, g1 << 4 | g2, b1 << 4 | b2]),
+            [a1, a2, r1, r2, g1, g2, b1, b2] => {
+                Ok([a1 << 4 | a2, r1 << 4 | r2, g1 << 4 | g2, b1 << 4 | b2])
+            }
+            _ => Err(ParseColorError::InvalidLength(digits.len())),
+        };
+    }
+    match digits[..] {
+        [r, g, b] => Ok([255, r * 17, g * 17, b * 17]),
+        [r, g, b, a] => Ok([a * 17, r * 17, g * 17, b * 17]),
+        [r1, r2, g1, g2, b1, b2] => Ok([255, r1 << 4 | r2, g1 << 4 | g2, b1 << 4 | b2]),
+        [r1, r2, g1, g2, b1, b2, a1, a2] => {
+            Ok([a1 << 4 | a2, r1 << 4 | r2, g1 << 4 | g2, b1 << 4 | b2])
         }
-        6 => {
-            [r, g, b] = <[u8; 3]>::from_hex(trimmed_hex).unwrap();
+        _ => Err(ParseColorError::InvalidLength(digits.len())),
+    }
+}
+
+/// Returns an ARGB numeric representation of a hex RGB(A) string, evaluated at compile time when
+/// used in a constant. Prefer the [`argb!`](crate::argb) macro, which always is.
+///
+/// # Arguments
+///
+/// * `hex`: String representing color as hex code, with or without leading #, using 3, 4, 6, or
+///   8 hex characters. Alpha, if any, is last, as with [`try_argb_from_hex`].
+///
+/// # Returns
+///
+/// * ARGB representation of color in a [u8; 4] package.
+///
+/// # Panics
+///
+/// * If the string is not a valid hex color. In a constant, this is a compile error:
+///
+/// ```compile_fail
+/// const BRAND: [u8; 4] = pymonet::utils::string::argb_from_hex_const("#4285g4");
+/// ```
+pub const fn argb_from_hex_const(hex: &str) -> [u8; 4] {
+    let bytes = hex.as_bytes();
+    let start = if !bytes.is_empty() && bytes[0] == b'#' {
+        1
+    } else {
+        0
+    };
+    let mut digits = [0u8; 8];
+    let len = bytes.len() - start;
+    if len != 3 && len != 4 && len != 6 && len != 8 {
+        panic!("hex color literal must have 3, 4, 6, or 8 hex digits");
+    }
+    let mut i = 0;
+    while i < len {
+        digits[i] = match bytes[start + i] {
+            c @ b'0'..=b'9' => c - b'0',
+            c @ b'a'..=b'f' => c - b'a' + 10,
+            c @ b'A'..=b'F' => c - b'A' + 10,
+            _ => panic!("invalid hex digit in hex color literal"),
+        };
+        i += 1;
+    }
+    let d = digits;
+    match len {
+        3 => [255, d[0] * 17, d[1] * 17, d[2] * 17],
+        4 => [d[3] * 17, d[0] * 17, d[1] * 17, d[2] * 17],
+        6 => [255, d[0] << 4 | d[1], d[2] << 4 | d[3], d[4] << 4 | d[5]],
+        _ => [
+            d[6] << 4 | d[7],
+            d[0] << 4 | d[1],
+            d[2] << 4 | d[3],
+            d[4] << 4 | d[5],
+        ],
+    }
+}
+
+/// Returns an ARGB numeric representation of a CSS color, or an error if the string is not a
+/// valid CSS color.
+///
+/// # Arguments
+///
+/// * `color`: A hex color accepted by [`try_argb_from_hex`] that starts with `#` or `0x`, as
+///   bare digits like `bad` are not CSS, a CSS named color like
+///   `rebeccapurple`, or CSS functional notation like `rgb(66, 133, 244)`,
+///   `rgba(66, 133, 244, 0.5)`, `rgb(26% 52% 96% / 50%)`, `hsl(217deg 89% 61%)` or
+///   `hwb(0.6turn 26% 4%)`. Components may be numbers or percentages, hues may use any CSS
+///   angle unit, and out-of-range values are clamped as CSS does.
+///
+/// # Returns
+///
+/// * ARGB representation of color in a [u8; 4] package, or the reason the
+///   string could not be parsed.
+pub fn argb_from_css(color: &str) -> Result<[u8; 4], ParseColorError> {
+    let color = color.trim();
+    let lower = color.to_ascii_lowercase();
+    let Some((function, rest)) = lower.split_once('(') else {
+        if let Some(argb) = argb_from_named_color(color) {
+            return Ok(argb);
+        }
+        if !color.starts_with('#') && !lower.starts_with("0x") {
+            return Err(ParseColorError::InvalidCss(color.to_string()));
+        }
+        return try_argb_from_hex(color);
+    };
+    let arguments = rest
+        .strip_suffix(')')
+        .ok_or_else(|| ParseColorError::InvalidCss(color.to_string()))?;
+
+    let (components, alpha): (Vec<&str>, Option<&str>) = if arguments.contains(',') {
+        let mut parts: Vec<&str> = arguments.split(',').map(str::trim).collect();
+        let alpha = if parts.len() == 4 { parts.pop() } else { None };
+        (parts, alpha)
+    } else {
+        let (components, alpha) = match arguments.split_once('/') {
+            Some((components, alpha)) => (components, Some(alpha.trim())),
+            None => (arguments, None),
+        };
+        (components.split_whitespace().collect(), alpha)
+    };
+    let [first, second, third] = components[..] else {
+        return Err(ParseColorError::InvalidCss(arguments.trim().to_string()));
+    };
+
+    let alpha = match alpha {
+        Some(alpha) => (css_component(alpha, 1.0)? * 255.0).round() as u8,
+        None => 255,
+    };
+    let argb = match function.trim_end() {
+        "rgb" | "rgba" => {
+            let channel = |value| Ok((css_component(value, 255.0)? * 255.0).round() as u8);
+            argb_from_rgb([channel(first)?, channel(second)?, channel(third)?])
         }
-        8 => {
-            [r, g, b, a] = <[u8; 4]>::from_hex(trimmed_hex).unwrap();
+        "hsl" | "hsla" => argb_from_hsl(
+            css_hue(first)?,
+            css_component(second, 100.0)?,
+            css_component(third, 100.0)?,
+        ),
+        "hwb" => argb_from_hwb(
+            css_hue(first)?,
+            css_component(second, 100.0)?,
+            css_component(third, 100.0)?,
+        ),
+        _ => return Err(ParseColorError::InvalidCss(function.to_string())),
+    };
+    Ok(with_alpha(argb, alpha))
+}
+
+/// Returns the ARGB value of a CSS named color, such as `rebeccapurple` or `transparent`.
+///
+/// # Arguments
+///
+/// * `name`: The name of the color, in any case.
+///
+/// # Returns
+///
+/// * ARGB representation of the color, or `None` if CSS has no color by that name.
+pub fn argb_from_named_color(name: &str) -> Option<[u8; 4]> {
+    let name = name.to_ascii_lowercase();
+    if name == "transparent" {
+        return Some([0, 0, 0, 0]);
+    }
+    NAMED_COLORS
+        .binary_search_by(|(candidate, _)| candidate.cmp(&name.as_str()))
+        .ok()
+        .map(|index| argb_from_rgb(NAMED_COLORS[index].1))
+}
+
+/// Returns the CSS named color that looks most like a color, ignoring alpha.
+///
+/// Colors are compared by [`Cam16::distance`]. Where CSS has several names for one color, such
+/// as `gray` and `grey`, the alphabetically first is returned.
+///
+/// # Arguments
+///
+/// * `argb`: ARGB representation of a color.
+///
+/// # Returns
+///
+/// * The name of the closest CSS named color, e.g. "steelblue".
+#[pyfunction]
+pub fn nearest_named_color(argb: [u8; 4]) -> &'static str {
+    let cam = Cam16::from_argb(argb);
+    let mut nearest = ("", f64::INFINITY);
+    for (name, rgb) in NAMED_COLORS {
+        let distance = cam.distance(&Cam16::from_argb(argb_from_rgb(rgb)));
+        if distance < nearest.1 {
+            nearest = (name, distance);
         }
-        _ => panic!("Invalid hex color string supplied."),
     }
-    [a, r, g, b]
+    nearest.0
 }
 
+/// Parses a CSS hue, in `deg`, `grad`, `rad`, or `turn` units, into degrees [0, 360).
+///
+/// # Arguments
+///
+/// * `value`: An angle; a bare number is in degrees.
+fn css_hue(value: &str) -> Result<f64, ParseColorError> {
+    let (number, degrees_per_unit) = [
+        ("deg", 1.0),
+        ("grad", 0.9),
+        ("rad", 180.0 / std::f64::consts::PI),
+        ("turn", 360.0),
+    ]
+    .into_iter()
+    .find_map(|(unit, scale)| value.strip_suffix(unit).map(|number| (number, scale)))
+    .unwrap_or((value, 1.0));
+    match number.trim().parse::<f64>() {
+        Ok(hue) if hue.is_finite() => Ok(sanitize_degrees_double(hue * degrees_per_unit)),
+        _ => Err(ParseColorError::InvalidCss(value.to_string())),
+    }
+}
+
+/// Parses one component of CSS functional notation into [0, 1].
+///
+/// # Arguments
+///
+/// * `value`: A number, or a percentage of the full range.
+/// * `full`: The number that represents the full range, e.g. 255 for RGB channels.
+fn css_component(value: &str, full: f64) -> Result<f64, ParseColorError> {
+    let invalid = || ParseColorError::InvalidCss(value.to_string());
+    let fraction = match value.strip_suffix('%') {
+        Some(percent) => percent.trim().parse::<f64>().map_err(|_| invalid())? / 100.0,
+        None => value.parse::<f64>().map_err(|_| invalid())? / full,
+    };
+    if fraction.is_nan() {
+        return Err(invalid());
+    }
+    Ok(fraction.clamp(0.0, 1.0))
+}
+
+/// The CSS named colors, sorted by name for binary search.
+const NAMED_COLORS: [(&str, [u8; 3]); 148] = [
+    ("aliceblue", [0xf0, 0xf8, 0xff]),
+    ("antiquewhite", [0xfa, 0xeb, 0xd7]),
+    ("aqua", [0x00, 0xff, 0xff]),
+    ("aquamarine", [0x7f, 0xff, 0xd4]),
+    ("azure", [0xf0, 0xff, 0xff]),
+    ("beige", [0xf5, 0xf5, 0xdc]),
+    ("bisque", [0xff, 0xe4, 0xc4]),
+    ("black", [0x00, 0x00, 0x00]),
+    ("blanchedalmond", [0xff, 0xeb, 0xcd]),
+    ("blue", [0x00, 0x00, 0xff]),
+    ("blueviolet", [0x8a, 0x2b, 0xe2]),
+    ("brown", [0xa5, 0x2a, 0x2a]),
+    ("burlywood", [0xde, 0xb8, 0x87]),
+    ("cadetblue", [0x5f, 0x9e, 0xa0]),
+    ("chartreuse", [0x7f, 0xff, 0x00]),
+    ("chocolate", [0xd2, 0x69, 0x1e]),
+    ("coral", [0xff, 0x7f, 0x50]),
+    ("cornflowerblue", [0x64, 0x95, 0xed]),
+    ("cornsilk", [0xff, 0xf8, 0xdc]),
+    ("crimson", [0xdc, 0x14, 0x3c]),
+    ("cyan", [0x00, 0xff, 0xff]),
+    ("darkblue", [0x00, 0x00, 0x8b]),
+    ("darkcyan", [0x00, 0x8b, 0x8b]),
+    ("darkgoldenrod", [0xb8, 0x86, 0x0b]),
+    ("darkgray", [0xa9, 0xa9, 0xa9]),
+    ("darkgreen", [0x00, 0x64, 0x00]),
+    ("darkgrey", [0xa9, 0xa9, 0xa9]),
+    ("darkkhaki", [0xbd, 0xb7, 0x6b]),
+    ("darkmagenta", [0x8b, 0x00, 0x8b]),
+    ("darkolivegreen", [0x55, 0x6b, 0x2f]),
+    ("darkorange", [0xff, 0x8c, 0x00]),
+    ("darkorchid", [0x99, 0x32, 0xcc]),
+    ("darkred", [0x8b, 0x00, 0x00]),
+    ("darksalmon", [0xe9, 0x96, 0x7a]),
+    ("darkseagreen", [0x8f, 0xbc, 0x8f]),
+    ("darkslateblue", [0x48, 0x3d, 0x8b]),
+    ("darkslategray", [0x2f, 0x4f, 0x4f]),
+    ("darkslategrey", [0x2f, 0x4f, 0x4f]),
+    ("darkturquoise", [0x00, 0xce, 0xd1]),
+    ("darkviolet", [0x94, 0x00, 0xd3]),
+    ("deeppink", [0xff, 0x14, 0x93]),
+    ("deepskyblue", [0x00, 0xbf, 0xff]),
+    ("dimgray", [0x69, 0x69, 0x69]),
+    ("dimgrey", [0x69, 0x69, 0x69]),
+    ("dodgerblue", [0x1e, 0x90, 0xff]),
+    ("firebrick", [0xb2, 0x22, 0x22]),
+    ("floralwhite", [0xff, 0xfa, 0xf0]),
+    ("forestgreen", [0x22, 0x8b, 0x22]),
+    ("fuchsia", [0xff, 0x00, 0xff]),
+    ("gainsboro", [0xdc, 0xdc, 0xdc]),
+    ("ghostwhite", [0xf8, 0xf8, 0xff]),
+    ("gold", [0xff, 0xd7, 0x00]),
+    ("goldenrod", [0xda, 0xa5, 0x20]),
+    ("gray", [0x80, 0x80, 0x80]),
+    ("green", [0x00, 0x80, 0x00]),
+    ("greenyellow", [0xad, 0xff, 0x2f]),
+    ("grey", [0x80, 0x80, 0x80]),
+    ("honeydew", [0xf0, 0xff, 0xf0]),
+    ("hotpink", [0xff, 0x69, 0xb4]),
+    ("indianred", [0xcd, 0x5c, 0x5c]),
+    ("indigo", [0x4b, 0x00, 0x82]),
+    ("ivory", [0xff, 0xff, 0xf0]),
+    ("khaki", [0xf0, 0xe6, 0x8c]),
+    ("lavender", [0xe6, 0xe6, 0xfa]),
+    ("lavenderblush", [0xff, 0xf0, 0xf5]),
+    ("lawngreen", [0x7c, 0xfc, 0x00]),
+    ("lemonchiffon", [0xff, 0xfa, 0xcd]),
+    ("lightblue", [0xad, 0xd8, 0xe6]),
+    ("lightcoral", [0xf0, 0x80, 0x80]),
+    ("lightcyan", [0xe0, 0xff, 0xff]),
+    ("lightgoldenrodyellow", [0xfa, 0xfa, 0xd2]),
+    ("lightgray", [0xd3, 0xd3, 0xd3]),
+    ("lightgreen", [0x90, 0xee, 0x90]),
+    ("lightgrey", [0xd3, 0xd3, 0xd3]),
+    ("lightpink", [0xff, 0xb6, 0xc1]),
+    ("lightsalmon", [0xff, 0xa0, 0x7a]),
+    ("lightseagreen", [0x20, 0xb2, 0xaa]),
+    ("lightskyblue", [0x87, 0xce, 0xfa]),
+    ("lightslategray", [0x77, 0x88, 0x99]),
+    ("lightslategrey", [0x77, 0x88, 0x99]),
+    ("lightsteelblue", [0xb0, 0xc4, 0xde]),
+    ("lightyellow", [0xff, 0xff, 0xe0]),
+    ("lime", [0x00, 0xff, 0x00]),
+    ("limegreen", [0x32, 0xcd, 0x32]),
+    ("linen", [0xfa, 0xf0, 0xe6]),
+    ("magenta", [0xff, 0x00, 0xff]),
+    ("maroon", [0x80, 0x00, 0x00]),
+    ("mediumaquamarine", [0x66, 0xcd, 0xaa]),
+    ("mediumblue", [0x00, 0x00, 0xcd]),
+    ("mediumorchid", [0xba, 0x55, 0xd3]),
+    ("mediumpurple", [0x93, 0x70, 0xdb]),
+    ("mediumseagreen", [0x3c, 0xb3, 0x71]),
+    ("mediumslateblue", [0x7b, 0x68, 0xee]),
+    ("mediumspringgreen", [0x00, 0xfa, 0x9a]),
+    ("mediumturquoise", [0x48, 0xd1, 0xcc]),
+    ("mediumvioletred", [0xc7, 0x15, 0x85]),
+    ("midnightblue", [0x19, 0x19, 0x70]),
+    ("mintcream", [0xf5, 0xff, 0xfa]),
+    ("mistyrose", [0xff, 0xe4, 0xe1]),
+    ("moccasin", [0xff, 0xe4, 0xb5]),
+    ("navajowhite", [0xff, 0xde, 0xad]),
+    ("navy", [0x00, 0x00, 0x80]),
+    ("oldlace", [0xfd, 0xf5, 0xe6]),
+    ("olive", [0x80, 0x80, 0x00]),
+    ("olivedrab", [0x6b, 0x8e, 0x23]),
+    ("orange", [0xff, 0xa5, 0x00]),
+    ("orangered", [0xff, 0x45, 0x00]),
+    ("orchid", [0xda, 0x70, 0xd6]),
+    ("palegoldenrod", [0xee, 0xe8, 0xaa]),
+    ("palegreen", [0x98, 0xfb, 0x98]),
+    ("paleturquoise", [0xaf, 0xee, 0xee]),
+    ("palevioletred", [0xdb, 0x70, 0x93]),
+    ("papayawhip", [0xff, 0xef, 0xd5]),
+    ("peachpuff", [0xff, 0xda, 0xb9]),
+    ("peru", [0xcd, 0x85, 0x3f]),
+    ("pink", [0xff, 0xc0, 0xcb]),
+    ("plum", [0xdd, 0xa0, 0xdd]),
+    ("powderblue", [0xb0, 0xe0, 0xe6]),
+    ("purple", [0x80, 0x00, 0x80]),
+    ("rebeccapurple", [0x66, 0x33, 0x99]),
+    ("red", [0xff, 0x00, 0x00]),
+    ("rosybrown", [0xbc, 0x8f, 0x8f]),
+    ("royalblue", [0x41, 0x69, 0xe1]),
+    ("saddlebrown", [0x8b, 0x45, 0x13]),
+    ("salmon", [0xfa, 0x80, 0x72]),
+    ("sandybrown", [0xf4, 0xa4, 0x60]),
+    ("seagreen", [0x2e, 0x8b, 0x57]),
+    ("seashell", [0xff, 0xf5, 0xee]),
+    ("sienna", [0xa0, 0x52, 0x2d]),
+    ("silver", [0xc0, 0xc0, 0xc0]),
+    ("skyblue", [0x87, 0xce, 0xeb]),
+    ("slateblue", [0x6a, 0x5a, 0xcd]),
+    ("slategray", [0x70, 0x80, 0x90]),
+    ("slategrey", [0x70, 0x80, 0x90]),
+    ("snow", [0xff, 0xfa, 0xfa]),
+    ("springgreen", [0x00, 0xff, 0x7f]),
+    ("steelblue", [0x46, 0x82, 0xb4]),
+    ("tan", [0xd2, 0xb4, 0x8c]),
+    ("teal", [0x00, 0x80, 0x80]),
+    ("thistle", [0xd8, 0xbf, 0xd8]),
+    ("tomato", [0xff, 0x63, 0x47]),
+    ("turquoise", [0x40, 0xe0, 0xd0]),
+    ("violet", [0xee, 0x82, 0xee]),
+    ("wheat", [0xf5, 0xde, 0xb3]),
+    ("white", [0xff, 0xff, 0xff]),
+    ("whitesmoke", [0xf5, 0xf5, 0xf5]),
+    ("yellow", [0xff, 0xff, 0x00]),
+    ("yellowgreen", [0x9a, 0xcd, 0x32]),
+];
+
 #[cfg(test)]
 mod tests {
-    use crate::utils::string::{argb_from_hex, hex_from_argb};
+    use crate::utils::string::{
+        argb_from_css, argb_from_hex, argb_from_hex_const, argb_from_named_color,
+        css_hsl_from_argb, css_rgb_from_argb, hex_from_argb, hex_from_argb_with,
+        nearest_named_color, try_argb_from_hex, HexAlpha, HexAlphaPosition, HexFormat,
+        ParseColorError, NAMED_COLORS,
+    };
 
     #[test]
     fn get_argb_from_hex() {
@@ -96,6 +683,15 @@ mod tests {
         assert_eq!(argb_two[3], 153);
     }
 
+    #[test]
+    fn get_argb_from_hex_four() {
+        assert_eq!(argb_from_hex(String::from("#0000")), [0, 0, 0, 0]);
+        assert_eq!(argb_from_hex(String::from("#ffff")), [255, 255, 255, 255]);
+        assert_eq!(argb_from_hex(String::from("#09c8")), [136, 0, 153, 204]);
+        assert_eq!(argb_from_hex(String::from("09C8")), [136, 0, 153, 204]);
+        assert_eq!(try_argb_from_hex("#aBc4"), Ok([68, 170, 187, 204]));
+    }
+
     #[test]
     #[should_panic]
     fn test_argb_from_hex_panic() {
@@ -123,4 +719,323 @@ mod tests {
         let hex = hex_from_argb([128, 119, 0, 153]);
         assert_eq!(hex, String::from("#77009980"));
     }
+
+    #[test]
+    fn try_get_argb_from_hex() {
+        assert_eq!(try_argb_from_hex("#770099"), Ok([255, 119, 0, 153]));
+        assert_eq!(try_argb_from_hex("709"), Ok([255, 119, 0, 153]));
+        assert_eq!(try_argb_from_hex("#77009980"), Ok([128, 119, 0, 153]));
+    }
+
+    #[test]
+    fn try_get_argb_from_hex_errors() {
+        assert_eq!(
+            try_argb_from_hex("#12345"),
+            Err(ParseColorError::InvalidLength(5))
+        );
+        assert_eq!(
+            try_argb_from_hex("#7g0099"),
+            Err(ParseColorError::InvalidHexDigit {
+                digit: 'g',
+                position: 2
+            })
+        );
+        assert_eq!(
+            try_argb_from_hex("#7G0099").unwrap_err().to_string(),
+            "invalid hex digit 'G' at position 2"
+        );
+        assert_eq!(
+            try_argb_from_hex("7é0099"),
+            Err(ParseColorError::InvalidHexDigit {
+                digit: 'é',
+                position: 1
+            })
+        );
+        assert_eq!(
+            try_argb_from_hex(""),
+            Err(ParseColorError::InvalidLength(0))
+        );
+        assert_eq!(
+            try_argb_from_hex("#"),
+            Err(ParseColorError::InvalidLength(0))
+        );
+        assert_eq!(
+            try_argb_from_hex("##4285f4"),
+            Err(ParseColorError::RepeatedHash)
+        );
+        assert_eq!(
+            argb_from_css("##4285f4").unwrap_err().to_string(),
+            "hex color starts with more than one '#'"
+        );
+    }
+
+    #[test]
+    fn try_get_argb_from_hex_0x() {
+        assert_eq!(try_argb_from_hex("0xFF4285F4"), Ok([255, 66, 133, 244]));
+        assert_eq!(try_argb_from_hex("0X804285f4"), Ok([128, 66, 133, 244]));
+        assert_eq!(try_argb_from_hex("0x4285f4"), Ok([255, 66, 133, 244]));
+        assert_eq!(argb_from_css("0xff4285f4"), Ok([255, 66, 133, 244]));
+        assert_eq!(
+            try_argb_from_hex("0xfff"),
+            Err(ParseColorError::InvalidLength(3))
+        );
+        assert_eq!(
+            argb_from_css("0xff42z5f4"),
+            Err(ParseColorError::InvalidHexDigit {
+                digit: 'z',
+                position: 6
+            })
+        );
+    }
+
+    #[test]
+    fn parsers_never_panic_on_garbage() {
+        const PIECES: [&str; 24] = [
+            "#", "0x", "0X", "rgb(", "hsl(", "hwb(", "(", ")", ",", "/", "%", " ", "deg", "turn",
+            "-", ".", "e", "9", "f", "G", "é", "✓", "NaN", "inf",
+        ];
+        // xorshift64, so the inputs are random but reproducible.
+        let mut state = 0x9e3779b97f4a7c15_u64;
+        for _ in 0..20_000 {
+            let mut input = String::new();
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            let len = state % 12;
+            for _ in 0..len {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                input.push_str(PIECES[(state % PIECES.len() as u64) as usize]);
+            }
+            let _ = try_argb_from_hex(&input);
+            let _ = argb_from_css(&input);
+        }
+    }
+
+    #[test]
+    fn css_rgb_syntaxes() {
+        let blue = Ok([255, 66, 133, 244]);
+        assert_eq!(argb_from_css("rgb(66, 133, 244)"), blue);
+        assert_eq!(argb_from_css("rgb(66 133 244)"), blue);
+        assert_eq!(argb_from_css("  RGB( 66 ,133,  244 ) "), blue);
+        assert_eq!(argb_from_css("rgba(66,133,244)"), blue);
+        assert_eq!(argb_from_css("#4285f4"), blue);
+    }
+
+    #[test]
+    fn css_alpha() {
+        let translucent = Ok([128, 66, 133, 244]);
+        assert_eq!(argb_from_css("rgba(66, 133, 244, 0.5)"), translucent);
+        assert_eq!(argb_from_css("rgba(66 133 244 / 0.5)"), translucent);
+        assert_eq!(argb_from_css("rgb(66 133 244/50%)"), translucent);
+        assert_eq!(argb_from_css("rgb(66 133 244 / 0)"), Ok([0, 66, 133, 244]));
+    }
+
+    #[test]
+    fn css_percentages_and_clamping() {
+        assert_eq!(argb_from_css("rgb(100%, 50%, 0%)"), Ok([255, 255, 128, 0]));
+        assert_eq!(argb_from_css("rgb(300 -20 127.6)"), Ok([255, 255, 0, 128]));
+        assert_eq!(argb_from_css("rgb(0 0 0 / 150%)"), Ok([255, 0, 0, 0]));
+    }
+
+    #[test]
+    fn css_errors() {
+        assert_eq!(
+            argb_from_css("rgb(1, 2)"),
+            Err(ParseColorError::InvalidCss(String::from("1, 2")))
+        );
+        assert_eq!(
+            argb_from_css("rgb(1 2 blue)"),
+            Err(ParseColorError::InvalidCss(String::from("blue")))
+        );
+        assert_eq!(
+            argb_from_css("rgb(1 2 3"),
+            Err(ParseColorError::InvalidCss(String::from("rgb(1 2 3")))
+        );
+        assert_eq!(
+            argb_from_css("#12345"),
+            Err(ParseColorError::InvalidLength(5))
+        );
+        // Hex digits without the # are accepted by try_argb_from_hex, but aren't CSS.
+        assert_eq!(try_argb_from_hex("bad"), Ok([255, 0xbb, 0xaa, 0xdd]));
+        for bare in ["bad", "4285f4", "ff4285f4"] {
+            assert_eq!(
+                argb_from_css(bare),
+                Err(ParseColorError::InvalidCss(String::from(bare)))
+            );
+        }
+    }
+
+    #[test]
+    fn named_colors() {
+        assert_eq!(argb_from_css("rebeccapurple"), Ok([255, 0x66, 0x33, 0x99]));
+        assert_eq!(argb_from_css(" SteelBlue "), Ok([255, 0x46, 0x82, 0xb4]));
+        assert_eq!(argb_from_css("transparent"), Ok([0, 0, 0, 0]));
+        assert_eq!(argb_from_named_color("grey"), argb_from_named_color("gray"));
+        assert_eq!(argb_from_named_color("notacolor"), None);
+        assert_eq!(
+            argb_from_css("notacolor"),
+            Err(ParseColorError::InvalidCss(String::from("notacolor")))
+        );
+        assert!(NAMED_COLORS.windows(2).all(|pair| pair[0].0 < pair[1].0));
+    }
+
+    #[test]
+    fn nearest_named_colors() {
+        for name in [
+            "steelblue",
+            "rebeccapurple",
+            "tomato",
+            "black",
+            "white",
+            "gray",
+        ] {
+            let argb = argb_from_named_color(name).unwrap();
+            assert_eq!(nearest_named_color(argb), name);
+        }
+        assert_eq!(nearest_named_color([255, 0x47, 0x80, 0xb0]), "steelblue");
+    }
+
+    #[test]
+    fn css_hsl() {
+        assert_eq!(argb_from_css("hsl(120, 100%, 50%)"), Ok([255, 0, 255, 0]));
+        assert_eq!(
+            argb_from_css("hsla(120 100% 50% / 0.5)"),
+            Ok([128, 0, 255, 0])
+        );
+        assert_eq!(
+            argb_from_css("hsl(0.5turn 100% 50%)"),
+            Ok([255, 0, 255, 255])
+        );
+        assert_eq!(argb_from_css("hsl(-120deg 100% 50%)"), Ok([255, 0, 0, 255]));
+        assert_eq!(argb_from_css("hsl(600 100% 50%)"), Ok([255, 0, 0, 255]));
+        assert_eq!(
+            argb_from_css("hsl(3.14159265rad 100% 50%)"),
+            Ok([255, 0, 255, 255])
+        );
+        assert_eq!(argb_from_css("hsl(400grad 100% 50%)"), Ok([255, 255, 0, 0]));
+    }
+
+    #[test]
+    fn css_hwb() {
+        assert_eq!(argb_from_css("hwb(0 0% 0%)"), Ok([255, 255, 0, 0]));
+        assert_eq!(argb_from_css("hwb(240 0% 50%)"), Ok([255, 0, 0, 128]));
+        assert_eq!(argb_from_css("hwb(90 60% 60%)"), Ok([255, 128, 128, 128]));
+        assert_eq!(
+            argb_from_css("hwb(90 50% 50% / 25%)"),
+            Ok([64, 128, 128, 128])
+        );
+        assert_eq!(
+            argb_from_css("hwb(1x 0% 0%)"),
+            Err(ParseColorError::InvalidCss(String::from("1x")))
+        );
+        assert_eq!(
+            argb_from_css("lab(50 0 0)"),
+            Err(ParseColorError::InvalidCss(String::from("lab")))
+        );
+    }
+
+    #[test]
+    fn hex_with_formats() {
+        let blue = [255, 66, 133, 244];
+        assert_eq!(hex_from_argb_with(blue, HexFormat::android()), "#FF4285F4");
+        assert_eq!(
+            hex_from_argb_with(
+                blue,
+                HexFormat {
+                    include_hash: false,
+                    ..HexFormat::android()
+                }
+            ),
+            "FF4285F4"
+        );
+        assert_eq!(hex_from_argb_with(blue, HexFormat::default()), "#4285f4");
+        assert_eq!(
+            hex_from_argb_with(blue, HexFormat::default()),
+            hex_from_argb(blue)
+        );
+
+        let translucent = [128, 66, 133, 244];
+        assert_eq!(
+            hex_from_argb_with(translucent, HexFormat::default()),
+            "#4285f480"
+        );
+        assert_eq!(
+            hex_from_argb_with(translucent, HexFormat::android()),
+            "#804285F4"
+        );
+        let never = HexFormat {
+            alpha: HexAlpha::Never,
+            ..HexFormat::default()
+        };
+        assert_eq!(hex_from_argb_with(translucent, never), "#4285f4");
+        let always_trailing = HexFormat {
+            alpha: HexAlpha::Always,
+            alpha_position: HexAlphaPosition::Trailing,
+            ..HexFormat::default()
+        };
+        assert_eq!(hex_from_argb_with(blue, always_trailing), "#4285f4ff");
+    }
+
+    #[test]
+    fn css_rgb_output() {
+        assert_eq!(css_rgb_from_argb([255, 66, 133, 244], 2), "rgb(66 133 244)");
+        assert_eq!(
+            css_rgb_from_argb([128, 66, 133, 244], 2),
+            "rgb(66 133 244 / 0.50)"
+        );
+        assert_eq!(
+            css_rgb_from_argb([128, 66, 133, 244], 3),
+            "rgb(66 133 244 / 0.502)"
+        );
+        assert_eq!(css_rgb_from_argb([0, 0, 0, 0], 0), "rgb(0 0 0 / 0)");
+        let text = css_rgb_from_argb([64, 1, 2, 3], 4);
+        assert_eq!(argb_from_css(&text), Ok([64, 1, 2, 3]));
+    }
+
+    #[test]
+    fn css_hsl_output() {
+        assert_eq!(css_hsl_from_argb([255, 0, 255, 0], 0), "hsl(120 100% 50%)");
+        assert_eq!(
+            css_hsl_from_argb([255, 66, 133, 244], 1),
+            "hsl(217.4 89.0% 60.8%)"
+        );
+        assert_eq!(
+            css_hsl_from_argb([255, 66, 133, 244], 0),
+            "hsl(217 89% 61%)"
+        );
+        assert_eq!(
+            css_hsl_from_argb([128, 128, 128, 128], 2),
+            "hsl(0.00 0.00% 50.20% / 0.50)"
+        );
+        let text = css_hsl_from_argb([200, 66, 133, 244], 4);
+        assert_eq!(argb_from_css(&text), Ok([200, 66, 133, 244]));
+    }
+
+    #[test]
+    fn hex_const() {
+        const BLUE: [u8; 4] = argb_from_hex_const("#4285f4");
+        const TRANSLUCENT: [u8; 4] = argb_from_hex_const("4285F480");
+        const SHORT: [u8; 4] = crate::argb!("#09c8");
+        assert_eq!(BLUE, [255, 66, 133, 244]);
+        assert_eq!(TRANSLUCENT, [128, 66, 133, 244]);
+        assert_eq!(SHORT, [136, 0, 153, 204]);
+        assert_eq!(crate::argb!("fff"), [255, 255, 255, 255]);
+        for hex in ["#770099", "709", "#77009980", "#aBc4", "C0FFEE"] {
+            assert_eq!(Ok(argb_from_hex_const(hex)), try_argb_from_hex(hex));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid hex digit")]
+    fn hex_const_invalid_digit() {
+        argb_from_hex_const("#4285g4");
+    }
+
+    #[test]
+    #[should_panic(expected = "3, 4, 6, or 8")]
+    fn hex_const_invalid_length() {
+        argb_from_hex_const("#4285f");
+    }
 }
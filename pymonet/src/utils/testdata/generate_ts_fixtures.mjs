@@ -0,0 +1,45 @@
+// Writes the TypeScript material-color-utilities reference fixtures used by the serde and
+// argb_from_xyz tests.
+//
+// From the pymonet directory:
+//
+//   npm install --no-save @material/material-color-utilities@0.2.7
+//   node src/utils/testdata/generate_ts_fixtures.mjs
+//
+// The JSON output is the library's own JSON.stringify of each object, unedited: colors are ARGB
+// numbers, schemes are their role props, and palettes carry the TS TonalPalette fields.
+//
+// argb_from_xyz.csv keeps its x,y,z inputs, and its argb column is recomputed by the library.
+import { readFileSync, writeFileSync } from "node:fs";
+import {
+  Scheme,
+  argbFromHex,
+  argbFromXyz,
+  themeFromSourceColor,
+} from "@material/material-color-utilities";
+
+const dir = new URL(".", import.meta.url);
+
+const write = (name, value) =>
+  writeFileSync(new URL(name, dir), JSON.stringify(value, null, 2) + "\n");
+
+const source = argbFromHex("#4285f4");
+write("theme_4285f4.ts.json", themeFromSourceColor(source));
+write("scheme_4285f4.ts.json", { light: Scheme.light(source), dark: Scheme.dark(source) });
+
+const xyzs = readFileSync(new URL("argb_from_xyz.csv", dir), "utf8")
+  .split("\n")
+  .filter((line) => line && !line.startsWith("#"))
+  .map((line) => line.split(",").slice(0, 3));
+const rows = xyzs.map((xyz) => {
+  const argb = argbFromXyz(...xyz.map(Number)) >>> 0;
+  return [...xyz, argb.toString(16).padStart(8, "0")].join(",");
+});
+writeFileSync(
+  new URL("argb_from_xyz.csv", dir),
+  [
+    "# x,y,z,argb from @material/material-color-utilities argbFromXyz, written by",
+    "# generate_ts_fixtures.mjs. Two thirds of them are near the sRGB gamut boundary.",
+    ...rows,
+  ].join("\n") + "\n",
+);
@@ -1,27 +1,50 @@
-use crate::palettes::{core::CorePalette, tonal::TonalPalette};
-use crate::scheme::Scheme;
+use crate::blend::{harmonize, Harmonizer};
+use crate::palettes::{
+    core::{CorePalette, CorePaletteOptions},
+    tonal::TonalPalette,
+};
+use crate::scheme::{variant::Variant, DarkMode, Role, Scheme};
+use crate::utils::color::Argb;
+use crate::utils::math::sanitize_degrees_double;
+use ahash::AHashMap;
+
+pub mod diff;
+pub mod export;
+pub use diff::{RoleChange, ThemeDiff};
+pub use export::{CssColorFormat, CssOptions, CssSchemeSelector, ScssForm, ScssOptions};
 
 /// Custom color used to pair with a theme
-#[derive(Debug)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CustomColor {
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::color_format"))]
     pub value: [u8; 4],
     pub name: String,
     pub blend: bool,
 }
 
 /// Color group
-#[derive(Debug)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 pub struct ColorGroup {
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::color_format"))]
     pub color: [u8; 4],
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::color_format"))]
     pub on_color: [u8; 4],
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::color_format"))]
     pub color_container: [u8; 4],
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::color_format"))]
     pub on_color_container: [u8; 4],
 }
 
 /// Custom Color Group
-#[derive(Debug)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 pub struct CustomColorGroup {
     pub color: CustomColor,
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::color_format"))]
     pub value: [u8; 4],
     pub light: ColorGroup,
     pub dark: ColorGroup,
@@ -29,6 +52,7 @@ pub struct CustomColorGroup {
 
 /// Collection of color schemes based of the palette source color
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Schemes {
     pub light: Scheme,
     pub dark: Scheme,
@@ -36,6 +60,8 @@ pub struct Schemes {
 
 /// A collection of palettes..
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 pub struct Palettes {
     pub primary: TonalPalette,
     pub secondary: TonalPalette,
@@ -49,48 +75,91 @@ pub struct Palettes {
 ///
 /// Holds the data specific to a theme based on a source color
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 pub struct Theme {
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::color_format"))]
     pub source: [u8; 4],
     pub schemes: Schemes,
     pub palettes: Palettes,
+    pub custom_colors: Vec<CustomColorGroup>,
 }
 
 impl Theme {
     /// Generate a theme from a source color
     ///
-    /// @param source Source color
-    /// @param customColors Array of custom colors
+    /// A shortcut for `ThemeBuilder::new(source).build()`; see [`ThemeBuilder`] for the options.
+    ///
+    /// @param source Source color, as `[u8; 4]`, `Argb`, or a `u32` packed as 0xAARRGGBB
     /// @return Theme object
-    pub fn from_source_color(source: [u8; 4]) -> Theme {
-        let mut palette = CorePalette::new(source, false);
-        let light = Scheme::light_from_core_palette(&mut palette);
-        let dark = Scheme::dark_from_core_palette(&mut palette);
-        let schemes: Schemes = Schemes { light, dark };
-        let primary = palette.a1;
-        let secondary = palette.a2;
-        let tertiary = palette.a3;
-        let neutral = palette.n1;
-        let neutral_variant = palette.n2;
-        let error = palette.error;
-        let palettes: Palettes = Palettes {
-            primary,
-            secondary,
-            tertiary,
-            neutral,
-            neutral_variant,
-            error,
-        };
-        Theme {
-            source,
-            schemes,
-            palettes,
+    pub fn from_source_color(source: impl Into<Argb>) -> Theme {
+        ThemeBuilder::new(source).build()
+    }
+
+    /// Generate a theme from a source color, along with color groups for custom colors
+    ///
+    /// @param source Source color, as `[u8; 4]`, `Argb`, or a `u32` packed as 0xAARRGGBB
+    /// @param custom_colors Array of custom colors, see [`custom_color`]
+    /// @return Theme object
+    pub fn from_source_color_with_custom_colors(
+        source: impl Into<Argb>,
+        custom_colors: Vec<CustomColor>,
+    ) -> Theme {
+        custom_colors
+            .into_iter()
+            .fold(ThemeBuilder::new(source), ThemeBuilder::custom_color)
+            .build()
+    }
+
+    /// Harmonize a batch of custom colors toward the source color
+    ///
+    /// Equivalent to [`custom_color`] with `blend` set for every color, but the source color is
+    /// analyzed once for the whole batch.
+    ///
+    /// @param colors Names and values of the custom colors
+    /// @return Custom color groups, in the order of `colors`
+    pub fn harmonize_custom_colors(&self, colors: &[(&str, [u8; 4])]) -> Vec<CustomColorGroup> {
+        if colors.is_empty() {
+            return Vec::new();
         }
+        let harmonizer = Harmonizer::new(self.source);
+        colors
+            .iter()
+            .map(|&(name, value)| {
+                let color = CustomColor {
+                    value,
+                    name: String::from(name),
+                    blend: true,
+                };
+                color_group(&color, harmonizer.harmonize(value))
+            })
+            .collect()
+    }
+
+    /// Generate a theme from the source color of an image
+    ///
+    /// The image is shrunk, quantized, and scored; the best scoring color becomes the source.
+    ///
+    /// @param bytes An encoded PNG or JPEG image
+    /// @return Theme object, or why no source color could be found
+    #[cfg(feature = "image")]
+    pub fn from_image_bytes(bytes: &[u8]) -> Result<Theme, crate::utils::image::ImageError> {
+        crate::utils::image::source_color_from_image_bytes(bytes).map(Theme::from_source_color)
     }
 
+    /// Generate a content theme from three source colors
+    ///
+    /// The primary, neutral, and neutral variant palettes derive from the first source, as in
+    /// [`Theme::from_source_color`]; the secondary and tertiary palettes take their hue and chroma
+    /// from the second and third sources. See [`CorePalette::content_from_colors`].
+    ///
+    /// @param sources Primary, secondary, and tertiary source colors
+    /// @return Theme object, whose `source` is the primary source
     pub fn from_source_colors(sources: [[u8; 4]; 3]) -> Theme {
-        let mut palette = CorePalette::new(sources[0], true);
-        let light = Scheme::light_from_core_palette(&mut palette);
-        let dark = Scheme::dark_from_core_palette(&mut palette);
+        let palette =
+            CorePalette::content_from_colors(sources[0], Some(sources[1]), Some(sources[2]));
+        let light = Scheme::light_from_core_palette(&palette);
+        let dark = Scheme::dark_from_core_palette(&palette);
         let schemes: Schemes = Schemes { light, dark };
         let primary = palette.a1;
         let secondary = palette.a2;
@@ -110,6 +179,835 @@ impl Theme {
             source: sources[0],
             schemes,
             palettes,
+            custom_colors: Vec::new(),
+        }
+    }
+}
+
+/// Why a [`ThemeBuilder`] can't build its theme
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThemeBuildError {
+    /// [`ThemeBuilder::palette_options`] tunes the [`Variant::TonalSpot`] palettes, so it can't
+    /// be combined with another variant.
+    PaletteOptionsWithVariant(Variant),
+}
+
+impl std::fmt::Display for ThemeBuildError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ThemeBuildError::PaletteOptionsWithVariant(variant) => write!(
+                f,
+                "palette options apply to the TonalSpot variant, not {variant:?}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ThemeBuildError {}
+
+/// Builds a theme from a source color and options
+///
+/// ```
+/// use pymonet::scheme::variant::Variant;
+/// use pymonet::utils::theme::{Theme, ThemeBuilder};
+///
+/// let theme = ThemeBuilder::new([0xff, 0x42, 0x85, 0xf4])
+///     .variant(Variant::Vibrant)
+///     .contrast(0.5)
+///     .error_hue(15.0)
+///     .build();
+/// assert_eq!(theme.source, [0xff, 0x42, 0x85, 0xf4]);
+/// let tonal_spot = Theme::from_source_color([0xff, 0x42, 0x85, 0xf4]);
+/// assert_ne!(theme.schemes.light, tonal_spot.schemes.light);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ThemeBuilder {
+    source: [u8; 4],
+    variant: Variant,
+    contrast: f64,
+    custom_colors: Vec<CustomColor>,
+    error_hue: Option<f64>,
+    error_chroma: Option<f64>,
+    dark_mode: DarkMode,
+    tone_overrides: AHashMap<Role, (f64, f64)>,
+    palette_options: Option<CorePaletteOptions>,
+}
+
+impl ThemeBuilder {
+    /// Start a theme with the default options: [`Variant::TonalSpot`], standard contrast, no
+    /// custom colors, the standard error palette, and [`DarkMode::Standard`]
+    ///
+    /// @param source Source color, as `[u8; 4]`, `Argb`, or a `u32` packed as 0xAARRGGBB
+    pub fn new(source: impl Into<Argb>) -> ThemeBuilder {
+        ThemeBuilder {
+            source: source.into().0,
+            variant: Variant::default(),
+            contrast: 0.0,
+            custom_colors: Vec::new(),
+            error_hue: None,
+            error_chroma: None,
+            dark_mode: DarkMode::default(),
+            tone_overrides: AHashMap::new(),
+            palette_options: None,
+        }
+    }
+
+    /// @param variant Style of the palettes, see [`Variant`]
+    pub fn variant(mut self, variant: Variant) -> ThemeBuilder {
+        self.variant = variant;
+        self
+    }
+
+    /// @param contrast Contrast level from -1 (reduced) through 0 (standard) to 1 (high), see
+    /// [`Scheme::light_from_core_palette_with_contrast`]
+    pub fn contrast(mut self, contrast: f64) -> ThemeBuilder {
+        self.contrast = contrast;
+        self
+    }
+
+    /// @param color Custom color to generate a color group for, see [`custom_color`]
+    pub fn custom_color(mut self, color: CustomColor) -> ThemeBuilder {
+        self.custom_colors.push(color);
+        self
+    }
+
+    /// @param hue Hue of the error palette, in place of the standard red
+    pub fn error_hue(mut self, hue: f64) -> ThemeBuilder {
+        self.error_hue = Some(sanitize_degrees_double(hue));
+        self
+    }
+
+    /// @param chroma Chroma of the error palette, in place of the standard 84
+    pub fn error_chroma(mut self, chroma: f64) -> ThemeBuilder {
+        self.error_chroma = Some(chroma);
+        self
+    }
+
+    /// @param dark_mode How the dark scheme is generated, ex. [`DarkMode::Amoled`] for a pure
+    /// black background
+    pub fn dark_mode(mut self, dark_mode: DarkMode) -> ThemeBuilder {
+        self.dark_mode = dark_mode;
+        self
+    }
+
+    /// Overrides the tones of one role, which is still drawn from its usual palette. Overrides
+    /// are applied after the contrast level, and a later override of the same role replaces an
+    /// earlier one. Surface tint follows an overridden primary unless it has an override too.
+    ///
+    /// @param role Role to override
+    /// @param light_tone Tone of the role in the light scheme
+    /// @param dark_tone Tone of the role in the dark scheme
+    pub fn tone_override(mut self, role: Role, light_tone: f64, dark_tone: f64) -> ThemeBuilder {
+        self.tone_overrides.insert(role, (light_tone, dark_tone));
+        self
+    }
+
+    /// Tunes the chromas and tertiary hue of the [`Variant::TonalSpot`] palettes. Combining the
+    /// options with another variant is an error, see [`ThemeBuilder::try_build`].
+    ///
+    /// @param options Chromas and hue shift, see [`CorePalette::with_options`]
+    pub fn palette_options(mut self, options: CorePaletteOptions) -> ThemeBuilder {
+        self.palette_options = Some(options);
+        self
+    }
+
+    fn core_palette(&self) -> Result<CorePalette, ThemeBuildError> {
+        let mut palette = match (self.palette_options, self.variant) {
+            (Some(options), Variant::TonalSpot) => CorePalette::with_options(self.source, options),
+            (Some(_), variant) => return Err(ThemeBuildError::PaletteOptionsWithVariant(variant)),
+            (None, variant) => variant.core_palette(self.source),
+        };
+        if self.error_hue.is_some() || self.error_chroma.is_some() {
+            palette.error = TonalPalette::from_hue_and_chroma(
+                self.error_hue.unwrap_or(palette.error.hue()),
+                self.error_chroma.unwrap_or(palette.error.chroma()),
+            );
+        }
+        Ok(palette)
+    }
+
+    /// Like [`ThemeBuilder::try_build`], for options known not to conflict
+    ///
+    /// Panics if [`ThemeBuilder::palette_options`] is combined with a variant other than
+    /// [`Variant::TonalSpot`].
+    ///
+    /// @return Theme object
+    pub fn build(self) -> Theme {
+        self.try_build().unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    /// @return Theme object, or the conflict between the options that prevents building it
+    pub fn try_build(self) -> Result<Theme, ThemeBuildError> {
+        let palette = self.core_palette()?;
+        let mut light = Scheme::light_from_core_palette_with_contrast(&palette, self.contrast);
+        self.override_tones(&mut light, &palette, false);
+        let dark = self.dark_scheme(&palette);
+        let custom_colors = if self.custom_colors.is_empty() {
+            Vec::new()
+        } else {
+            let harmonizer = Harmonizer::new(self.source);
+            self.custom_colors
+                .iter()
+                .map(|color| harmonized_custom_color(&harmonizer, color))
+                .collect()
+        };
+        Ok(Theme {
+            source: self.source,
+            schemes: Schemes { light, dark },
+            palettes: Palettes {
+                primary: palette.a1,
+                secondary: palette.a2,
+                tertiary: palette.a3,
+                neutral: palette.n1,
+                neutral_variant: palette.n2,
+                error: palette.error,
+            },
+            custom_colors,
+        })
+    }
+
+    /// Build only the dark scheme, for apps without a light mode
+    ///
+    /// Panics under the same conditions as [`ThemeBuilder::build`].
+    ///
+    /// @return The scheme `build().schemes.dark` would have, without generating the rest
+    pub fn build_dark(self) -> Scheme {
+        self.try_build_dark()
+            .unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    /// @return The scheme `try_build()` would give the dark scheme of, without generating the
+    /// rest
+    pub fn try_build_dark(self) -> Result<Scheme, ThemeBuildError> {
+        Ok(self.dark_scheme(&self.core_palette()?))
+    }
+
+    fn dark_scheme(&self, palette: &CorePalette) -> Scheme {
+        let mut scheme = match self.dark_mode {
+            DarkMode::Standard => {
+                Scheme::dark_from_core_palette_with_contrast(palette, self.contrast)
+            }
+            DarkMode::Amoled => {
+                Scheme::dark_from_core_palette_amoled_with_contrast(palette, self.contrast)
+            }
+        };
+        self.override_tones(&mut scheme, palette, true);
+        scheme
+    }
+
+    fn override_tones(&self, scheme: &mut Scheme, palette: &CorePalette, dark: bool) {
+        for (role, (light_tone, dark_tone)) in &self.tone_overrides {
+            let tone = if dark { *dark_tone } else { *light_tone };
+            scheme[role] = role.palette(palette).tone(tone);
+        }
+        // The tint follows an overridden primary, unless it was overridden itself.
+        if !self.tone_overrides.contains_key(&Role::SurfaceTint) {
+            scheme.surface_tint = scheme.primary;
+        }
+    }
+}
+
+/// Generate custom color group from source and target color
+///
+/// @param source Source color
+/// @param color Custom color, harmonized towards `source` first if its `blend` is set
+/// @return Custom color group
+pub fn custom_color(source: [u8; 4], color: &CustomColor) -> CustomColorGroup {
+    let value = if color.blend {
+        harmonize(color.value, source)
+    } else {
+        color.value
+    };
+    color_group(color, value)
+}
+
+/// [`custom_color`] with the source color already analyzed by `harmonizer`, for building several
+/// custom colors toward one source.
+pub(crate) fn harmonized_custom_color(
+    harmonizer: &Harmonizer,
+    color: &CustomColor,
+) -> CustomColorGroup {
+    let value = if color.blend {
+        harmonizer.harmonize(color.value)
+    } else {
+        color.value
+    };
+    color_group(color, value)
+}
+
+/// Builds the light and dark groups of `color` from `value`, its possibly harmonized color.
+fn color_group(color: &CustomColor, value: [u8; 4]) -> CustomColorGroup {
+    let tones = CorePalette::of(value).a1;
+    CustomColorGroup {
+        color: color.clone(),
+        value,
+        light: ColorGroup {
+            color: tones.tone(40),
+            on_color: tones.tone(100),
+            color_container: tones.tone(90),
+            on_color_container: tones.tone(10),
+        },
+        dark: ColorGroup {
+            color: tones.tone(80),
+            on_color: tones.tone(20),
+            color_container: tones.tone(30),
+            on_color_container: tones.tone(90),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blend::SOURCE_ANALYSES;
+    use crate::contrast::ratio_of_tones;
+    use crate::hct::Hct;
+    use crate::utils::color::lstar_from_argb;
+    use crate::utils::math::difference_degrees;
+    use assert_approx_eq::assert_approx_eq;
+
+    #[test]
+    fn from_source_color_accepts_packed_u32() {
+        let packed = Theme::from_source_color(0xff4285f4u32);
+        let unpacked = Theme::from_source_color([0xff, 0x42, 0x85, 0xf4]);
+        assert_eq!(packed.source, [0xff, 0x42, 0x85, 0xf4]);
+        assert_eq!(packed.schemes.light.primary, unpacked.schemes.light.primary);
+        assert_eq!(packed.schemes.dark.primary, unpacked.schemes.dark.primary);
+    }
+
+    #[test]
+    fn from_source_colors_uses_every_source() {
+        let blue = [0xff, 0x42, 0x85, 0xf4];
+        let red = [0xff, 0xdb, 0x44, 0x37];
+        let green = [0xff, 0x0f, 0x9d, 0x58];
+        let theme = Theme::from_source_colors([blue, red, green]);
+        assert_eq!(theme.source, blue);
+        assert_approx_eq!(
+            theme.palettes.primary.hue(),
+            Hct::from_int(blue).hue(),
+            1e-9
+        );
+        assert_approx_eq!(
+            theme.palettes.secondary.hue(),
+            Hct::from_int(red).hue(),
+            1e-9
+        );
+        assert_approx_eq!(
+            theme.palettes.tertiary.hue(),
+            Hct::from_int(green).hue(),
+            1e-9
+        );
+        assert_approx_eq!(
+            theme.palettes.neutral.hue(),
+            Hct::from_int(blue).hue(),
+            1e-9
+        );
+        let secondary_hue = Hct::from_int(theme.schemes.light.secondary).hue();
+        assert!(difference_degrees(secondary_hue, Hct::from_int(red).hue()) < 5.0);
+    }
+
+    #[test]
+    fn builder_defaults_match_from_source_color() {
+        let source = [0xff, 0x42, 0x85, 0xf4];
+        let built = ThemeBuilder::new(source).build();
+        let theme = Theme::from_source_color(source);
+        assert_eq!(built.schemes.light, theme.schemes.light);
+        assert_eq!(built.schemes.dark, theme.schemes.dark);
+    }
+
+    #[test]
+    fn builder_variant() {
+        let source = [0xff, 0x42, 0x85, 0xf4];
+        let monochrome = ThemeBuilder::new(source)
+            .variant(Variant::Monochrome)
+            .build();
+        let [_, r, g, b] = monochrome.schemes.light.primary;
+        assert!(r == g && g == b);
+        let expressive = ThemeBuilder::new(source)
+            .variant(Variant::Expressive)
+            .build();
+        let source_hue = Hct::from_int(source).hue();
+        let primary_hue = Hct::from_int(expressive.schemes.light.primary).hue();
+        assert!(difference_degrees(primary_hue, source_hue) > 90.0);
+    }
+
+    #[test]
+    fn builder_contrast() {
+        let source = [0xff, 0x42, 0x85, 0xf4];
+        let ratio = |theme: &Theme| {
+            let scheme = &theme.schemes.light;
+            ratio_of_tones(
+                lstar_from_argb(scheme.on_surface_variant),
+                lstar_from_argb(scheme.surface_variant),
+            )
+        };
+        let standard = ThemeBuilder::new(source).build();
+        let high = ThemeBuilder::new(source).contrast(1.0).build();
+        let reduced = ThemeBuilder::new(source).contrast(-1.0).build();
+        assert!(ratio(&high) > ratio(&standard));
+        assert!(ratio(&reduced) < ratio(&standard));
+        assert_eq!(high.schemes.light.surface, standard.schemes.light.surface);
+    }
+
+    #[test]
+    fn builder_custom_color() {
+        let success = CustomColor {
+            value: [0xff, 0x00, 0xa0, 0x00],
+            name: String::from("success"),
+            blend: false,
+        };
+        let theme = ThemeBuilder::new([0xff, 0x42, 0x85, 0xf4])
+            .custom_color(success.clone())
+            .build();
+        assert_eq!(theme.custom_colors.len(), 1);
+        assert_eq!(theme.custom_colors[0].value, success.value);
+        assert!(Theme::from_source_color([0xff, 0x42, 0x85, 0xf4])
+            .custom_colors
+            .is_empty());
+    }
+
+    #[test]
+    fn builder_analyzes_source_once() {
+        let source = [0xff, 0x42, 0x85, 0xf4];
+        let builder = (0..3).fold(ThemeBuilder::new(source), |builder, i| {
+            builder.custom_color(CustomColor {
+                value: [0xff, 0x40 * i, 0xa0, 0x00],
+                name: format!("custom-{i}"),
+                blend: true,
+            })
+        });
+        let before = SOURCE_ANALYSES.with(|count| count.get());
+        let theme = builder.build();
+        assert_eq!(SOURCE_ANALYSES.with(|count| count.get()), before + 1);
+        for group in &theme.custom_colors {
+            assert_eq!(group.value, harmonize(group.color.value, source));
+        }
+    }
+
+    #[test]
+    fn builder_error_hue() {
+        let theme = ThemeBuilder::new([0xff, 0x42, 0x85, 0xf4])
+            .error_hue(375.0)
+            .build();
+        assert_approx_eq!(theme.palettes.error.hue(), 15.0);
+        let error_hue = Hct::from_int(theme.schemes.light.error).hue();
+        assert!(difference_degrees(error_hue, 15.0) < 5.0);
+        let standard = Theme::from_source_color([0xff, 0x42, 0x85, 0xf4]);
+        assert_eq!(theme.schemes.light.primary, standard.schemes.light.primary);
+    }
+
+    #[test]
+    fn builder_dark_mode() {
+        let builder = ThemeBuilder::new([0xff, 0x42, 0x85, 0xf4]);
+        let amoled = builder.clone().dark_mode(DarkMode::Amoled).build();
+        let standard = builder.build();
+        assert_eq!(amoled.schemes.dark.surface, [0xff, 0x00, 0x00, 0x00]);
+        assert_ne!(standard.schemes.dark.surface, [0xff, 0x00, 0x00, 0x00]);
+        assert_eq!(amoled.schemes.light.surface, standard.schemes.light.surface);
+        assert_eq!(amoled.schemes.dark.primary, standard.schemes.dark.primary);
+    }
+
+    #[test]
+    fn builder_tone_override() {
+        let source = [0xff, 0x42, 0x85, 0xf4];
+        let standard = Theme::from_source_color(source);
+        let theme = ThemeBuilder::new(source)
+            .tone_override(Role::PrimaryContainer, 85.0, 30.0)
+            .tone_override(Role::Surface, 90.0, 5.0)
+            .tone_override(Role::Surface, 95.0, 6.0)
+            .build();
+        let palettes = &standard.palettes;
+        assert_eq!(
+            theme.schemes.light.primary_container,
+            palettes.primary.tone(85)
+        );
+        assert_eq!(
+            theme.schemes.dark.primary_container,
+            palettes.primary.tone(30)
+        );
+        assert_eq!(theme.schemes.light.surface, palettes.neutral.tone(95));
+        assert_eq!(theme.schemes.dark.surface, palettes.neutral.tone(6));
+        let light = &theme.schemes.light;
+        let dark = &theme.schemes.dark;
+        assert_eq!(
+            light.differing_roles(&standard.schemes.light, 0),
+            [Role::PrimaryContainer, Role::Surface]
+        );
+        // The dark primary container is already tone 30.
+        assert_eq!(
+            dark.differing_roles(&standard.schemes.dark, 0),
+            [Role::Surface]
+        );
+    }
+
+    #[test]
+    fn builder_primary_override_moves_surface_tint() {
+        let source = [0xff, 0x42, 0x85, 0xf4];
+        let standard = Theme::from_source_color(source);
+        let theme = ThemeBuilder::new(source)
+            .tone_override(Role::Primary, 50.0, 70.0)
+            .build();
+        let primary = &standard.palettes.primary;
+        assert_eq!(theme.schemes.light.primary, primary.tone(50));
+        assert_eq!(theme.schemes.light[&Role::SurfaceTint], primary.tone(50));
+        assert_eq!(theme.schemes.dark[&Role::SurfaceTint], primary.tone(70));
+
+        let theme = ThemeBuilder::new(source)
+            .tone_override(Role::Primary, 50.0, 70.0)
+            .tone_override(Role::SurfaceTint, 30.0, 90.0)
+            .build();
+        assert_eq!(theme.schemes.light[&Role::SurfaceTint], primary.tone(30));
+        assert_eq!(theme.schemes.dark[&Role::SurfaceTint], primary.tone(90));
+    }
+
+    #[test]
+    fn builder_palette_options() {
+        let source = [0xff, 0x42, 0x85, 0xf4];
+        let standard = Theme::from_source_color(source);
+        let theme = ThemeBuilder::new(source)
+            .palette_options(CorePaletteOptions {
+                neutral_chroma: 12.0,
+                ..Default::default()
+            })
+            .build();
+        assert_approx_eq!(theme.palettes.neutral.chroma(), 12.0);
+        assert_eq!(
+            theme
+                .schemes
+                .dark
+                .differing_roles(&standard.schemes.dark, 0),
+            [
+                Role::Background,
+                Role::OnBackground,
+                Role::Surface,
+                Role::OnSurface,
+                Role::InverseSurface,
+                Role::InverseOnSurface,
+            ]
+        );
+    }
+
+    #[test]
+    fn builder_palette_options_conflict_with_variant() {
+        let source = [0xff, 0x42, 0x85, 0xf4];
+        let options = CorePaletteOptions {
+            neutral_chroma: 12.0,
+            ..Default::default()
+        };
+        let builder = ThemeBuilder::new(source)
+            .variant(Variant::Vibrant)
+            .palette_options(options);
+        let error = ThemeBuildError::PaletteOptionsWithVariant(Variant::Vibrant);
+        assert_eq!(builder.clone().try_build_dark(), Err(error));
+        assert_eq!(builder.try_build().map(|theme| theme.source), Err(error));
+        let tonal_spot = ThemeBuilder::new(source)
+            .variant(Variant::TonalSpot)
+            .palette_options(options)
+            .try_build()
+            .unwrap();
+        assert_approx_eq!(tonal_spot.palettes.neutral.chroma(), 12.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "not Monochrome")]
+    fn builder_build_panics_on_conflict() {
+        ThemeBuilder::new([0xff, 0x42, 0x85, 0xf4])
+            .palette_options(CorePaletteOptions::default())
+            .variant(Variant::Monochrome)
+            .build();
+    }
+
+    #[test]
+    fn custom_error_palette() {
+        let source = [0xff, 0x42, 0x85, 0xf4];
+        let standard = Theme::from_source_color(source);
+        let error_roles = [
+            Role::Error,
+            Role::OnError,
+            Role::ErrorContainer,
+            Role::OnErrorContainer,
+        ];
+        let orange = ThemeBuilder::new(source)
+            .error_hue(60.0)
+            .error_chroma(60.0)
+            .build();
+        assert_approx_eq!(orange.palettes.error.chroma(), 60.0);
+        let options = ThemeBuilder::new(source)
+            .palette_options(CorePaletteOptions {
+                error_hue: 60.0,
+                error_chroma: 60.0,
+                ..Default::default()
+            })
+            .build();
+        for theme in [orange, options] {
+            for (scheme, standard) in [
+                (&theme.schemes.light, &standard.schemes.light),
+                (&theme.schemes.dark, &standard.schemes.dark),
+            ] {
+                // Light on-error is white whatever the palette.
+                let differing = scheme.differing_roles(standard, 0);
+                assert!(differing.iter().all(|role| error_roles.contains(role)));
+                assert!(differing.len() >= 3);
+                let hue = Hct::from_int(scheme.error).hue();
+                assert!(difference_degrees(hue, 60.0) < 5.0, "{hue}");
+            }
+        }
+    }
+
+    #[test]
+    fn builder_dark_only() {
+        let builder = ThemeBuilder::new([0xff, 0x42, 0x85, 0xf4])
+            .variant(Variant::Vibrant)
+            .contrast(0.5);
+        let dark = builder.clone().build_dark();
+        let theme = builder.build();
+        assert_eq!(dark, theme.schemes.dark);
+    }
+
+    #[test]
+    fn harmonize_custom_colors_batch() {
+        let theme = Theme::from_source_color([0xff, 0x42, 0x85, 0xf4]);
+        let names: Vec<String> = (1..=12).map(|i| format!("chart-{i}")).collect();
+        let mut colors: Vec<(&str, [u8; 4])> = vec![
+            ("success", [0xff, 0x00, 0xa0, 0x00]),
+            ("warning", [0xff, 0xf4, 0xb4, 0x00]),
+            ("info", [0xff, 0x00, 0x8b, 0xd8]),
+        ];
+        for (i, name) in names.iter().enumerate() {
+            colors.push((name, [0xff, (i * 20) as u8, 0x80, (255 - i * 20) as u8]));
+        }
+        let before = SOURCE_ANALYSES.with(|count| count.get());
+        let groups = theme.harmonize_custom_colors(&colors);
+        assert_eq!(SOURCE_ANALYSES.with(|count| count.get()), before + 1);
+        assert_eq!(groups.len(), colors.len());
+        for (group, (name, value)) in groups.iter().zip(&colors) {
+            assert_eq!(group.color.name, *name);
+            assert_eq!(group.value, harmonize(*value, theme.source));
+            let single = custom_color(
+                theme.source,
+                &CustomColor {
+                    value: *value,
+                    name: name.to_string(),
+                    blend: true,
+                },
+            );
+            assert_eq!(group.light.color, single.light.color);
+            assert_eq!(
+                group.dark.on_color_container,
+                single.dark.on_color_container
+            );
+        }
+        assert!(theme.harmonize_custom_colors(&[]).is_empty());
+    }
+
+    #[test]
+    fn custom_color_blend() {
+        let source = [0xff, 0x42, 0x85, 0xf4];
+        let red = |blend| CustomColor {
+            value: [0xff, 0xff, 0x00, 0x00],
+            name: String::from("danger"),
+            blend,
+        };
+        let blended = custom_color(source, &red(true));
+        let unblended = custom_color(source, &red(false));
+        assert_eq!(blended.value, harmonize(red(true).value, source));
+        assert_eq!(unblended.value, red(false).value);
+        let blended_hue = Hct::from_int(blended.light.color).hue();
+        let unblended_hue = Hct::from_int(unblended.light.color).hue();
+        assert!(difference_degrees(blended_hue, unblended_hue) > 5.0);
+        assert_approx_eq!(Hct::from_int(blended.light.color).tone(), 40.0, 1.0);
+        assert_approx_eq!(Hct::from_int(blended.dark.color).tone(), 80.0, 1.0);
+        assert_eq!(unblended.light.on_color, [0xff, 0xff, 0xff, 0xff]);
+    }
+
+    #[test]
+    fn from_source_color_with_custom_colors() {
+        let colors = vec![
+            CustomColor {
+                value: [0xff, 0x00, 0xa0, 0x00],
+                name: String::from("success"),
+                blend: true,
+            },
+            CustomColor {
+                value: [0xff, 0xff, 0xa0, 0x00],
+                name: String::from("warning"),
+                blend: false,
+            },
+        ];
+        let theme = Theme::from_source_color_with_custom_colors(0xff4285f4u32, colors);
+        assert_eq!(theme.custom_colors.len(), 2);
+        assert_eq!(theme.custom_colors[0].color.name, "success");
+        assert_eq!(
+            theme.custom_colors[1].light.color,
+            custom_color(theme.source, &theme.custom_colors[1].color)
+                .light
+                .color
+        );
+        assert!(Theme::from_source_color(0xff4285f4u32)
+            .custom_colors
+            .is_empty());
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn from_image_bytes() {
+        // Mostly #2e7d32, with a yellow band and a gray strip, so the seed is not the Google Blue
+        // that scoring falls back to.
+        let theme = Theme::from_image_bytes(include_bytes!("testdata/seed_2e7d32.png")).unwrap();
+        assert_eq!(theme.source, [0xff, 0x2e, 0x7d, 0x32]);
+        assert_eq!(
+            theme.schemes.light.primary,
+            Theme::from_source_color(theme.source).schemes.light.primary
+        );
+        let transparent = Theme::from_image_bytes(include_bytes!("testdata/transparent.png"));
+        assert!(matches!(
+            transparent,
+            Err(crate::utils::image::ImageError::NoOpaquePixels)
+        ));
+    }
+
+    // theme_4285f4.json is this crate's own serialization, kept as a snapshot of its hex format.
+    // Parity with the TypeScript library is checked by serde_matches_ts_fixture.
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_matches_snapshot() {
+        let theme = Theme::from_source_color(0xff4285f4u32);
+        let fixture: serde_json::Value =
+            serde_json::from_str(include_str!("testdata/theme_4285f4.json")).unwrap();
+        assert_eq!(serde_json::to_value(&theme).unwrap(), fixture);
+        assert_eq!(fixture["schemes"]["light"]["onPrimaryContainer"], "#001a41");
+        assert_eq!(
+            fixture["palettes"]["neutralVariant"]["tones"]["50"],
+            "#74777f"
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    #[ignore = "needs theme_4285f4.ts.json from testdata/generate_ts_fixtures.mjs"]
+    fn serde_matches_ts_fixture() {
+        use crate::scheme::Role;
+        use crate::utils::color_format::{ts_fixture, SerdeColor};
+
+        let fixture = ts_fixture("theme_4285f4.ts.json");
+        let theme = Theme::from_source_color(0xff4285f4u32);
+        let color = |value: &serde_json::Value| {
+            serde_json::from_value::<SerdeColor>(value.clone())
+                .unwrap()
+                .0
+        };
+        assert_eq!(color(&fixture["source"]), theme.source);
+        for (mode, scheme) in [
+            ("light", &theme.schemes.light),
+            ("dark", &theme.schemes.dark),
+        ] {
+            for (role, value) in fixture["schemes"][mode].as_object().unwrap() {
+                let role: Role = role.parse().unwrap();
+                assert_eq!(color(value), scheme[role], "{} {}", mode, role);
+            }
+        }
+        let palettes = &theme.palettes;
+        for (name, palette) in [
+            ("primary", &palettes.primary),
+            ("secondary", &palettes.secondary),
+            ("tertiary", &palettes.tertiary),
+            ("neutral", &palettes.neutral),
+            ("neutralVariant", &palettes.neutral_variant),
+            ("error", &palettes.error),
+        ] {
+            let expected = &fixture["palettes"][name];
+            assert_approx_eq!(palette.hue(), expected["hue"].as_f64().unwrap(), 1e-6);
+            assert_approx_eq!(palette.chroma(), expected["chroma"].as_f64().unwrap(), 1e-6);
+        }
+        assert_eq!(fixture["customColors"], serde_json::json!([]));
+    }
+
+    // Expected colors from the upstream TypeScript scheme and palette tests, read from the
+    // numeric JSON shape that themeFromSourceColor produces.
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_matches_upstream_expectations() {
+        use crate::utils::color_format::{with_color_format, ColorFormat};
+
+        let json = |source: u32| {
+            let theme = Theme::from_source_color(source);
+            with_color_format(ColorFormat::Number, || serde_json::to_value(&theme)).unwrap()
+        };
+
+        let blue = json(0xff0000ff);
+        assert_eq!(blue["source"], 0xff0000ffu32);
+        assert_eq!(blue["schemes"]["light"]["primary"], 0xff343dffu32);
+        assert_eq!(blue["schemes"]["dark"]["primary"], 0xffbec2ffu32);
+        let tones = [100, 95, 90, 80, 70, 60, 50, 40, 20, 10, 0];
+        let primary: [u32; 11] = [
+            0xffffffff, 0xfff1efff, 0xffe0e0ff, 0xffbec2ff, 0xff9da3ff, 0xff7c84ff, 0xff5a64ff,
+            0xff343dff, 0xff0001ac, 0xff00006e, 0xff000000,
+        ];
+        let secondary: [u32; 11] = [
+            0xffffffff, 0xfff1efff, 0xffe1e0f9, 0xffc5c4dd, 0xffa9a9c1, 0xff8f8fa6, 0xff75758b,
+            0xff5c5d72, 0xff2e2f42, 0xff191a2c, 0xff000000,
+        ];
+        for (index, tone) in tones.iter().enumerate() {
+            let tone = tone.to_string();
+            assert_eq!(blue["palettes"]["primary"]["tones"][&tone], primary[index]);
+            assert_eq!(
+                blue["palettes"]["secondary"]["tones"][&tone],
+                secondary[index]
+            );
+        }
+
+        let baseline = json(0xff6750a4);
+        let roles = ["primary", "secondary", "tertiary", "surface", "onSurface"];
+        let light: [u32; 5] = [0xff6750a4, 0xff625b71, 0xff7e5260, 0xfffffbff, 0xff1c1b1e];
+        let dark: [u32; 5] = [0xffcfbcff, 0xffcbc2db, 0xffefb8c8, 0xff1c1b1e, 0xffe6e1e6];
+        for (mode, expected) in [("light", light), ("dark", dark)] {
+            for (role, expected) in roles.iter().zip(expected) {
+                assert_eq!(baseline["schemes"][mode][role], expected, "{mode} {role}");
+            }
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_numeric_colors() {
+        use crate::utils::color::u32_from_argb;
+        use crate::utils::color_format::{with_color_format, ColorFormat};
+
+        let theme = Theme::from_source_color(0xff4285f4u32);
+        let json = with_color_format(ColorFormat::Number, || serde_json::to_value(&theme)).unwrap();
+        assert_eq!(json["source"], 0xff4285f4u32);
+        assert_eq!(
+            json["schemes"]["dark"]["primary"],
+            u32_from_argb(theme.schemes.dark.primary)
+        );
+        let back: Theme = serde_json::from_value(json).unwrap();
+        assert_eq!(back.schemes.dark.primary, theme.schemes.dark.primary);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let colors = vec![CustomColor {
+            value: [0xff, 0x00, 0xa0, 0x00],
+            name: String::from("success"),
+            blend: true,
+        }];
+        let theme = Theme::from_source_color_with_custom_colors(0xff4285f4u32, colors);
+        let json = serde_json::to_string(&theme).unwrap();
+        assert!(json.contains("\"customColors\":[{\"color\":{\"value\":\"#00a000\""));
+        assert!(json.contains("\"onColorContainer\""));
+        let back: Theme = serde_json::from_str(&json).unwrap();
+        assert_eq!(serde_json::to_string(&back).unwrap(), json);
+        let original = theme.clone();
+        for tone in [35, 40, 98] {
+            assert_eq!(
+                back.palettes.primary.tone(tone),
+                original.palettes.primary.tone(tone)
+            );
         }
+        assert_eq!(
+            back.custom_colors[0].light.color,
+            theme.custom_colors[0].light.color
+        );
     }
 }
@@ -0,0 +1,138 @@
+//! Comparing two themes role by role.
+
+use super::Theme;
+use crate::hct::cam16::Cam16;
+use crate::scheme::{Role, Scheme};
+use crate::utils::color::lstar_from_argb;
+use crate::utils::string::hex_from_argb;
+use std::fmt;
+
+/// A role whose color differs between two themes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RoleChange {
+    pub role: Role,
+    pub old: [u8; 4],
+    pub new: [u8; 4],
+    /// Tone of `new` minus tone of `old`.
+    pub tone_delta: f64,
+    /// CAM16-UCS distance between `old` and `new`.
+    pub distance: f64,
+}
+
+/// The roles that differ between two themes, per scheme, in [`Role::iterator`] order.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ThemeDiff {
+    pub light: Vec<RoleChange>,
+    pub dark: Vec<RoleChange>,
+}
+
+impl ThemeDiff {
+    /// Whether every role of both schemes is the same.
+    pub fn is_empty(&self) -> bool {
+        self.light.is_empty() && self.dark.is_empty()
+    }
+}
+
+fn diff_schemes(old: &Scheme, new: &Scheme) -> Vec<RoleChange> {
+    old.iter()
+        .zip(new.values())
+        .filter(|((_, old), new)| old != new)
+        .map(|((role, old), new)| RoleChange {
+            role,
+            old,
+            new,
+            tone_delta: lstar_from_argb(new) - lstar_from_argb(old),
+            distance: Cam16::from_argb(old).distance(&Cam16::from_argb(new)),
+        })
+        .collect()
+}
+
+impl Theme {
+    /// List the roles whose colors differ from another theme
+    ///
+    /// @param other Theme to compare against, whose colors are the "new" side of each change
+    /// @return Changed roles of the light and dark schemes
+    pub fn diff(&self, other: &Theme) -> ThemeDiff {
+        ThemeDiff {
+            light: diff_schemes(&self.schemes.light, &other.schemes.light),
+            dark: diff_schemes(&self.schemes.dark, &other.schemes.dark),
+        }
+    }
+}
+
+/// A table with a row per change, ex. `light  Primary  #005ac1  #0b57d0  +1.25  3.48`.
+impl fmt::Display for ThemeDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return writeln!(f, "no changes");
+        }
+        writeln!(
+            f,
+            "{:<6} {:<24} {:<8} {:<8} {:>7} {:>8}",
+            "scheme", "role", "old", "new", "tone", "distance"
+        )?;
+        for (scheme, changes) in [("light", &self.light), ("dark", &self.dark)] {
+            for change in changes {
+                writeln!(
+                    f,
+                    "{:<6} {:<24} {:<8} {:<8} {:>+7.2} {:>8.2}",
+                    scheme,
+                    format!("{:?}", change.role),
+                    hex_from_argb(change.old),
+                    hex_from_argb(change.new),
+                    change.tone_delta,
+                    change.distance
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_approx_eq::assert_approx_eq;
+
+    #[test]
+    fn same_theme() {
+        let theme = Theme::from_source_color(0xff4285f4u32);
+        let diff = theme.diff(&theme.clone());
+        assert!(diff.is_empty());
+        assert_eq!(diff.to_string(), "no changes\n");
+    }
+
+    #[test]
+    fn nearby_seeds() {
+        let a = Theme::from_source_color(0xff4285f4u32);
+        let b = Theme::from_source_color(0xff4a85f0u32);
+        let forward = a.diff(&b);
+        let backward = b.diff(&a);
+        assert!(!forward.is_empty());
+        for (forward, backward) in [
+            (&forward.light, &backward.light),
+            (&forward.dark, &backward.dark),
+        ] {
+            assert_eq!(forward.len(), backward.len());
+            for (f, b) in forward.iter().zip(backward) {
+                assert_eq!(f.role, b.role);
+                assert_eq!((f.old, f.new), (b.new, b.old));
+                assert_approx_eq!(f.tone_delta, -b.tone_delta);
+                assert_approx_eq!(f.distance, b.distance);
+                assert!(f.distance > 0.0);
+            }
+        }
+        let table = forward.to_string();
+        assert!(table.starts_with("scheme role"));
+        assert_eq!(
+            table.lines().count(),
+            1 + forward.light.len() + forward.dark.len()
+        );
+        let first = &forward.light[0];
+        assert!(table
+            .lines()
+            .nth(1)
+            .unwrap()
+            .contains(&hex_from_argb(first.new)));
+    }
+}
@@ -0,0 +1,934 @@
+//! Exporters that write a [`Theme`] in the formats of other platforms: CSS custom properties,
+//! SCSS, Tailwind, Android resources, Base16/Base24 schemes, Xresources, and GTK named colors.
+
+use super::{harmonized_custom_color, CustomColor, Theme};
+use crate::blend::Harmonizer;
+use crate::palettes::tonal::TonalPalette;
+use crate::scheme::{Role, Role::*, Scheme};
+use crate::utils::string::{css_rgb_from_argb, hex_from_argb, hex_from_argb_with, HexFormat};
+use std::fmt::Write;
+
+/// How [`Theme::to_css`] selects between the light and dark schemes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CssSchemeSelector {
+    /// `.light` and `.dark` classes.
+    #[default]
+    Classes,
+    /// `:root` inside `@media (prefers-color-scheme: light)` and `(prefers-color-scheme: dark)`.
+    MediaQuery,
+}
+
+/// How [`Theme::to_css`] writes color values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CssColorFormat {
+    /// Hex, ex. `#005ac1`.
+    #[default]
+    Hex,
+    /// Space-separated `rgb()`, ex. `rgb(0 90 193)`.
+    Rgb,
+}
+
+/// Options for [`Theme::to_css`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CssOptions {
+    /// Prepended to every property name. Defaults to `--md-sys-color-`.
+    pub prefix: String,
+    pub selector: CssSchemeSelector,
+    pub color_format: CssColorFormat,
+}
+
+impl Default for CssOptions {
+    fn default() -> Self {
+        CssOptions {
+            prefix: String::from("--md-sys-color-"),
+            selector: CssSchemeSelector::default(),
+            color_format: CssColorFormat::default(),
+        }
+    }
+}
+
+/// How [`Theme::to_scss`] writes the schemes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ScssForm {
+    /// A variable per role and scheme, ex. `$md-sys-color-primary-light: #005ac1;`.
+    #[default]
+    Variables,
+    /// A map per scheme, ex. `$light-scheme: (primary: #005ac1, ...);`.
+    Maps,
+}
+
+/// Options for [`Theme::to_scss`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScssOptions {
+    /// Prepended to every variable name in [`ScssForm::Variables`]. Map keys are not prefixed.
+    pub prefix: String,
+    pub form: ScssForm,
+}
+
+impl Default for ScssOptions {
+    fn default() -> Self {
+        ScssOptions {
+            prefix: String::from("md-sys-color-"),
+            form: ScssForm::default(),
+        }
+    }
+}
+
+/// GTK named colors and the roles they take their color from, as used by [`Theme::to_gtk_css`].
+/// Pass a modified copy to [`Theme::to_gtk_css_with`] to customize the mapping.
+///
+/// Follows the Material You presets of GTK theming tools: accents from the primary role,
+/// destructive and error colors from the error role, windows and views from the background and
+/// surface, and raised surfaces (cards, dialogs, popovers) from the surface variant.
+pub static GTK_NAMED_COLORS: &[(&str, Role)] = &[
+    ("accent_color", Role::Primary),
+    ("accent_bg_color", Role::Primary),
+    ("accent_fg_color", Role::OnPrimary),
+    ("destructive_color", Role::Error),
+    ("destructive_bg_color", Role::Error),
+    ("destructive_fg_color", Role::OnError),
+    ("error_color", Role::Error),
+    ("error_bg_color", Role::Error),
+    ("error_fg_color", Role::OnError),
+    ("window_bg_color", Role::Background),
+    ("window_fg_color", Role::OnBackground),
+    ("view_bg_color", Role::Surface),
+    ("view_fg_color", Role::OnSurface),
+    ("headerbar_bg_color", Role::Surface),
+    ("headerbar_fg_color", Role::OnSurface),
+    ("headerbar_border_color", Role::OutlineVariant),
+    ("headerbar_backdrop_color", Role::Background),
+    ("sidebar_bg_color", Role::SurfaceVariant),
+    ("sidebar_fg_color", Role::OnSurfaceVariant),
+    ("card_bg_color", Role::SurfaceVariant),
+    ("card_fg_color", Role::OnSurfaceVariant),
+    ("dialog_bg_color", Role::SurfaceVariant),
+    ("dialog_fg_color", Role::OnSurfaceVariant),
+    ("popover_bg_color", Role::SurfaceVariant),
+    ("popover_fg_color", Role::OnSurfaceVariant),
+];
+
+/// Options for [`Theme::to_tailwind_json_with`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TailwindOptions {
+    /// Export the dark scheme instead of the light scheme.
+    pub dark: bool,
+    /// Add tones 10 through 99 of every palette, ex. `"primary-40"`.
+    pub tonal_ramps: bool,
+}
+
+/// Tones written for each palette with [`TailwindOptions::tonal_ramps`].
+const TAILWIND_RAMP_TONES: [u8; 11] = [10, 20, 30, 40, 50, 60, 70, 80, 90, 95, 99];
+
+enum TailwindValue {
+    Color([u8; 4]),
+    Family(Vec<(String, [u8; 4])>),
+}
+
+/// libadwaita's `success_color` and `warning_color`, before harmonizing.
+const GTK_SUCCESS: [u8; 4] = [0xff, 0x26, 0xa2, 0x69];
+const GTK_WARNING: [u8; 4] = [0xff, 0xe5, 0xa5, 0x0a];
+
+/// Red, green, yellow, blue, magenta, and cyan, in ANSI order, before harmonizing.
+const ANSI_HUES: [[u8; 4]; 6] = [
+    [0xff, 0xff, 0x00, 0x00],
+    [0xff, 0x00, 0xff, 0x00],
+    [0xff, 0xff, 0xff, 0x00],
+    [0xff, 0x00, 0x00, 0xff],
+    [0xff, 0xff, 0x00, 0xff],
+    [0xff, 0x00, 0xff, 0xff],
+];
+
+/// Names and colors of a scheme, in the order they are exported.
+type NamedColors = Vec<(String, [u8; 4])>;
+
+impl Theme {
+    /// Export the theme as CSS custom properties
+    ///
+    /// Every role of both schemes is written as a property named after the role, ex.
+    /// `--md-sys-color-on-primary-container: #001a41;`. Each custom color adds four properties,
+    /// ex. `--md-sys-color-success`, `-on-success`, `-success-container`, and
+    /// `-on-success-container`. Custom color names are kebab-cased and stripped of everything
+    /// but `a-z`, `0-9` and `-`, so any name gives valid CSS.
+    ///
+    /// @param options Property prefix, scheme selectors, and color format
+    /// @return CSS rules for the light scheme followed by the dark scheme
+    pub fn to_css(&self, options: CssOptions) -> String {
+        let mut css = String::new();
+        for (index, (name, colors)) in self.named_colors().into_iter().enumerate() {
+            if index > 0 {
+                css.push('\n');
+            }
+            let indent = match options.selector {
+                CssSchemeSelector::Classes => {
+                    let _ = writeln!(css, ".{name} {{");
+                    "  "
+                }
+                CssSchemeSelector::MediaQuery => {
+                    let _ = writeln!(css, "@media (prefers-color-scheme: {name}) {{");
+                    css.push_str("  :root {\n");
+                    "    "
+                }
+            };
+            for (property, argb) in colors {
+                let value = match options.color_format {
+                    CssColorFormat::Hex => hex_from_argb(argb),
+                    CssColorFormat::Rgb => css_rgb_from_argb(argb, 2),
+                };
+                let _ = writeln!(css, "{indent}{}{property}: {value};", options.prefix);
+            }
+            if options.selector == CssSchemeSelector::MediaQuery {
+                css.push_str("  }\n");
+            }
+            css.push_str("}\n");
+        }
+        css
+    }
+
+    /// Export the theme as an Android `colors.xml` resource file
+    ///
+    /// Colors are named as in the Material Theme Builder's Android export, ex.
+    /// `md_theme_light_onPrimaryContainer`, and written as `#AARRGGBB`. The source color is
+    /// written as `seed`.
+    ///
+    /// @return A `<resources>` document with both schemes
+    pub fn to_android_xml(&self) -> String {
+        let hex = |argb: [u8; 4]| hex_from_argb_with(argb, HexFormat::android());
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<resources>\n");
+        let _ = writeln!(xml, "    <color name=\"seed\">{}</color>", hex(self.source));
+        for (name, scheme) in [("light", &self.schemes.light), ("dark", &self.schemes.dark)] {
+            for (role, argb) in scheme {
+                let _ = writeln!(
+                    xml,
+                    "    <color name=\"md_theme_{name}_{role}\">{}</color>",
+                    hex(argb)
+                );
+            }
+        }
+        xml.push_str("</resources>\n");
+        xml
+    }
+
+    /// Export the theme as SCSS variables or maps
+    ///
+    /// Names follow [`Theme::to_css`]: every role of both schemes, then four entries per custom
+    /// color. Variables end in the scheme name, ex. `$md-sys-color-on-primary-dark`.
+    ///
+    /// @param options Variable prefix and form
+    /// @return SCSS for the light scheme followed by the dark scheme
+    pub fn to_scss(&self, options: ScssOptions) -> String {
+        let mut scss = String::new();
+        for (index, (name, colors)) in self.named_colors().into_iter().enumerate() {
+            if index > 0 {
+                scss.push('\n');
+            }
+            match options.form {
+                ScssForm::Variables => {
+                    for (key, argb) in colors {
+                        let value = hex_from_argb(argb);
+                        let _ = writeln!(scss, "${}{key}-{name}: {value};", options.prefix);
+                    }
+                }
+                ScssForm::Maps => {
+                    let _ = writeln!(scss, "${name}-scheme: (");
+                    for (key, argb) in colors {
+                        let _ = writeln!(scss, "  {key}: {},", hex_from_argb(argb));
+                    }
+                    scss.push_str(");\n");
+                }
+            }
+        }
+        scss
+    }
+
+    /// Export the dark scheme as a Base16 scheme, for terminal and editor theming
+    ///
+    /// Slots are filled from the palettes, with tones matching the dark scheme where a role fits:
+    ///
+    /// | Slots | Source |
+    /// | --- | --- |
+    /// | `base00`-`base03` | surfaces: neutral 10, neutral 20, neutral variant 30 and 60 |
+    /// | `base04`-`base07` | on-colors: neutral variant 80, neutral 90, 95, and 99 |
+    /// | `base08`, `base09` | error 80 and 90 |
+    /// | `base0A`, `base0B` | tertiary 90 and 80 |
+    /// | `base0C`, `base0F` | secondary 80 and 90 |
+    /// | `base0D`, `base0E` | primary 80 and 90 |
+    ///
+    /// @param name Name of the scheme
+    /// @return YAML in the Base16 scheme format, with colors as `rrggbb`
+    pub fn to_base16(&self, name: &str) -> String {
+        base16_yaml(name, &self.base16_slots(false))
+    }
+
+    /// Export the dark scheme as a Base24 scheme
+    ///
+    /// The first 16 slots are those of [`Theme::to_base16`]. `base10` and `base11` are neutral
+    /// 6 and 4, and the bright colors `base12`-`base17` are 5 tones lighter than `base08`,
+    /// `base0A`-`base0E`.
+    ///
+    /// @param name Name of the scheme
+    /// @return YAML in the Base24 scheme format, with colors as `rrggbb`
+    pub fn to_base24(&self, name: &str) -> String {
+        base16_yaml(name, &self.base16_slots(true))
+    }
+
+    fn base16_slots(&self, base24: bool) -> Vec<[u8; 4]> {
+        let palettes = &self.palettes;
+        let mut slots = vec![
+            palettes.neutral.tone(10),
+            palettes.neutral.tone(20),
+            palettes.neutral_variant.tone(30),
+            palettes.neutral_variant.tone(60),
+            palettes.neutral_variant.tone(80),
+            palettes.neutral.tone(90),
+            palettes.neutral.tone(95),
+            palettes.neutral.tone(99),
+            palettes.error.tone(80),
+            palettes.error.tone(90),
+            palettes.tertiary.tone(90),
+            palettes.tertiary.tone(80),
+            palettes.secondary.tone(80),
+            palettes.primary.tone(80),
+            palettes.primary.tone(90),
+            palettes.secondary.tone(90),
+        ];
+        if base24 {
+            slots.extend([
+                palettes.neutral.tone(6),
+                palettes.neutral.tone(4),
+                palettes.error.tone(85),
+                palettes.tertiary.tone(95),
+                palettes.tertiary.tone(85),
+                palettes.secondary.tone(85),
+                palettes.primary.tone(85),
+                palettes.primary.tone(95),
+            ]);
+        }
+        slots
+    }
+
+    /// The 16 ANSI terminal colors for a scheme
+    ///
+    /// Black and white come from the neutral palettes. Red, green, yellow, blue, magenta, and
+    /// cyan start from the sRGB primaries and secondaries, harmonized toward the source color so
+    /// they suit the theme while staying recognizable; their tones keep them readable on the
+    /// scheme's surface, with the bright colors 10 tones further from it.
+    ///
+    /// @param dark Whether the colors are for the dark scheme
+    /// @return `color0` through `color15`
+    pub fn ansi_colors(&self, dark: bool) -> [[u8; 4]; 16] {
+        let neutral = &self.palettes.neutral;
+        let neutral_variant = &self.palettes.neutral_variant;
+        let (black, bright_black, white, bright_white) = if dark {
+            (
+                neutral.tone(20),
+                neutral_variant.tone(60),
+                neutral.tone(90),
+                neutral.tone(99),
+            )
+        } else {
+            (
+                neutral.tone(10),
+                neutral_variant.tone(40),
+                neutral.tone(90),
+                neutral.tone(99),
+            )
+        };
+        let (normal, bright) = if dark { (70, 80) } else { (40, 30) };
+        let mut colors = [[0; 4]; 16];
+        colors[0] = black;
+        colors[7] = white;
+        colors[8] = bright_black;
+        colors[15] = bright_white;
+        let harmonizer = Harmonizer::new(self.source);
+        for (index, hue_color) in ANSI_HUES.iter().enumerate() {
+            let palette = TonalPalette::from_int(harmonizer.harmonize(*hue_color));
+            colors[index + 1] = palette.tone(normal);
+            colors[index + 9] = palette.tone(bright);
+        }
+        colors
+    }
+
+    /// Export a scheme as an Xresources snippet for terminals
+    ///
+    /// Writes `*.background` and `*.foreground` from the surface and on-surface roles,
+    /// `*.cursorColor` from the primary role, and `*.color0` through `*.color15` from
+    /// [`Theme::ansi_colors`].
+    ///
+    /// @param dark Whether to export the dark scheme
+    /// @return Xresources lines, ex. `*.color1: #ffb4a9`
+    pub fn to_xresources(&self, dark: bool) -> String {
+        let scheme = if dark {
+            &self.schemes.dark
+        } else {
+            &self.schemes.light
+        };
+        let mut xresources = String::new();
+        let _ = writeln!(
+            xresources,
+            "*.background: {}",
+            hex_from_argb(scheme.surface)
+        );
+        let _ = writeln!(
+            xresources,
+            "*.foreground: {}",
+            hex_from_argb(scheme.on_surface)
+        );
+        let _ = writeln!(
+            xresources,
+            "*.cursorColor: {}",
+            hex_from_argb(scheme.primary)
+        );
+        for (index, argb) in self.ansi_colors(dark).iter().enumerate() {
+            let _ = writeln!(xresources, "*.color{index}: {}", hex_from_argb(*argb));
+        }
+        xresources
+    }
+
+    /// Export a scheme as GTK named colors for libadwaita
+    ///
+    /// Roles are mapped with [`GTK_NAMED_COLORS`]. The `success_*` and `warning_*` colors have no
+    /// role, so they are libadwaita's own green and yellow, harmonized toward the source color.
+    ///
+    /// @param dark Whether to export the dark scheme
+    /// @return `@define-color` declarations, ex. `@define-color accent_bg_color #005ac1;`
+    pub fn to_gtk_css(&self, dark: bool) -> String {
+        self.to_gtk_css_with(dark, GTK_NAMED_COLORS)
+    }
+
+    /// Like [`Theme::to_gtk_css`], with a custom mapping of GTK names to roles
+    ///
+    /// @param dark Whether to export the dark scheme
+    /// @param named_colors GTK color names and the roles they take their color from
+    /// @return `@define-color` declarations
+    pub fn to_gtk_css_with(&self, dark: bool, named_colors: &[(&str, Role)]) -> String {
+        let scheme = if dark {
+            &self.schemes.dark
+        } else {
+            &self.schemes.light
+        };
+        let mut css = String::new();
+        let mut define = |name: &str, argb: [u8; 4]| {
+            let _ = writeln!(css, "@define-color {name} {};", hex_from_argb(argb));
+        };
+        for (name, role) in named_colors {
+            define(name, scheme[role]);
+        }
+        let harmonizer = Harmonizer::new(self.source);
+        for (name, value) in [("success", GTK_SUCCESS), ("warning", GTK_WARNING)] {
+            let color = CustomColor {
+                value,
+                name: String::from(name),
+                blend: true,
+            };
+            let group = harmonized_custom_color(&harmonizer, &color);
+            let colors = if dark { group.dark } else { group.light };
+            define(&format!("{name}_color"), colors.color);
+            define(&format!("{name}_bg_color"), colors.color);
+            define(&format!("{name}_fg_color"), colors.on_color);
+        }
+        css
+    }
+
+    /// Export the light scheme as a Tailwind `theme.extend.colors` JSON object
+    ///
+    /// A shortcut for [`Theme::to_tailwind_json_with`] with the default options.
+    pub fn to_tailwind_json(&self) -> String {
+        self.to_tailwind_json_with(TailwindOptions::default())
+    }
+
+    /// Export a scheme as a Tailwind `theme.extend.colors` JSON object
+    ///
+    /// Roles are nested per family, ex. `"primary": {"DEFAULT": ..., "container": ...,
+    /// "on": ..., "on-container": ...}`, and each custom color adds a family of its own. With
+    /// [`TailwindOptions::tonal_ramps`], tones of each palette are added as `"primary-10"`
+    /// through `"primary-99"`.
+    ///
+    /// @param options Scheme and whether to include tonal ramps
+    /// @return JSON text, indented by two spaces
+    pub fn to_tailwind_json_with(&self, options: TailwindOptions) -> String {
+        let scheme = if options.dark {
+            &self.schemes.dark
+        } else {
+            &self.schemes.light
+        };
+        let family = |roles: &[(&str, Role)]| {
+            TailwindValue::Family(
+                roles
+                    .iter()
+                    .map(|(key, role)| (key.to_string(), scheme[role]))
+                    .collect(),
+            )
+        };
+        let accent = |color: Role, on: Role, container: Role, on_container: Role| {
+            family(&[
+                ("DEFAULT", color),
+                ("container", container),
+                ("on", on),
+                ("on-container", on_container),
+            ])
+        };
+        let mut entries = vec![
+            (
+                String::from("primary"),
+                accent(Primary, OnPrimary, PrimaryContainer, OnPrimaryContainer),
+            ),
+            (
+                String::from("secondary"),
+                accent(
+                    Secondary,
+                    OnSecondary,
+                    SecondaryContainer,
+                    OnSecondaryContainer,
+                ),
+            ),
+            (
+                String::from("tertiary"),
+                accent(Tertiary, OnTertiary, TertiaryContainer, OnTertiaryContainer),
+            ),
+            (
+                String::from("error"),
+                accent(Error, OnError, ErrorContainer, OnErrorContainer),
+            ),
+            (
+                String::from("background"),
+                family(&[("DEFAULT", Background), ("on", OnBackground)]),
+            ),
+            (
+                String::from("surface"),
+                family(&[
+                    ("DEFAULT", Surface),
+                    ("variant", SurfaceVariant),
+                    ("on", OnSurface),
+                    ("on-variant", OnSurfaceVariant),
+                    ("inverse", InverseSurface),
+                    ("on-inverse", InverseOnSurface),
+                    ("tint", SurfaceTint),
+                ]),
+            ),
+            (
+                String::from("outline"),
+                family(&[("DEFAULT", Outline), ("variant", OutlineVariant)]),
+            ),
+            (
+                String::from("inverse-primary"),
+                TailwindValue::Color(scheme.inverse_primary),
+            ),
+            (String::from("shadow"), TailwindValue::Color(scheme.shadow)),
+            (String::from("scrim"), TailwindValue::Color(scheme.scrim)),
+        ];
+        for group in &self.custom_colors {
+            let colors = if options.dark {
+                &group.dark
+            } else {
+                &group.light
+            };
+            entries.push((
+                kebab_case(&group.color.name),
+                TailwindValue::Family(vec![
+                    (String::from("DEFAULT"), colors.color),
+                    (String::from("container"), colors.color_container),
+                    (String::from("on"), colors.on_color),
+                    (String::from("on-container"), colors.on_color_container),
+                ]),
+            ));
+        }
+        if options.tonal_ramps {
+            let palettes = [
+                ("primary", &self.palettes.primary),
+                ("secondary", &self.palettes.secondary),
+                ("tertiary", &self.palettes.tertiary),
+                ("error", &self.palettes.error),
+                ("neutral", &self.palettes.neutral),
+                ("neutral-variant", &self.palettes.neutral_variant),
+            ];
+            for (name, palette) in palettes {
+                for tone in TAILWIND_RAMP_TONES {
+                    entries.push((
+                        format!("{name}-{tone}"),
+                        TailwindValue::Color(palette.tone(tone)),
+                    ));
+                }
+            }
+        }
+
+        let mut json = String::from("{\n");
+        for (index, (key, value)) in entries.iter().enumerate() {
+            let separator = if index + 1 < entries.len() { "," } else { "" };
+            match value {
+                TailwindValue::Color(argb) => {
+                    let _ = writeln!(
+                        json,
+                        "  {}: \"{}\"{separator}",
+                        json_string(key),
+                        hex_from_argb(*argb)
+                    );
+                }
+                TailwindValue::Family(colors) => {
+                    let _ = writeln!(json, "  {}: {{", json_string(key));
+                    for (color_index, (color_key, argb)) in colors.iter().enumerate() {
+                        let color_separator = if color_index + 1 < colors.len() {
+                            ","
+                        } else {
+                            ""
+                        };
+                        let _ = writeln!(
+                            json,
+                            "    \"{color_key}\": \"{}\"{color_separator}",
+                            hex_from_argb(*argb)
+                        );
+                    }
+                    let _ = writeln!(json, "  }}{separator}");
+                }
+            }
+        }
+        json.push_str("}\n");
+        json
+    }
+
+    /// Kebab-case names and colors of every role and custom color, for each scheme. Custom color
+    /// names are made safe identifiers with [`identifier`].
+    fn named_colors(&self) -> [(&'static str, NamedColors); 2] {
+        let named = |scheme: &Scheme, dark: bool| {
+            let mut colors: NamedColors = scheme
+                .iter()
+                .map(|(role, argb)| (role.as_kebab().to_string(), argb))
+                .collect();
+            for group in &self.custom_colors {
+                let name = identifier(&group.color.name);
+                let group_colors = if dark { &group.dark } else { &group.light };
+                colors.push((name.clone(), group_colors.color));
+                colors.push((format!("on-{name}"), group_colors.on_color));
+                colors.push((format!("{name}-container"), group_colors.color_container));
+                colors.push((
+                    format!("on-{name}-container"),
+                    group_colors.on_color_container,
+                ));
+            }
+            colors
+        };
+        [
+            ("light", named(&self.schemes.light, false)),
+            ("dark", named(&self.schemes.dark, true)),
+        ]
+    }
+}
+
+/// Writes a Base16 or Base24 scheme file, with slots named `base00` through `base17`.
+fn base16_yaml(name: &str, slots: &[[u8; 4]]) -> String {
+    let escaped = name.replace('\\', "\\\\").replace('"', "\\\"");
+    let mut yaml = format!("scheme: \"{escaped}\"\nauthor: \"pymonet\"\n");
+    for (index, argb) in slots.iter().enumerate() {
+        let hex = hex_from_argb(*argb);
+        let _ = writeln!(yaml, "base{index:02X}: \"{}\"", &hex[1..]);
+    }
+    yaml
+}
+
+/// Quotes `value` as a JSON string.
+fn json_string(value: &str) -> String {
+    let mut json = String::with_capacity(value.len() + 2);
+    json.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(json, "\\u{:04x}", c as u32);
+            }
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+/// Converts `OnPrimaryContainer` or `Brand accent` to `on-primary-container` or `brand-accent`.
+fn kebab_case(name: &str) -> String {
+    let mut kebab = String::with_capacity(name.len() + 4);
+    for c in name.trim().chars() {
+        if c.is_uppercase() && !kebab.is_empty() && !kebab.ends_with('-') {
+            kebab.push('-');
+        }
+        if c.is_whitespace() || c == '_' {
+            if !kebab.ends_with('-') {
+                kebab.push('-');
+            }
+        } else {
+            kebab.extend(c.to_lowercase());
+        }
+    }
+    kebab
+}
+
+/// [`kebab_case`] restricted to `a-z`, `0-9` and `-`, for names written unquoted into CSS and
+/// SCSS. A name left empty or starting with a digit gets a `custom` prefix.
+fn identifier(name: &str) -> String {
+    let kebab: String = kebab_case(name)
+        .chars()
+        .filter(|c| matches!(c, 'a'..='z' | '0'..='9' | '-'))
+        .collect();
+    let words: Vec<&str> = kebab.split('-').filter(|word| !word.is_empty()).collect();
+    let identifier = words.join("-");
+    match identifier.chars().next() {
+        None => String::from("custom"),
+        Some(c) if c.is_ascii_digit() => format!("custom-{identifier}"),
+        Some(_) => identifier,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::color::lstar_from_argb;
+
+    #[test]
+    fn kebab_case_names() {
+        assert_eq!(kebab_case("OnPrimaryContainer"), "on-primary-container");
+        assert_eq!(kebab_case("Primary"), "primary");
+        assert_eq!(kebab_case("Brand accent"), "brand-accent");
+        assert_eq!(kebab_case(" success_Color "), "success-color");
+    }
+
+    #[test]
+    fn identifier_names() {
+        assert_eq!(identifier("Brand accent"), "brand-accent");
+        assert_eq!(identifier("x;} body { color: red } /*"), "x-body-color-red");
+        assert_eq!(identifier("café--au  lait"), "caf-au-lait");
+        assert_eq!(identifier("2nd"), "custom-2nd");
+        assert_eq!(identifier("\\"), "custom");
+    }
+
+    #[test]
+    fn css_and_scss_sanitize_hostile_names() {
+        let mut theme = success_theme();
+        theme.custom_colors[0].color.name = String::from("evil;} :root { --x: url(\"a\") } /*");
+        let is_identifier = |name: &str| {
+            name.chars()
+                .all(|c| matches!(c, 'a'..='z' | '0'..='9' | '-'))
+        };
+        let css = theme.to_css(CssOptions::default());
+        for line in css.lines().filter(|line| line.contains("--md-sys-color-")) {
+            let (property, value) = line.trim().split_once(": ").unwrap();
+            assert!(is_identifier(property), "{line}");
+            assert!(value.starts_with('#') && value.ends_with(';'), "{line}");
+        }
+        assert!(css.contains("--md-sys-color-on-evil-root-x-urla-container: "));
+        let plain = success_theme().to_css(CssOptions::default());
+        assert_eq!(css.matches('{').count(), plain.matches('{').count());
+        assert_eq!(css.matches('}').count(), plain.matches('}').count());
+
+        for form in [ScssForm::Variables, ScssForm::Maps] {
+            let scss = theme.to_scss(ScssOptions {
+                form,
+                ..Default::default()
+            });
+            for line in scss.lines().filter(|line| line.contains(": #")) {
+                let name = line
+                    .trim()
+                    .split(':')
+                    .next()
+                    .unwrap()
+                    .trim_start_matches('$');
+                assert!(is_identifier(name), "{line}");
+            }
+            assert!(scss.contains("evil-root-x-urla"));
+            assert!(!scss.contains("/*"));
+        }
+    }
+
+    #[test]
+    fn css_matches_golden_file() {
+        let colors = vec![CustomColor {
+            value: [0xff, 0x00, 0xa0, 0x00],
+            name: String::from("Success"),
+            blend: true,
+        }];
+        let theme = Theme::from_source_color_with_custom_colors(0xff4285f4u32, colors);
+        assert_eq!(
+            theme.to_css(CssOptions::default()),
+            include_str!("../testdata/theme_4285f4.css")
+        );
+    }
+
+    #[test]
+    fn android_xml_matches_golden_file() {
+        let theme = Theme::from_source_color(0xff6750a4u32);
+        assert_eq!(
+            theme.to_android_xml(),
+            include_str!("../testdata/theme_6750a4.xml")
+        );
+    }
+
+    #[test]
+    fn css_options() {
+        let theme = Theme::from_source_color(0xff4285f4u32);
+        let css = theme.to_css(CssOptions {
+            prefix: String::from("--color-"),
+            selector: CssSchemeSelector::MediaQuery,
+            color_format: CssColorFormat::Rgb,
+        });
+        assert!(css.starts_with(
+            "@media (prefers-color-scheme: light) {\n  :root {\n    --color-primary: rgb(0 90 193);\n"
+        ));
+        assert!(css.contains("\n  }\n}\n\n@media (prefers-color-scheme: dark) {\n  :root {\n"));
+        assert!(css.contains("    --color-on-primary-container: rgb(216 226 255);\n"));
+        assert!(css.ends_with("    --color-surface-tint: rgb(173 198 255);\n  }\n}\n"));
+        assert!(!css.contains("md-sys"));
+    }
+
+    fn success_theme() -> Theme {
+        let colors = vec![CustomColor {
+            value: [0xff, 0x00, 0xa0, 0x00],
+            name: String::from("Success"),
+            blend: true,
+        }];
+        Theme::from_source_color_with_custom_colors(0xff4285f4u32, colors)
+    }
+
+    #[test]
+    fn scss_variables_match_golden_file() {
+        assert_eq!(
+            success_theme().to_scss(ScssOptions::default()),
+            include_str!("../testdata/theme_4285f4_variables.scss")
+        );
+    }
+
+    #[test]
+    fn scss_maps_match_golden_file() {
+        let options = ScssOptions {
+            form: ScssForm::Maps,
+            ..ScssOptions::default()
+        };
+        assert_eq!(
+            success_theme().to_scss(options),
+            include_str!("../testdata/theme_4285f4_maps.scss")
+        );
+    }
+
+    #[test]
+    fn base16_matches_golden_file() {
+        let theme = Theme::from_source_color(0xff4285f4u32);
+        assert_eq!(
+            theme.to_base16("Google Blue"),
+            include_str!("../testdata/theme_4285f4.base16.yaml")
+        );
+    }
+
+    #[test]
+    fn base16_slots() {
+        let theme = Theme::from_source_color(0xff4285f4u32);
+        for (yaml, count) in [
+            (theme.to_base16("Blue \"Dark\""), 16),
+            (theme.to_base24("Blue \"Dark\""), 24),
+        ] {
+            let mut lines = yaml.lines();
+            assert_eq!(lines.next(), Some("scheme: \"Blue \\\"Dark\\\"\""));
+            assert_eq!(lines.next(), Some("author: \"pymonet\""));
+            let slots: Vec<&str> = lines.collect();
+            assert_eq!(slots.len(), count);
+            for (index, line) in slots.iter().enumerate() {
+                let (key, value) = line.split_once(": ").unwrap();
+                assert_eq!(key, format!("base{index:02X}"));
+                let hex = value.trim_matches('"');
+                assert_eq!(value.len(), 8);
+                assert!(hex.len() == 6 && hex.chars().all(|c| c.is_ascii_hexdigit()));
+            }
+        }
+        assert!(theme.to_base24("x").starts_with(&theme.to_base16("x")));
+        let surface = hex_from_argb(theme.schemes.dark.surface);
+        assert!(theme
+            .to_base16("x")
+            .contains(&format!("base00: \"{}\"", &surface[1..])));
+    }
+
+    #[test]
+    fn xresources_slots() {
+        let theme = Theme::from_source_color(0xff4285f4u32);
+        for dark in [false, true] {
+            let xresources = theme.to_xresources(dark);
+            assert_eq!(xresources.lines().count(), 19);
+            for index in 0..16 {
+                assert!(xresources.contains(&format!("\n*.color{index}: #")));
+            }
+            let scheme = if dark {
+                &theme.schemes.dark
+            } else {
+                &theme.schemes.light
+            };
+            let background = format!("*.background: {}\n", hex_from_argb(scheme.surface));
+            assert!(xresources.starts_with(&background));
+        }
+    }
+
+    #[test]
+    fn ansi_colors_stay_recognizable() {
+        let theme = Theme::from_source_color(0xff4285f4u32);
+        for dark in [false, true] {
+            let colors = theme.ansi_colors(dark);
+            for red in [colors[1], colors[9]] {
+                let [_, r, g, b] = red;
+                assert!(r > g && r > b, "{red:?} is not red");
+            }
+            let [_, r, g, b] = colors[2];
+            assert!(g > r && g > b);
+            let [_, r, g, b] = colors[4];
+            assert!(b > r && b > g);
+        }
+        let light = theme.ansi_colors(false);
+        let dark = theme.ansi_colors(true);
+        assert!(lstar_from_argb(light[1]) < lstar_from_argb(dark[1]));
+    }
+
+    #[test]
+    fn gtk_css_matches_golden_file() {
+        let theme = Theme::from_source_color(0xff4285f4u32);
+        assert_eq!(
+            theme.to_gtk_css(true),
+            include_str!("../testdata/theme_4285f4_dark.gtk.css")
+        );
+        let light = theme.to_gtk_css(false);
+        assert_eq!(light.lines().count(), GTK_NAMED_COLORS.len() + 6);
+        let accent = hex_from_argb(theme.schemes.light.primary);
+        assert!(light.starts_with(&format!("@define-color accent_color {accent};\n")));
+    }
+
+    #[test]
+    fn gtk_css_custom_mapping() {
+        let theme = Theme::from_source_color(0xff4285f4u32);
+        let css = theme.to_gtk_css_with(false, &[("accent_bg_color", Role::Tertiary)]);
+        let tertiary = hex_from_argb(theme.schemes.light.tertiary);
+        assert!(css.starts_with(&format!("@define-color accent_bg_color {tertiary};\n")));
+        assert!(css.contains("@define-color success_fg_color #"));
+    }
+
+    #[test]
+    fn tailwind_matches_golden_file() {
+        assert_eq!(
+            success_theme().to_tailwind_json(),
+            include_str!("../testdata/theme_4285f4.tailwind.json")
+        );
+    }
+
+    #[test]
+    fn tailwind_is_valid_json() {
+        let mut theme = success_theme();
+        theme.custom_colors[0].color.name = String::from("say \"hi\"\\\n");
+        let options = TailwindOptions {
+            dark: true,
+            tonal_ramps: true,
+        };
+        let json: serde_json::Value =
+            serde_json::from_str(&theme.to_tailwind_json_with(options)).unwrap();
+        let primary = hex_from_argb(theme.schemes.dark.primary);
+        assert_eq!(json["primary"]["DEFAULT"], primary.as_str());
+        assert_eq!(
+            json["primary-40"],
+            hex_from_argb(theme.palettes.primary.tone(40)).as_str()
+        );
+        assert_eq!(json["neutral-variant-99"].as_str().map(str::len), Some(7));
+        assert!(json["say-\"hi\"\\"]["on-container"].is_string());
+        let plain: serde_json::Value = serde_json::from_str(&theme.to_tailwind_json()).unwrap();
+        assert!(plain.get("primary-40").is_none());
+    }
+}